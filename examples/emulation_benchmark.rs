@@ -0,0 +1,84 @@
+fn main() -> circuits_simulator::Result<()>
+{
+  // Times the emulation of a serial RLC circuit driven by a square wave
+  // analysed with 5000 frequencies, against the direct evaluation of one
+  // exponential per sample and per pulse. Run it with and without
+  // `--features rayon` to compare the serial and the parallel paths.
+  use std::time::Instant;
+
+  use circuits_simulator::{
+    id,
+    Circuit,
+    Component,
+    Dipole::{
+      Capacitor,
+      Inductor,
+      Resistor,
+    },
+  };
+  use num::Complex;
+
+  fn square_wave(x: f64) -> f64
+  {
+    if (x % 4e-3) > 2e-3 {
+      -1.
+    } else {
+      1.
+    }
+  }
+
+  let n_freqs = 5000;
+  let duration = 8e-3;
+  let step = duration / (2 * n_freqs) as f64;
+
+  let mut c = Circuit::new();
+  c.set_generator_fn(square_wave, duration, n_freqs)
+    .content_mut()
+    .push_serie(Component::from(Resistor(0.2e3)))
+    .push_serie(Component::from(Capacitor(10e-9)))
+    .push_serie(Component::from(Inductor(100e-3)));
+  c.init()?;
+
+  let start = Instant::now();
+  let (_, tensions, _) = c.emulate_one(duration, step, &id![1u8])?;
+  let time_required = start.elapsed().as_secs_f64();
+  println!(
+    "Emulated {} samples of {} frequencies in {}s (rayon: {})",
+    tensions.len(),
+    n_freqs,
+    time_required,
+    cfg!(feature = "rayon")
+  );
+  // Summing the samples gives a fingerprint of the results, which must not
+  // depend on the path
+  println!("Checksum: {:?}", tensions.iter().sum::<f64>());
+
+  let node = c.get_node(&id![1u8]).unwrap();
+  let start = Instant::now();
+  let naive = (0..tensions.len())
+    .map(|k| {
+      c.voltages()
+        .zip(&node.next_component_tensions)
+        .map(|((pulse, _), coef)| {
+          if *pulse == 0. {
+            coef.re
+          } else {
+            2. * (coef * Complex::new(0., k as f64 * step * pulse).exp()).re
+          }
+        })
+        .sum::<f64>()
+    })
+    .collect::<Vec<_>>();
+  let naive_time_required = start.elapsed().as_secs_f64();
+  let max_error = tensions
+    .iter()
+    .zip(&naive)
+    .map(|(a, b)| (a - b).abs())
+    .fold(0., f64::max);
+  println!(
+    "Direct evaluation of the exponentials: {}s, maximum deviation: {:e}",
+    naive_time_required, max_error
+  );
+
+  Ok(())
+}
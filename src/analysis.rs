@@ -0,0 +1,416 @@
+use num::Complex;
+
+use crate::{
+  error::{
+    Error::CircuitSolve,
+    Result,
+  },
+  util::{
+    coefficients,
+    roots,
+  },
+  Circuit,
+};
+
+/// Relative tolerance under which the imaginary part of a root is neglected.
+const REAL_ROOT_TOLERANCE: f64 = 1e-6;
+
+/// Finds the roots of a polynomial, turning a convergence failure into an
+/// error.
+fn find_roots(coefs: &[Complex<f64>]) -> Result<Vec<Complex<f64>>>
+{
+  roots(coefs).ok_or_else(|| {
+    CircuitSolve(format!(
+      "The root finder failed to converge on the polynomial {coefs:?}"
+    ))
+  })
+}
+
+/// The kind of a critical pulse of the impedance of a circuit, see
+/// [`Circuit::critical_frequencies`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CriticalKind
+{
+  /// A series resonance, where the impedance vanishes.
+  Resonance,
+  /// A parallel resonance (anti-resonance), where the impedance is infinite.
+  Antiresonance,
+}
+
+/// Gives the pulses of the oscillating complex pulses `±ω_d + i/τ`, leaving
+/// out the non-oscillating ones: their damped oscillation pulses `ω_d`, the
+/// absolute values of their real parts, or if `undamped` their undamped pulses
+/// `√(ω_d² + 1/τ²)`, their moduli.
+fn oscillation_pulses(roots: Vec<Complex<f64>>, undamped: bool) -> impl Iterator<Item = f64>
+{
+  roots
+    .into_iter()
+    .filter(|root| root.re.abs() > REAL_ROOT_TOLERANCE * root.norm())
+    .map(move |root| if undamped { root.norm() } else { root.re.abs() })
+}
+
+impl Circuit
+{
+  /// Computes the poles of the total impedance of the circuit, that is the
+  /// complex pulses at which the impedance is infinite. Since the impedance is
+  /// a rational fraction of the pulse ω, a pole p corresponds to the Laplace
+  /// variable s = i·p: its real part is an oscillation pulse and its imaginary
+  /// part a damping rate.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the impedance cannot be computed or if the root
+  /// finder fails to converge.
+  pub fn impedance_poles(&mut self) -> Result<Vec<Complex<f64>>>
+  {
+    self.init_impedance()?;
+    find_roots(&coefficients(&self.impedance().denominator))
+  }
+
+  /// Computes the zeros of the total impedance of the circuit, that is the
+  /// complex pulses at which the impedance vanishes. See
+  /// [`Circuit::impedance_poles`] for the meaning of a complex pulse.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the impedance cannot be computed or if the root
+  /// finder fails to converge.
+  pub fn impedance_zeros(&mut self) -> Result<Vec<Complex<f64>>>
+  {
+    self.init_impedance()?;
+    find_roots(&coefficients(&self.impedance().numerator))
+  }
+
+  /// Computes the eigenfrequencies of a lossless circuit, made only of
+  /// capacitors and inductors. They are the positive pulses (in rad/s) of the
+  /// real poles of the impedance, i.e. the undamped oscillation modes of the
+  /// network. The pulses are sorted in increasing order and the zero pulse is
+  /// left out.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the circuit contains a resistor or an equivalent
+  /// dipole, since its oscillations are then damped, or if the poles cannot be
+  /// computed.
+  pub fn eigenfrequencies(&mut self) -> Result<Vec<f64>>
+  {
+    self.init_impedance()?;
+    if !self.content().is_reactive() {
+      return Err(CircuitSolve(
+        "Eigenfrequencies are only defined for circuits made of capacitors and inductors"
+          .to_string(),
+      ));
+    }
+    let mut pulses = self
+      .impedance_poles()?
+      .into_iter()
+      .filter(|pole| pole.im.abs() <= REAL_ROOT_TOLERANCE * pole.norm())
+      .map(|pole| pole.re.abs())
+      .filter(|pulse| *pulse > 0f64)
+      .collect::<Vec<_>>();
+    pulses.sort_by(f64::total_cmp);
+    pulses.dedup_by(|a, b| (*a - *b).abs() <= REAL_ROOT_TOLERANCE * *b);
+    Ok(pulses)
+  }
+
+  /// Returns the coefficients of the characteristic polynomial of the circuit,
+  /// lowest order first, that is the denominator of its reduced impedance as a
+  /// polynomial of the pulse ω (not of the Laplace variable s = iω). Its roots
+  /// are the poles of the impedance (see [`Circuit::impedance_poles`]), i.e.
+  /// the natural pulses of the circuit left open. Those of the circuit driven
+  /// by its ideal source, which shorts its terminals, are the zeros of the
+  /// impedance (see [`Circuit::impedance_zeros`]).
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the impedance cannot be computed.
+  pub fn characteristic_polynomial(&mut self) -> Result<Vec<Complex<f64>>>
+  {
+    self.init_impedance()?;
+    Ok(coefficients(&self.impedance().denominator))
+  }
+
+  /// Returns the order of the circuit, that is the number of its independent
+  /// energy storage elements. It is computed as the highest degree between the
+  /// numerator and the denominator of the reduced impedance, so that capacitors
+  /// in series or inductors in parallel sharing a state are only counted once.
+  /// The denominator alone is not enough: a serial RLC circuit has an impedance
+  /// `(iLCω² + RCω - i) / Cω` of order 2.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the impedance cannot be computed.
+  pub fn order(&mut self) -> Result<usize>
+  {
+    self.init_impedance()?;
+    let degree = |p| coefficients(p).len().saturating_sub(1);
+    Ok(degree(&self.impedance().numerator).max(degree(&self.impedance().denominator)))
+  }
+
+  /// Computes the pseudo-period and the attenuation time of an underdamped
+  /// second-order circuit driven by its source, e.g. a serial RLC circuit.
+  /// They are derived from its pair of natural modes, the zeros of the
+  /// impedance seen by the source (see [`Circuit::impedance_zeros`]), which
+  /// are of the form `±ω_d + i/τ` as complex pulses, i.e. `-1/τ ± iω_d` in the
+  /// Laplace variable: the pseudo-period is `2π / ω_d` and the attenuation
+  /// time is `τ`, infinite for a lossless circuit.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the impedance cannot be computed or if the root
+  /// finder fails to converge.
+  ///
+  /// # Returns
+  ///
+  /// Returns `Some((pseudo_period, attenuation_time))` in seconds, or `None`
+  /// if the circuit does not have exactly two natural modes or if they do not
+  /// oscillate (overdamped or critically damped circuit).
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Inductor,
+  ///     Resistor,
+  ///   },
+  /// };
+  ///
+  /// let rlc = |r: f64| {
+  ///   let mut c = Circuit::new();
+  ///   c.content_mut()
+  ///     .push_serie(Component::from(Resistor(r)))
+  ///     .push_serie(Component::from(Capacitor(10e-9)))
+  ///     .push_serie(Component::from(Inductor(100e-3)));
+  ///   c
+  /// };
+  ///
+  /// // τ = 2L / R and ω_d = √(1 / LC - 1 / τ²)
+  /// let (pseudo_period, attenuation_time) = rlc(200.).second_order_parameters()?.unwrap();
+  /// assert!((attenuation_time - 1e-3).abs() < 1e-12);
+  /// assert!((pseudo_period - 2. * std::f64::consts::PI / (1e9f64 - 1e6).sqrt()).abs() < 1e-12);
+  ///
+  /// // Overdamped with a larger resistance
+  /// assert!(rlc(1e4).second_order_parameters()?.is_none());
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn second_order_parameters(&mut self) -> Result<Option<(f64, f64)>>
+  {
+    let impedance = self.total_impedance()?;
+    let modes = find_roots(&coefficients(&impedance.numerator))?;
+    let [mode, _] = modes.as_slice() else {
+      return Ok(None);
+    };
+    if mode.re.abs() <= REAL_ROOT_TOLERANCE * mode.norm() {
+      return Ok(None);
+    }
+    let pseudo_period = 2f64 * std::f64::consts::PI / mode.re.abs();
+    let attenuation_time = if mode.im.abs() <= REAL_ROOT_TOLERANCE * mode.norm() {
+      f64::INFINITY
+    } else {
+      mode.im.recip()
+    };
+    Ok(Some((pseudo_period, attenuation_time)))
+  }
+
+  /// Lists the critical pulses of the impedance of the circuit in increasing
+  /// order, in rad/s: its series resonances, where it vanishes (zeros of the
+  /// impedance), and its parallel resonances or anti-resonances, where it is
+  /// infinite (poles of the impedance). These are the notable points of a plot
+  /// of the impedance against the frequency.
+  ///
+  /// For a lossy circuit, the zeros and the poles are complex pulses (see
+  /// [`Circuit::impedance_poles`]): their damped oscillation pulses, the real
+  /// parts `ω_d` of `±ω_d + i/τ`, are listed, and the non-oscillating ones
+  /// (e.g. the pole of an RC circuit) are left out. Each pair of opposite
+  /// pulses is only listed once. [`Circuit::resonant_frequencies`] gives the
+  /// undamped pulses `√(ω_d² + 1/τ²)` of the same zeros and poles instead,
+  /// where the magnitude of the impedance is extremal. Both are equal for a
+  /// lossless circuit.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the impedance cannot be computed or if the root
+  /// finder fails to converge.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   Circuit,
+  ///   Component,
+  ///   CriticalKind,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Inductor,
+  ///   },
+  /// };
+  ///
+  /// // An inductor in series with a parallel LC tank
+  /// let mut c = Circuit::new();
+  /// c.content_mut()
+  ///   .push_serie(Component::from(Inductor(1e-3)))
+  ///   .push_serie(Component::from(Inductor(1e-3)));
+  /// c.get_comp_by_id_mut(&[1])
+  ///   .unwrap()
+  ///   .push_parallel(Component::from(Capacitor(1e-3)));
+  ///
+  /// let critical = c.critical_frequencies()?;
+  /// let kinds = critical.iter().map(|(_, kind)| *kind).collect::<Vec<_>>();
+  /// assert_eq!(
+  ///   kinds,
+  ///   [CriticalKind::Antiresonance, CriticalKind::Resonance]
+  /// );
+  /// assert!((critical[0].0 - 1e3).abs() < 1e-6);
+  /// assert!((critical[1].0 - 1e3 * 2f64.sqrt()).abs() < 1e-6);
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn critical_frequencies(&mut self) -> Result<Vec<(f64, CriticalKind)>>
+  {
+    let resonances = oscillation_pulses(self.impedance_zeros()?, false)
+      .map(|pulse| (pulse, CriticalKind::Resonance));
+    let antiresonances = oscillation_pulses(self.impedance_poles()?, false)
+      .map(|pulse| (pulse, CriticalKind::Antiresonance));
+    let mut critical = resonances.chain(antiresonances).collect::<Vec<_>>();
+    critical.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+    critical.dedup_by(|(a, kind_a), (b, kind_b)| {
+      kind_a == kind_b && (*a - *b).abs() <= REAL_ROOT_TOLERANCE * *b
+    });
+    Ok(critical)
+  }
+
+  /// Computes the resonant pulses of the circuit in increasing order, in
+  /// rad/s, that is the pulses at which the magnitude of its impedance is
+  /// extremal: minimal at a series resonance, maximal at a parallel one. They
+  /// are given by the oscillating zeros and poles of the impedance (see
+  /// [`Circuit::critical_frequencies`]), a complex pulse `±ω_d + i/τ` giving
+  /// its modulus `√(ω_d² + 1/τ²)`, the undamped resonant pulse, whereas
+  /// [`Circuit::critical_frequencies`] gives its damped oscillation pulse
+  /// `ω_d`. Both are equal for a lossless circuit.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the impedance cannot be computed or if the root
+  /// finder fails to converge.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Inductor,
+  ///     Resistor,
+  ///   },
+  /// };
+  ///
+  /// let (r, l, c) = (200., 100e-3, 10e-9);
+  /// let mut rlc = Circuit::new();
+  /// rlc
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(r)))
+  ///   .push_serie(Component::from(Capacitor(c)))
+  ///   .push_serie(Component::from(Inductor(l)));
+  ///
+  /// let resonances = rlc.resonant_frequencies()?;
+  /// assert_eq!(resonances.len(), 1);
+  /// let pulse = 1. / (l * c).sqrt();
+  /// assert!((resonances[0] - pulse).abs() < 1e-9 * pulse);
+  /// // The damped oscillation pulse, with τ = 2L / R, is slightly lower
+  /// let damped = (pulse.powi(2) - (r / (2. * l)).powi(2)).sqrt();
+  /// let critical = rlc.critical_frequencies()?;
+  /// assert!((critical[0].0 - damped).abs() < 1e-9 * pulse);
+  ///
+  /// // The impedance is minimal there
+  /// rlc.init_impedance()?;
+  /// let magnitude = |pulse: f64| rlc.content().impedance(pulse).norm();
+  /// assert!((magnitude(pulse) - r).abs() < 1e-6);
+  /// assert!(magnitude(0.99 * pulse) > r && magnitude(1.01 * pulse) > r);
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn resonant_frequencies(&mut self) -> Result<Vec<f64>>
+  {
+    let mut pulses = oscillation_pulses(self.impedance_zeros()?, true)
+      .chain(oscillation_pulses(self.impedance_poles()?, true))
+      .collect::<Vec<_>>();
+    pulses.sort_by(f64::total_cmp);
+    pulses.dedup_by(|a, b| (*a - *b).abs() <= REAL_ROOT_TOLERANCE * *b);
+    Ok(pulses)
+  }
+
+  /// Computes the time constants of the natural modes of the circuit driven by
+  /// its source, the zeros of the impedance it sees (see
+  /// [`Circuit::second_order_parameters`]). A mode `±ω_d + i/τ` as a complex
+  /// pulse decays as `exp(-t/τ)`, so its time constant is `τ`, the inverse of
+  /// the absolute value of the real part of the pole `-1/τ ± iω_d` in the
+  /// Laplace variable. A non-oscillating mode gives its own constant, and a
+  /// pair of oscillating modes gives the single constant of their envelope.
+  /// The undamped modes, whose time constant is infinite, are left out.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the impedance cannot be computed or if the root
+  /// finder fails to converge.
+  ///
+  /// # Returns
+  ///
+  /// The time constants in seconds, sorted in increasing order.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Inductor,
+  ///     Resistor,
+  ///   },
+  /// };
+  ///
+  /// let mut c = Circuit::new();
+  /// c.content_mut()
+  ///   .push_serie(Component::from(Resistor(1e3)))
+  ///   .push_serie(Component::from(Capacitor(1e-6)));
+  /// let time_constants = c.time_constants()?;
+  /// assert_eq!(time_constants.len(), 1);
+  /// assert!((time_constants[0] - 1e-3).abs() < 1e-12);
+  ///
+  /// let rlc = |r: f64| {
+  ///   let mut c = Circuit::new();
+  ///   c.content_mut()
+  ///     .push_serie(Component::from(Resistor(r)))
+  ///     .push_serie(Component::from(Capacitor(10e-9)))
+  ///     .push_serie(Component::from(Inductor(100e-3)));
+  ///   c
+  /// };
+  ///
+  /// // The envelope of an underdamped serial RLC circuit, with τ = 2L / R
+  /// let time_constants = rlc(200.).time_constants()?;
+  /// assert_eq!(time_constants.len(), 1);
+  /// assert!((time_constants[0] - 1e-3).abs() < 1e-12);
+  ///
+  /// // Two real modes once overdamped
+  /// assert_eq!(rlc(1e4).time_constants()?.len(), 2);
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn time_constants(&mut self) -> Result<Vec<f64>>
+  {
+    let impedance = self.total_impedance()?;
+    let mut time_constants = find_roots(&coefficients(&impedance.numerator))?
+      .into_iter()
+      // Only one mode of each oscillating pair ±ω_d + i/τ is kept
+      .filter(|mode| mode.re >= -REAL_ROOT_TOLERANCE * mode.norm())
+      .filter(|mode| mode.im.abs() > REAL_ROOT_TOLERANCE * mode.norm())
+      .map(|mode| mode.im.abs().recip())
+      .collect::<Vec<_>>();
+    time_constants.sort_by(f64::total_cmp);
+    Ok(time_constants)
+  }
+}
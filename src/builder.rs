@@ -0,0 +1,171 @@
+use crate::{
+  error::Result,
+  validation::{
+    collect_problems,
+    report,
+  },
+  Circuit,
+  Component,
+  ComponentContent,
+  Dipole,
+};
+
+/// A builder of the content of a [`Circuit`], describing nested series and
+/// parallel combinations with closures instead of pushing components one by
+/// one. The components added directly to the builder are in series, and the
+/// IDs of the whole tree are set once by [`CircuitBuilder::build`]. A
+/// combination of a single component is that component.
+///
+/// # Example
+///
+/// ```
+/// use circuits_simulator::{
+///   CircuitBuilder,
+///   Component,
+///   Dipole::{
+///     Capacitor,
+///     Inductor,
+///     Resistor,
+///   },
+/// };
+///
+/// // Two RC branches in parallel, in series with an inductor
+/// let mut built = CircuitBuilder::new()
+///   .inductor(1e-3)
+///   .parallel(|b| {
+///     b.series(|b| b.resistor(100.).capacitor(1e-6))
+///       .series(|b| b.resistor(200.).capacitor(2e-6))
+///   })
+///   .build()?;
+///
+/// let mut branches = Component::from(Resistor(100.));
+/// branches.push_serie(Component::from(Capacitor(1e-6)));
+/// let mut branch = Component::from(Resistor(200.));
+/// branch.push_serie(Component::from(Capacitor(2e-6)));
+/// branches.push_parallel(branch);
+/// let mut manual = Component::from(Inductor(1e-3));
+/// manual.push_serie(branches);
+///
+/// built.content_mut().init_impedance()?;
+/// manual.init_impedance()?;
+/// for pulse in [1e2, 1e3, 1e4, 1e5] {
+///   assert!((built.content().impedance(pulse) - manual.impedance(pulse)).norm() < 1e-9);
+/// }
+/// // The IDs are set for the whole tree
+/// assert!(built.get_comp_by_id(&[1, 1, 0]).is_some());
+/// # Ok::<(), circuits_simulator::Error>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CircuitBuilder
+{
+  components: Vec<Component>,
+}
+
+impl CircuitBuilder
+{
+  /// Creates an empty builder.
+  #[inline]
+  pub fn new() -> Self
+  {
+    Self::default()
+  }
+
+  /// Adds a component.
+  #[inline]
+  pub fn component(mut self, component: Component) -> Self
+  {
+    self.components.push(component);
+    self
+  }
+
+  /// Adds a simple component made of a dipole.
+  #[inline]
+  pub fn dipole(self, dipole: Dipole) -> Self
+  {
+    self.component(Component::from(dipole))
+  }
+
+  /// Adds a resistor of a given resistance in ohms.
+  #[inline]
+  pub fn resistor(self, r: f64) -> Self
+  {
+    self.dipole(Dipole::Resistor(r))
+  }
+
+  /// Adds a capacitor of a given capacitance in farads.
+  #[inline]
+  pub fn capacitor(self, c: f64) -> Self
+  {
+    self.dipole(Dipole::Capacitor(c))
+  }
+
+  /// Adds an inductor of a given inductance in henries.
+  #[inline]
+  pub fn inductor(self, l: f64) -> Self
+  {
+    self.dipole(Dipole::Inductor(l))
+  }
+
+  /// Adds a series combination of the components added by `build` to an
+  /// empty builder.
+  #[inline]
+  pub fn series<F>(self, build: F) -> Self
+  where
+    F: FnOnce(Self) -> Self,
+  {
+    let components = build(Self::new()).components;
+    self.group(components, ComponentContent::Series)
+  }
+
+  /// Adds a parallel combination of the components added by `build` to an
+  /// empty builder.
+  #[inline]
+  pub fn parallel<F>(self, build: F) -> Self
+  where
+    F: FnOnce(Self) -> Self,
+  {
+    let components = build(Self::new()).components;
+    self.group(components, ComponentContent::Parallel)
+  }
+
+  /// Adds a combination of components, or the component itself if there is
+  /// only one.
+  fn group(
+    self,
+    mut components: Vec<Component>,
+    combine: fn(Vec<Component>) -> ComponentContent,
+  ) -> Self
+  {
+    if components.len() == 1 {
+      self.component(components.pop().unwrap())
+    } else {
+      self.component(Component::from(combine(components)))
+    }
+  }
+
+  /// Builds a circuit whose content is the series combination of the
+  /// components added to the builder, without any pulse.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error listing the structural problems of the content (see
+  /// [`Circuit::validate`]), e.g. if the builder or one of its combinations is
+  /// empty.
+  pub fn build(self) -> Result<Circuit>
+  {
+    let mut content = Component::new();
+    content.content = match self.components.len() {
+      0 => ComponentContent::Poisoned,
+      1 => self.components.into_iter().next().unwrap().content,
+      _ => ComponentContent::Series(self.components),
+    };
+    content.set_id(vec![]);
+
+    let mut problems = Vec::new();
+    collect_problems(&content, &vec![], &mut problems);
+    report(problems)?;
+    let mut circuit = Circuit::new();
+    *circuit.content_mut() = content;
+    Ok(circuit)
+  }
+}
@@ -0,0 +1,348 @@
+//! Export of transfer functions to discrete-time filters.
+//!
+//! The bilinear transform maps the Laplace variable s to the z-transform
+//! variable with `s = K (z - 1) / (z + 1)`, where `K = 2 fs` for a sample rate
+//! fs. It maps the whole imaginary axis onto the unit circle, so that a stable
+//! analog filter gives a stable digital filter, at the cost of a warping of the
+//! frequency axis: the analog pulse ω is mapped to the digital pulse `2
+//! atan(ω / K)`. Pre-warping at a pulse ωp uses `K = ωp / tan(ωp / (2 fs))`
+//! instead, so that the analog and digital responses match exactly at ωp.
+
+use num::Complex;
+use num_traits::Zero;
+
+use crate::{
+  error::{
+    Error::CircuitSolve,
+    Result,
+  },
+  transfer::pulse_to_laplace,
+  util::{
+    coefficients,
+    roots,
+  },
+  Circuit,
+  Id,
+};
+
+/// Relative tolerance under which imaginary parts are neglected.
+const REAL_TOLERANCE: f64 = 1e-9;
+
+/// Groups roots into conjugate pairs, remaining real roots being grouped two by
+/// two. There is at most one group of a single (real) root.
+fn group_roots(mut roots: Vec<Complex<f64>>) -> Vec<Vec<Complex<f64>>>
+{
+  let is_real = |r: &Complex<f64>| r.im.abs() <= REAL_TOLERANCE * r.norm().max(1f64);
+  let mut groups = Vec::new();
+  let mut reals = Vec::new();
+  while let Some(root) = roots.pop() {
+    if is_real(&root) {
+      reals.push(Complex::from(root.re));
+      continue;
+    }
+    // Conjugate of the root, which must also be a root of a real polynomial
+    let partner = roots
+      .iter()
+      .enumerate()
+      .min_by(|(_, a), (_, b)| {
+        (*a - root.conj())
+          .norm()
+          .total_cmp(&(*b - root.conj()).norm())
+      })
+      .map(|(k, _)| k);
+    match partner {
+      Some(k) => groups.push(vec![root, roots.swap_remove(k)]),
+      None => reals.push(Complex::from(root.re)),
+    }
+  }
+  reals.sort_by(|a, b| a.re.total_cmp(&b.re));
+  groups.extend(reals.chunks(2).map(<[_]>::to_vec));
+  groups
+}
+
+/// Coefficients of the product of `(1 - r z⁻¹)` over a group of roots.
+fn section_polynomial(group: &[Complex<f64>]) -> [f64; 3]
+{
+  match group {
+    [] => [1f64, 0f64, 0f64],
+    [r] => [1f64, -r.re, 0f64],
+    [r1, r2, ..] => [1f64, -(r1 + r2).re, (r1 * r2).re],
+  }
+}
+
+impl Circuit
+{
+  /// Exports the transfer function between the potentials of two nodes as a
+  /// digital filter, obtained with the bilinear transform (see the module
+  /// documentation) and factored into second-order sections.
+  ///
+  /// Each section is given as `[b0, b1, b2, a0, a1, a2]`, standing for `(b0 +
+  /// b1 z⁻¹ + b2 z⁻²) / (a0 + a1 z⁻¹ + a2 z⁻²)` with `a0 = 1`, and the filter
+  /// is their product. Conjugate poles (and zeros) are kept in the same
+  /// section. The sections are ordered by increasing modulus of their poles,
+  /// so that the most resonant section, whose poles are the closest to the
+  /// unit circle, comes last, and each one takes the zeros closest to its
+  /// poles. The overall gain is applied to the first section.
+  ///
+  /// # Arguments
+  ///
+  /// * `from` - The ID of the input node. `&vec![]` stands for the source.
+  /// * `to` - The ID of the output node.
+  /// * `sample_rate` - The sample rate of the digital filter, in hertz.
+  /// * `prewarp` - The pulse (in rad/s) at which the analog and digital
+  ///   responses match exactly, below the Nyquist pulse `π * sample_rate`.
+  ///   `None` applies the plain bilinear transform, which matches them at the
+  ///   zero pulse only.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the transfer function cannot be computed, if its
+  /// coefficients in s are not real, if it is improper (more zeros than
+  /// poles), if its roots cannot be found, or if the sample rate or the
+  /// pre-warping pulse are invalid.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  /// };
+  ///
+  /// // A first-order low-pass filter, whose gain is 1 at the zero frequency
+  /// let mut c = Circuit::new();
+  /// c.content_mut()
+  ///   .push_serie(Component::from(Resistor(1e3)))
+  ///   .push_serie(Component::from(Capacitor(1e-6)));
+  ///
+  /// let sections = c.to_biquad_cascade(&id![], &id![1u8], 48e3, None)?;
+  /// let [b0, b1, b2, a0, a1, a2] = sections[0];
+  /// assert_eq!(sections.len(), 1);
+  /// assert!(((b0 + b1 + b2) / (a0 + a1 + a2) - 1.).abs() < 1e-12);
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn to_biquad_cascade(
+    &mut self,
+    from: &Id,
+    to: &Id,
+    sample_rate: f64,
+    prewarp: Option<f64>,
+  ) -> Result<Vec<[f64; 6]>>
+  {
+    if !(sample_rate > 0f64 && sample_rate.is_finite()) {
+      return Err(CircuitSolve(format!(
+        "The sample rate must be positive and finite, got {sample_rate:?} Hz"
+      )));
+    }
+    let k = match prewarp {
+      None => 2f64 * sample_rate,
+      Some(pulse) if pulse > 0f64 && pulse < std::f64::consts::PI * sample_rate =>
+        pulse / (pulse / (2f64 * sample_rate)).tan(),
+      Some(pulse) =>
+        return Err(CircuitSolve(format!(
+          "The pre-warping pulse must be positive and below the Nyquist pulse, got {pulse:?} \
+           rad/s"
+        ))),
+    };
+
+    let transfer = self.transfer_function(from, to)?;
+    let mut numerator = pulse_to_laplace(&coefficients(&transfer.numerator));
+    let mut denominator = pulse_to_laplace(&coefficients(&transfer.denominator));
+    // The fraction is only defined up to a common (possibly complex) factor, so
+    // the denominator is made monic before checking the coefficients
+    let Some(&denominator_lead) = denominator.last() else {
+      return Err(CircuitSolve(
+        "The transfer function has a zero denominator".to_string(),
+      ));
+    };
+    for c in numerator.iter_mut().chain(denominator.iter_mut()) {
+      *c /= denominator_lead;
+    }
+    if numerator
+      .iter()
+      .chain(denominator.iter())
+      .any(|c| c.im.abs() > REAL_TOLERANCE * c.norm())
+    {
+      return Err(CircuitSolve(
+        "The transfer function does not have real coefficients in s and cannot be exported as \
+         a real filter"
+          .to_string(),
+      ));
+    }
+    if numerator.len() > denominator.len() {
+      return Err(CircuitSolve(
+        "The transfer function is improper (more zeros than poles) and has no bilinear transform"
+          .to_string(),
+      ));
+    }
+    let Some(&lead) = numerator.last() else {
+      // A zero transfer function
+      return Ok(vec![[0f64, 0f64, 0f64, 1f64, 0f64, 0f64]]);
+    };
+    let find_roots = |coefs: &[Complex<f64>]| {
+      roots(coefs).ok_or_else(|| {
+        CircuitSolve(format!(
+          "The root finder failed to converge on the polynomial {coefs:?}"
+        ))
+      })
+    };
+    let (zeros, poles) = (find_roots(&numerator)?, find_roots(&denominator)?);
+
+    // Each factor (s - r) becomes (K - r) (z - (K + r) / (K - r)) / (z + 1), and
+    // the zeros at infinity become zeros at z = -1
+    let mut gain = lead;
+    let mut map_roots = |roots: &[Complex<f64>], is_zero: bool| {
+      roots
+        .iter()
+        .map(|r| {
+          if is_zero {
+            gain *= k - r;
+          } else {
+            gain /= k - r;
+          }
+          (k + r) / (k - r)
+        })
+        .collect::<Vec<_>>()
+    };
+    let mut digital_zeros = map_roots(&zeros, true);
+    let digital_poles = map_roots(&poles, false);
+    digital_zeros.resize(digital_poles.len(), Complex::from(-1f64));
+
+    let mut pole_groups = group_roots(digital_poles);
+    pole_groups.sort_by(|a, b| {
+      let radius = |group: &Vec<Complex<f64>>| group.iter().fold(0f64, |acc, r| acc.max(r.norm()));
+      radius(a).total_cmp(&radius(b))
+    });
+    let mut zero_groups = group_roots(digital_zeros);
+    // The sections with the poles closest to the unit circle pick their zeros
+    // first
+    let mut sections = vec![[0f64; 6]; pole_groups.len()];
+    for (section, poles) in sections.iter_mut().zip(pole_groups.iter()).rev() {
+      let zeros = match poles.first() {
+        Some(pole) => {
+          let closest = (0..zero_groups.len()).min_by(|&a, &b| {
+            let distance = |k: usize| {
+              zero_groups[k]
+                .iter()
+                .map(|zero| (zero - pole).norm())
+                .fold(f64::INFINITY, f64::min)
+            };
+            distance(a).total_cmp(&distance(b))
+          });
+          closest.map_or(Vec::new(), |k| zero_groups.swap_remove(k))
+        },
+        None => Vec::new(),
+      };
+      let [b0, b1, b2] = section_polynomial(&zeros);
+      let [a0, a1, a2] = section_polynomial(poles);
+      *section = [b0, b1, b2, a0, a1, a2];
+    }
+    if sections.is_empty() {
+      sections.push([1f64, 0f64, 0f64, 1f64, 0f64, 0f64]);
+    }
+    // The gain is real for a real filter
+    let gain = if gain.is_zero() { 0f64 } else { gain.re };
+    for b in sections[0][..3].iter_mut() {
+      *b *= gain;
+    }
+    Ok(sections)
+  }
+
+  /// Exports the transfer function from the source to the potential of a node
+  /// as the coefficients `(b, a)` of a digital filter, obtained with the
+  /// bilinear transform (see the module documentation): the filter is
+  /// `(b[0] + b[1] z⁻¹ + ...) / (a[0] + a[1] z⁻¹ + ...)` with `a[0] = 1`, which
+  /// is the convention of `lfilter` in SciPy. It is the product of the sections
+  /// of [`Circuit::to_biquad_cascade`], which are better suited to high orders,
+  /// whose expanded coefficients are sensitive to rounding errors.
+  ///
+  /// # Arguments
+  ///
+  /// * `output` - The ID of the output node.
+  /// * `sample_rate` - The sample rate of the digital filter, in hertz.
+  /// * `prewarp` - The pulse (in rad/s) at which the analog and digital
+  ///   responses match exactly, see [`Circuit::to_biquad_cascade`].
+  ///
+  /// # Errors
+  ///
+  /// Returns an error in the same cases as [`Circuit::to_biquad_cascade`].
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use std::f64::consts::PI;
+  ///
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  /// };
+  /// use num::Complex;
+  ///
+  /// // A first-order low-pass filter, whose cutoff frequency is about 1.6 kHz,
+  /// // sampled at 8 kHz
+  /// let mut c = Circuit::new();
+  /// c.content_mut()
+  ///   .push_serie(Component::from(Resistor(1e3)))
+  ///   .push_serie(Component::from(Capacitor(1e-7)));
+  /// let (cutoff, sample_rate) = (1e4, 8e3);
+  ///
+  /// // The gain of the digital filter at the cutoff pulse
+  /// let gain = |(b, a): (Vec<f64>, Vec<f64>)| {
+  ///   let z_inv = Complex::new(0., -cutoff / sample_rate).exp();
+  ///   let eval = |p: &[f64]| {
+  ///     p.iter()
+  ///       .rev()
+  ///       .fold(Complex::from(0.), |acc, c| acc * z_inv + c)
+  ///   };
+  ///   (eval(&b) / eval(&a)).norm()
+  /// };
+  ///
+  /// // The warping moves the cutoff frequency away...
+  /// let (b, a) = c.to_digital_filter(&id![1], sample_rate, None)?;
+  /// assert_eq!((b.len(), a.len(), a[0]), (2, 2, 1.));
+  /// assert!((gain((b, a)) - 0.5f64.sqrt()).abs() > 1e-2);
+  /// // ...unless the transform is pre-warped at the cutoff pulse
+  /// let filter = c.to_digital_filter(&id![1], sample_rate, Some(cutoff))?;
+  /// assert!((gain(filter) - 0.5f64.sqrt()).abs() < 1e-12);
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn to_digital_filter(
+    &mut self,
+    output: &Id,
+    sample_rate: f64,
+    prewarp: Option<f64>,
+  ) -> Result<(Vec<f64>, Vec<f64>)>
+  {
+    let sections = self.to_biquad_cascade(&vec![], output, sample_rate, prewarp)?;
+    let multiply = |p: Vec<f64>, q: &[f64]| {
+      let mut product = vec![0f64; p.len() + q.len() - 1];
+      for (i, p) in p.iter().enumerate() {
+        for (j, q) in q.iter().enumerate() {
+          product[i + j] += p * q;
+        }
+      }
+      product
+    };
+    let (mut b, mut a) = sections
+      .iter()
+      .fold((vec![1f64], vec![1f64]), |(b, a), section| {
+        (multiply(b, &section[..3]), multiply(a, &section[3..]))
+      });
+    // The sections of first order end with zero coefficients
+    let order = |p: &[f64]| p.iter().rposition(|coef| !coef.is_zero()).unwrap_or(0);
+    let length = order(&b).max(order(&a)) + 1;
+    b.truncate(length);
+    a.truncate(length);
+    Ok((b, a))
+  }
+}
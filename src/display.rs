@@ -0,0 +1,101 @@
+use std::fmt::{
+  Display,
+  Formatter,
+  Result,
+};
+
+use crate::{
+  units::format_si,
+  util::coefficients,
+  Component,
+  ComponentContent,
+  Dipole,
+};
+
+/// Renders a dipole with its value in base SI units and an SI prefix: `R(200)`,
+/// `C(10n)` or `L(100m)` for resistors, capacitors and inductors, `Z(n)` for an
+/// equivalent dipole whose impedance is a fraction of degree `n` (the highest
+/// degree of its numerator and denominator), `T(ratio, load)` for a
+/// transformer and `E(control, gain, load)` for a voltage-controlled voltage
+/// source.
+impl Display for Dipole
+{
+  fn fmt(&self, f: &mut Formatter) -> Result
+  {
+    use Dipole::*;
+    match self {
+      Resistor(r) => write!(f, "R({})", format_si(*r)),
+      Capacitor(c) => write!(f, "C({})", format_si(*c)),
+      Inductor(l) => write!(f, "L({})", format_si(*l)),
+      Short => write!(f, "Short"),
+      Open => write!(f, "Open"),
+      Equivalent(impedance) => {
+        let degree = |coefs: Vec<_>| coefs.len().saturating_sub(1);
+        write!(
+          f,
+          "Z({})",
+          degree(coefficients(&impedance.numerator))
+            .max(degree(coefficients(&impedance.denominator)))
+        )
+      },
+      Transformer { ratio, load } => write!(f, "T({}, {load})", format_si(*ratio)),
+      Vcvs {
+        control,
+        gain,
+        load,
+      } => write!(f, "E({control:?}, {}, {load})", format_si(*gain)),
+      Poisoned => write!(f, "Poisoned"),
+    }
+  }
+}
+
+/// Renders the tree of a component, e.g. `Series[ R(200), Parallel[ C(10n),
+/// L(100m) ] ]`, its dipoles being rendered as with [`Dipole`]'s `Display`.
+///
+/// # Example
+///
+/// ```
+/// use circuits_simulator::{
+///   Component,
+///   Dipole::{
+///     Capacitor,
+///     Inductor,
+///     Resistor,
+///     Transformer,
+///   },
+/// };
+///
+/// let tank = Component::from(Capacitor(10e-9)) | Component::from(Inductor(100e-3));
+/// let circuit = Component::from(Resistor(200.)) + tank;
+/// assert_eq!(
+///   circuit.to_string(),
+///   "Series[ R(200), Parallel[ C(10n), L(100m) ] ]"
+/// );
+///
+/// let transformer = Component::from(Transformer {
+///   ratio: 2.,
+///   load:  Box::new(Component::from(Resistor(4.7e3))),
+/// });
+/// assert_eq!(transformer.to_string(), "T(2, R(4.7k))");
+/// ```
+impl Display for Component
+{
+  fn fmt(&self, f: &mut Formatter) -> Result
+  {
+    use ComponentContent::*;
+    let (name, components) = match &self.content {
+      Series(components) => ("Series", components),
+      Parallel(components) => ("Parallel", components),
+      Simple(dipole) => return write!(f, "{dipole}"),
+      Poisoned => return write!(f, "Poisoned"),
+    };
+    write!(f, "{name}[")?;
+    for (k, component) in components.iter().enumerate() {
+      if k > 0 {
+        write!(f, ",")?;
+      }
+      write!(f, " {component}")?;
+    }
+    write!(f, " ]")
+  }
+}
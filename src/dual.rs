@@ -0,0 +1,166 @@
+//! Dual networks.
+//!
+//! The dual of a network at the impedance level R0 is the network whose
+//! impedance is `R0² / Z`, where Z is the impedance of the original network.
+//! It is obtained by swapping series and parallel combinations and by mapping
+//! each dipole to its dual: a resistance R becomes `R0² / R` (with R0 = 1 Ω,
+//! the resistance becomes numerically equal to the conductance), an inductance
+//! L becomes a capacitance `L / R0²` and a capacitance C an inductance
+//! `C * R0²`.
+
+use fractios::RatioFrac;
+use num::Complex;
+
+use crate::{
+  error::{
+    Error::CircuitBuild,
+    Result,
+  },
+  Circuit,
+  Component,
+  ComponentContent,
+  Dipole,
+};
+
+impl Dipole
+{
+  /// Returns the dual of the dipole at the impedance level `r0`. See the
+  /// [`Circuit::dual`] documentation.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the dipole is poisoned or is a controlled source.
+  pub fn dual(&self, r0: f64) -> Result<Dipole>
+  {
+    let r0_squared = r0 * r0;
+    Ok(match self {
+      Dipole::Resistor(r) => Dipole::Resistor(r0_squared / r),
+      Dipole::Capacitor(c) => Dipole::Inductor(c * r0_squared),
+      Dipole::Inductor(l) => Dipole::Capacitor(l / r0_squared),
+      Dipole::Short => Dipole::Open,
+      Dipole::Open => Dipole::Short,
+      Dipole::Equivalent(e) => {
+        let mut impedance = e.clone();
+        impedance.inv_inplace();
+        impedance *= &RatioFrac::from(Complex::from(r0_squared));
+        Dipole::Equivalent(impedance)
+      },
+      // The load seen from the primary winding is scaled by ratio², hence its
+      // dual by 1 / ratio²
+      Dipole::Transformer { ratio, load } => Dipole::Transformer {
+        ratio: ratio.recip(),
+        load:  Box::new(load.dual(r0)?),
+      },
+      Dipole::Vcvs { .. } =>
+        return Err(CircuitBuild(
+          "Cannot dualize a controlled source".to_string(),
+        )),
+      Dipole::Poisoned => return Err(CircuitBuild("Cannot dualize a poisoned dipole".to_string())),
+    })
+  }
+}
+
+impl Component
+{
+  /// Returns the dual of the component at the impedance level `r0`, keeping
+  /// the IDs of its subcomponents. See the [`Circuit::dual`] documentation.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the component or one of its subcomponents is
+  /// poisoned.
+  pub fn dual(&self, r0: f64) -> Result<Component>
+  {
+    use ComponentContent::*;
+    let dual_components = |components: &Vec<Component>| {
+      components
+        .iter()
+        .map(|component| component.dual(r0))
+        .collect::<Result<Vec<_>>>()
+    };
+    let content = match &self.content {
+      Series(components) => Parallel(dual_components(components)?),
+      Parallel(components) => Series(dual_components(components)?),
+      Simple(dipole) => Simple(dipole.dual(r0)?),
+      Poisoned =>
+        return Err(CircuitBuild(
+          "Cannot dualize a poisoned component".to_string(),
+        )),
+    };
+    let mut component = Component::from(content);
+    component.fore_node_id = self.fore_node_id.clone();
+    Ok(component)
+  }
+}
+
+impl Circuit
+{
+  /// Returns the dual of the circuit at the impedance level R0 = 1 Ω, that is
+  /// the circuit whose impedance is the inverse of the impedance of `self`:
+  /// series and parallel combinations are swapped, resistances are replaced by
+  /// their conductances and inductances by capacitances (and conversely) of
+  /// the same value. See [`Circuit::dual_with_level`] to choose R0.
+  ///
+  /// The dual of a voltage source is a current source, which this crate does
+  /// not support: the dual circuit keeps the same source spectrum, which should
+  /// be read as a current source of spectrum `V / R0` (in amperes) for the
+  /// tensions of the dual to be `R0` times the currents of the original
+  /// circuit.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the circuit contains a poisoned component.
+  #[inline]
+  pub fn dual(&self) -> Result<Circuit>
+  {
+    self.dual_with_level(1f64)
+  }
+
+  /// Returns the dual of the circuit at the impedance level `r0`, whose
+  /// impedance is `r0² / Z` where Z is the impedance of `self`. See
+  /// [`Circuit::dual`].
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if `r0` is not positive and finite or if the circuit
+  /// contains a poisoned component.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  /// };
+  ///
+  /// let mut c = Circuit::new();
+  /// c.content_mut()
+  ///   .push_serie(Component::from(Resistor(200.)))
+  ///   .push_serie(Component::from(Capacitor(10e-9)));
+  ///
+  /// // A resistor in parallel with an inductor
+  /// let mut dual = c.dual_with_level(50.)?;
+  /// c.init_impedance()?;
+  /// dual.init_impedance()?;
+  /// let z = c.content().impedance(1e5);
+  /// let z_dual = dual.content().impedance(1e5);
+  /// assert!((z * z_dual - 2500.).norm() < 1e-6);
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn dual_with_level(&self, r0: f64) -> Result<Circuit>
+  {
+    if !(r0 > 0f64 && r0.is_finite()) {
+      return Err(CircuitBuild(format!(
+        "The impedance level of a dual circuit must be positive and finite, got {r0:?} Ω"
+      )));
+    }
+    let mut dual = Circuit::new();
+    *dual.content_mut() = self.content().dual(r0)?;
+    dual.replace_source(self.generator().clone());
+    Ok(dual)
+  }
+}
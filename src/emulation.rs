@@ -1,23 +1,149 @@
+use std::{
+  borrow::Cow,
+  collections::HashMap,
+  io::Write,
+};
+
 use num::Complex;
 use num_traits::Zero;
 
 use crate::{
   error::{
-    short_circuit_current,
+    Error::CircuitSolve,
     Result,
   },
+  util::SplitMix64,
   Circuit,
+  Component,
+  ComponentContent,
   Id,
+  Node,
 };
 
 /// Return type of the emulation functions.
 /// - The first vector contains the current values of the node.
 /// - The second vector contains the voltage values of the node.
 /// - The third vector contains the potential values of the node.
-type EmulationData = (Vec<f64>, Vec<f64>, Vec<f64>);
+pub(crate) type EmulationData = (Vec<f64>, Vec<f64>, Vec<f64>);
+
+/// Collects the IDs of the simple dipoles of a component.
+fn collect_dipoles(component: &Component) -> Vec<Id>
+{
+  component
+    .iter()
+    .filter(|(_, component)| matches!(component.content, ComponentContent::Simple(_)))
+    .map(|(_, component)| component.fore_node_id.clone())
+    .collect()
+}
+
+/// Adds the signals of an emulation to those of another one, sample by
+/// sample.
+fn superpose(
+  (currents, tensions, potentials): (&mut [f64], &mut [f64], &mut [f64]),
+  (added_currents, added_tensions, added_potentials): EmulationData,
+)
+{
+  for (buffer, added) in [
+    (currents, added_currents),
+    (tensions, added_tensions),
+    (potentials, added_potentials),
+  ] {
+    for (value, added) in buffer.iter_mut().zip(added) {
+      *value += added;
+    }
+  }
+}
+
+/// Number of consecutive samples over which the emulation turns the phasors of
+/// the pulses by recurrence, before computing them exactly again. The relative
+/// error of the recurrence grows linearly with the number of steps, and stays
+/// around 1e-13 over such a block.
+const PHASOR_RESYNC_PERIOD: usize = 256;
+
+/// Relative size of the imaginary part of the gains of a resistive circuit
+/// under which they are taken as real, see [`Circuit::is_resistive`].
+const REAL_GAIN_TOLERANCE: f64 = 1e-12;
+
+/// Checks that a duration and a step define a non-empty, finite time grid.
+pub(crate) fn check_time_grid(duration: f64, step: f64) -> Result<()>
+{
+  if !(step > 0f64 && step.is_finite()) {
+    return Err(CircuitSolve(format!(
+      "The emulation step must be positive and finite, got {step:?} s"
+    )));
+  }
+  if !(duration >= 0f64 && duration.is_finite()) {
+    return Err(CircuitSolve(format!(
+      "The emulation duration must be non-negative and finite, got {duration:?} s"
+    )));
+  }
+  if step > duration {
+    return Err(CircuitSolve(format!(
+      "The emulation step ({step:?} s) must not exceed the duration ({duration:?} s)"
+    )));
+  }
+  Ok(())
+}
 
 impl Circuit
 {
+  /// Evaluates at a given time the real signal whose spectrum, aligned with the
+  /// pulses of the source, is `spectrum`. Only positive pulses are stored, so
+  /// each of them accounts for twice the real part of its term, unless the
+  /// source is complex.
+  #[inline]
+  pub(crate) fn reconstruct(&self, spectrum: &[Complex<f64>], time: f64) -> f64
+  {
+    self.reconstruct_analytic(spectrum, time).re
+  }
+
+  /// Gives the spectrum of the potential of a node relative to the ground of
+  /// the circuit (see [`Circuit::set_ground`]).
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the ground node does not exist.
+  pub(crate) fn grounded_potentials<'a>(&'a self, node: &'a Node)
+    -> Result<Cow<'a, [Complex<f64>]>>
+  {
+    let Some(ground) = self.ground() else {
+      return Ok(Cow::Borrowed(&node.potentials));
+    };
+    let ground = self
+      .get_node(ground)
+      .ok_or_else(|| CircuitSolve(format!("Ground node of id {ground:?} not found")))?;
+    Ok(Cow::Owned(
+      node
+        .potentials
+        .iter()
+        .zip(ground.potentials.iter())
+        .map(|(potential, ground)| potential - ground)
+        .collect(),
+    ))
+  }
+
+  /// Evaluates at a given time the analytic signal associated with the real
+  /// signal whose spectrum is `spectrum`, that is the signal plus `i` times its
+  /// Hilbert transform. Since only positive pulses are stored, it is simply
+  /// twice the sum of their terms, plus the (real) DC component. For a complex
+  /// source, this is the complex signal itself.
+  pub(crate) fn reconstruct_analytic(&self, spectrum: &[Complex<f64>], time: f64) -> Complex<f64>
+  {
+    self
+      .voltages()
+      .zip(spectrum)
+      .map(|((pulse, _), coef)| {
+        if self.generator().complex {
+          coef * Complex::new(0f64, time * pulse).exp()
+        } else if pulse.is_zero() {
+          Complex::from(coef.re)
+        } else {
+          2f64 * coef * Complex::new(0f64, time * pulse).exp()
+        }
+      })
+      .sum()
+  }
+
   /// This function is used to emulate a circuit and returns the currents and
   /// voltages of the node as well as the tensions on the following component
   ///
@@ -30,54 +156,770 @@ impl Circuit
   /// # Returns
   ///
   /// A tuple containing the vectors of currents, tensions, and potentials of
-  /// the node
+  /// the node. With the `rayon` feature, the samples are computed in parallel,
+  /// with the same results.
+  ///
+  /// The phasor `exp(iωt)` of each pulse is turned from one sample to the next
+  /// by multiplying it by `exp(iω·step)`, which saves an exponential per
+  /// sample and per pulse. It is computed exactly again every few hundred
+  /// samples, so that the error stays negligible over long emulations.
   ///
   /// # Errors
   ///
-  /// Returns an error if the initialization of the circuit fails
+  /// Returns an error if the initialization of the circuit fails, or if `step`
+  /// is not positive, `duration` is negative or `step` exceeds `duration`.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::Resistor,
+  /// };
+  /// use num::Complex;
+  ///
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(1., Complex::new(0., -0.5))
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(200.)));
+  ///
+  /// assert!(c.emulate_one(1e-3, 1e-4, &id![]).is_ok());
+  /// // Degenerate time grids are rejected
+  /// assert!(c.emulate_one(1e-3, 0., &id![]).is_err());
+  /// assert!(c.emulate_one(1e-3, -1e-4, &id![]).is_err());
+  /// assert!(c.emulate_one(0., 1e-4, &id![]).is_err());
+  ///
+  /// // One sample per step, at the times k * step, whatever the rounding
+  /// for (duration, step) in [
+  ///   (1e-3, 1e-4),
+  ///   (1e-3, 3e-4),
+  ///   (0.3, 0.1),
+  ///   (0.7, 0.1),
+  ///   (1., 1. / 3.),
+  /// ] {
+  ///   let (currents, ..) = c.emulate_one(duration, step, &id![])?;
+  ///   assert_eq!(currents.len(), (duration / step).ceil() as usize);
+  /// }
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  ///
+  /// The recurrence on the phasors matches the direct evaluation of the
+  /// exponentials over long emulations:
+  ///
+  /// ```
+  /// use std::f64::consts::PI;
+  ///
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  /// };
+  /// use num::Complex;
+  ///
+  /// // The first 200 harmonics of a square wave of period 2 ms
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(0., Complex::from(0.));
+  /// for k in (1..400).step_by(2) {
+  ///   c.add_tone(500. * k as f64, 4. / (PI * k as f64), -PI / 2.);
+  /// }
+  /// c.content_mut()
+  ///   .push_serie(Component::from(Resistor(100.)))
+  ///   .push_serie(Component::from(Capacitor(1e-6)));
+  ///
+  /// let (step, duration) = (1e-6, 1e-2);
+  /// let (_, _, potentials) = c.emulate_one(duration, step, &id![1])?;
+  /// let coefficients = c.get_node(&id![1]).unwrap().potentials.clone();
+  /// let pulses = c.voltages().map(|(pulse, _)| *pulse).collect::<Vec<_>>();
+  /// for (k, potential) in potentials.iter().enumerate() {
+  ///   let time = k as f64 * step;
+  ///   let naive = coefficients[0].re
+  ///     + pulses[1..]
+  ///       .iter()
+  ///       .zip(&coefficients[1..])
+  ///       .map(|(pulse, c)| 2. * (c * Complex::new(0., time * pulse).exp()).re)
+  ///       .sum::<f64>();
+  ///   assert!((potential - naive).abs() < 1e-9);
+  /// }
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  ///
+  /// The constant term of the signals is that of the zero pulse of the source,
+  /// if it has any:
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::Resistor,
+  /// };
+  /// use num::Complex;
+  ///
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(1e3, Complex::from(0.5))
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(100.)));
+  ///
+  /// // 1 V of amplitude at 1000 rad/s, without offset then with 2 V of offset
+  /// for offset in [None, Some(2.)] {
+  ///   if let Some(offset) = offset {
+  ///     c.add_pulse(0., Complex::from(offset));
+  ///   }
+  ///   let (_, tensions, _) = c.emulate_one(1e-3, 1e-4, &id![])?;
+  ///   for (k, tension) in tensions.iter().enumerate() {
+  ///     let expected = offset.unwrap_or(0.) + (1e3 * k as f64 * 1e-4).cos();
+  ///     assert!((tension - expected).abs() < 1e-9);
+  ///   }
+  /// }
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  #[inline]
   pub fn emulate_one(&mut self, duration: f64, step: f64, node_id: &Id) -> Result<EmulationData>
   {
+    self.init()?.emulate_initialized(duration, step, node_id)
+  }
+
+  /// Same as [`Circuit::emulate_one`], but adds zero-mean Gaussian noise of
+  /// standard deviation `noise_std` to each sample of the currents, tensions
+  /// and potentials, to mimic measurement (e.g. ADC) noise. The noise is drawn
+  /// from a pseudo-random generator seeded with `seed`, so that a given seed
+  /// always gives the same samples.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if `noise_std` is negative or not finite, or in the same
+  /// cases as [`Circuit::emulate_one`].
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::Resistor,
+  /// };
+  /// use num::Complex;
+  ///
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(0., Complex::from(1.))
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(100.)));
+  ///
+  /// let (_, _, potentials) = c.emulate_one_noisy(1., 1e-4, &id![], 0.01, 42)?;
+  /// let mean = potentials.iter().sum::<f64>() / potentials.len() as f64;
+  /// let variance =
+  ///   potentials.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / potentials.len() as f64;
+  /// assert!((mean - 1.).abs() < 1e-3);
+  /// assert!((variance.sqrt() - 0.01).abs() < 1e-3);
+  /// // Reproducible
+  /// assert_eq!(
+  ///   potentials,
+  ///   c.emulate_one_noisy(1., 1e-4, &id![], 0.01, 42)?.2
+  /// );
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn emulate_one_noisy(
+    &mut self,
+    duration: f64,
+    step: f64,
+    node_id: &Id,
+    noise_std: f64,
+    seed: u64,
+  ) -> Result<EmulationData>
+  {
+    if !(noise_std >= 0f64 && noise_std.is_finite()) {
+      return Err(CircuitSolve(format!(
+        "The standard deviation of the noise must be non-negative and finite, got {noise_std:?}"
+      )));
+    }
+    let (mut currents, mut tensions, mut potentials) = self.emulate_one(duration, step, node_id)?;
+    let mut rng = SplitMix64::new(seed);
+    for sample in currents
+      .iter_mut()
+      .chain(tensions.iter_mut())
+      .chain(potentials.iter_mut())
+    {
+      *sample += noise_std * rng.next_gaussian();
+    }
+    Ok((currents, tensions, potentials))
+  }
+
+  /// Same as [`Circuit::emulate_one`], but writes the samples into buffers
+  /// provided by the caller instead of allocating new vectors. The buffers are
+  /// cleared first and keep their capacity, so that reusing them over many
+  /// emulations (e.g. a parameter sweep) does not allocate once they are large
+  /// enough.
+  ///
+  /// # Arguments
+  ///
+  /// * `duration` - The duration of the emulation in seconds
+  /// * `step` - The time step used for the emulation in seconds
+  /// * `node_id` - The ID of the node to emulate
+  /// * `currents` - The buffer receiving the currents of the node
+  /// * `tensions` - The buffer receiving the tensions of the next component
+  /// * `potentials` - The buffer receiving the potentials of the node
+  ///
+  /// # Errors
+  ///
+  /// Same as [`Circuit::emulate_one`]. The content of the buffers is
+  /// unspecified on error.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  /// };
+  /// use num::Complex;
+  ///
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(1., Complex::new(0., -0.5))
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(200.)))
+  ///   .push_serie(Component::from(Capacitor(1e-6)));
+  ///
+  /// let (mut currents, mut tensions, mut potentials) = (Vec::new(), Vec::new(), Vec::new());
+  /// c.emulate_one_into(
+  ///   1e-3,
+  ///   1e-4,
+  ///   &id![1u8],
+  ///   &mut currents,
+  ///   &mut tensions,
+  ///   &mut potentials,
+  /// )?;
+  /// assert_eq!(
+  ///   (currents, tensions, potentials),
+  ///   c.emulate_one(1e-3, 1e-4, &id![1u8])?
+  /// );
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  #[inline]
+  pub fn emulate_one_into(
+    &mut self,
+    duration: f64,
+    step: f64,
+    node_id: &Id,
+    currents: &mut Vec<f64>,
+    tensions: &mut Vec<f64>,
+    potentials: &mut Vec<f64>,
+  ) -> Result<()>
+  {
+    self
+      .init()?
+      .emulate_initialized_into(duration, step, node_id, currents, tensions, potentials)
+  }
+
+  /// Same as [`Circuit::emulate_one`], at arbitrary times instead of a uniform
+  /// grid, e.g. to refine the samples where the signals vary quickly. Each
+  /// sample is computed independently from the spectra of the node, at the
+  /// cost of an exponential per pulse.
+  ///
+  /// The times need not be sorted nor lie in the emulated window: the signals
+  /// are those of the steady state, which is periodic with the period of the
+  /// source (`2 * duration` after `Circuit::set_generator_fn`), so a time
+  /// outside of the window gives the value of its periodic extension. The
+  /// sources added with [`Circuit::add_source`] are superposed as in
+  /// [`Circuit::emulate_one`].
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the initialization of the circuit fails, if the node
+  /// does not exist or if a time is not finite.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  /// };
+  /// use num::Complex;
+  ///
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(1e3, Complex::new(0., -0.5))
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(100.)))
+  ///   .push_serie(Component::from(Capacitor(1e-6)));
+  ///
+  /// let (currents, tensions, potentials) = c.emulate_one(1e-2, 1e-4, &id![1])?;
+  /// let times = (0..currents.len())
+  ///   .map(|k| k as f64 * 1e-4)
+  ///   .collect::<Vec<_>>();
+  /// let at = c.emulate_at(&times, &id![1])?;
+  /// for (uniform, arbitrary) in [(currents, at.0), (tensions, at.1), (potentials, at.2)] {
+  ///   assert!(uniform
+  ///     .iter()
+  ///     .zip(&arbitrary)
+  ///     .all(|(a, b)| (a - b).abs() < 1e-9));
+  /// }
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn emulate_at(&mut self, times: &[f64], node_id: &Id) -> Result<EmulationData>
+  {
+    if let Some(time) = times.iter().find(|time| !time.is_finite()) {
+      return Err(CircuitSolve(format!(
+        "The emulation times must be finite, got {time:?} s"
+      )));
+    }
     self.init()?;
+    let (mut currents, mut tensions, mut potentials) = self.sample_main_source(times, node_id)?;
+    // The contributions of the added sources are superposed
+    for circuit in self.superposed.iter() {
+      superpose(
+        (&mut currents, &mut tensions, &mut potentials),
+        circuit.sample_main_source(times, node_id)?,
+      );
+    }
+    Ok((currents, tensions, potentials))
+  }
 
+  /// Same as [`Circuit::emulate_at`], on a circuit that is already
+  /// initialized and with its main source only.
+  fn sample_main_source(&self, times: &[f64], node_id: &Id) -> Result<EmulationData>
+  {
+    let node = self
+      .get_node(node_id)
+      .ok_or_else(|| CircuitSolve(format!("Node of id {node_id:?} not found")))?;
+    let sample = |spectrum: &[Complex<f64>]| {
+      times
+        .iter()
+        .map(|time| self.reconstruct(spectrum, *time))
+        .collect()
+    };
+    Ok((
+      sample(&node.currents),
+      sample(&node.next_component_tensions),
+      sample(&self.grounded_potentials(node)?),
+    ))
+  }
+
+  /// Same as [`Circuit::emulate_one`], on a circuit that is already
+  /// initialized.
+  pub(crate) fn emulate_initialized(
+    &self,
+    duration: f64,
+    step: f64,
+    node_id: &Id,
+  ) -> Result<EmulationData>
+  {
+    check_time_grid(duration, step)?;
+    let nb_iter = (duration / step).ceil() as usize;
+    let mut currents = Vec::with_capacity(nb_iter);
+    let mut tensions = Vec::with_capacity(nb_iter);
+    let mut potentials = Vec::with_capacity(nb_iter);
+    self.emulate_initialized_into(
+      duration,
+      step,
+      node_id,
+      &mut currents,
+      &mut tensions,
+      &mut potentials,
+    )?;
+    Ok((currents, tensions, potentials))
+  }
+
+  /// Same as [`Circuit::emulate_one_into`], on a circuit that is already
+  /// initialized.
+  pub(crate) fn emulate_initialized_into(
+    &self,
+    duration: f64,
+    step: f64,
+    node_id: &Id,
+    currents: &mut Vec<f64>,
+    tensions: &mut Vec<f64>,
+    potentials: &mut Vec<f64>,
+  ) -> Result<()>
+  {
+    self.emulate_main_source_into(duration, step, node_id, currents, tensions, potentials)?;
+    // The contributions of the added sources are superposed
+    for circuit in self.superposed.iter() {
+      superpose(
+        (currents, tensions, potentials),
+        circuit.emulate_initialized(duration, step, node_id)?,
+      );
+    }
+    Ok(())
+  }
+
+  /// Same as [`Circuit::emulate_initialized_into`], with the main source of
+  /// the circuit only.
+  fn emulate_main_source_into(
+    &self,
+    duration: f64,
+    step: f64,
+    node_id: &Id,
+    currents: &mut Vec<f64>,
+    tensions: &mut Vec<f64>,
+    potentials: &mut Vec<f64>,
+  ) -> Result<()>
+  {
+    check_time_grid(duration, step)?;
     let node = self
       .get_node(node_id)
       .unwrap_or_else(|| panic!("Node of id {node_id:?} not found :/"));
     let initial_currents = &node.currents;
     let initial_tensions = &node.next_component_tensions;
-    let initial_potentials = &node.potentials;
+    let initial_potentials = &self.grounded_potentials(node)?;
+
+    // The times are computed from the index of the samples rather than
+    // accumulated, so that exactly `nb_iter` samples are pushed
+    let nb_iter = (duration / step).ceil() as usize;
+    for buffer in [&mut *currents, &mut *tensions, &mut *potentials] {
+      buffer.clear();
+      buffer.reserve(nb_iter);
+    }
+
+    // The pulses contributing to the signals, with the phase by which each of
+    // them turns between two samples and the weight of their terms. The zero
+    // pulse of a complex source is turned as the others, its term being complex
+    let source = self.generator();
+    let driven = self
+      .voltages()
+      .enumerate()
+      .filter(|(_, (pulse, voltage))| !voltage.is_zero() && (source.complex || !pulse.is_zero()))
+      .map(|(k, (pulse, _))| {
+        (
+          k,
+          *pulse,
+          Complex::new(0f64, step * pulse).exp(),
+          source.weight(*pulse),
+        )
+      })
+      .collect::<Vec<_>>();
+    // The constant term of a spectrum, if the source is real and has a zero
+    // pulse
+    let constant_index = self
+      .voltages()
+      .position(|(pulse, _)| pulse.is_zero())
+      .filter(|_| !source.complex);
+    let constant = |spectrum: &[Complex<f64>]| constant_index.map_or(0f64, |k| spectrum[k].re);
+    // The signals of a purely resistive circuit are the tension of the source
+    // scaled by real gains, read on any pulse driven by the source, so that
+    // only the tension of the source is synthesized
+    let voltages = self
+      .voltages()
+      .map(|(_, voltage)| *voltage)
+      .collect::<Vec<_>>();
+    let gains = self
+      .voltages()
+      .position(|(_, voltage)| !voltage.is_zero())
+      .filter(|_| self.is_resistive())
+      .map(|k| {
+        [initial_currents, initial_tensions, &**initial_potentials]
+          .map(|spectrum| spectrum[k] / voltages[k])
+      })
+      .filter(|gains| {
+        gains
+          .iter()
+          .all(|gain| gain.im.abs() <= REAL_GAIN_TOLERANCE * gain.re.abs())
+      })
+      .map(|gains| gains.map(|gain| gain.re));
+    // Synthesizes a signal from its spectrum, given the phasors of the driven
+    // pulses at the time of the sample
+    let synthesize = |spectrum: &[Complex<f64>], phasors: &[Complex<f64>]| {
+      driven
+        .iter()
+        .zip(phasors)
+        .fold(constant(spectrum), |signal, ((k, _, _, weight), phasor)| {
+          signal + weight * (spectrum[*k] * phasor).re
+        })
+    };
+    // Computes the samples of indices `start..end`. Rather than evaluating an
+    // exponential per sample and per pulse, the phasor `exp(iωt)` of each
+    // pulse is turned by its phase step from one sample to the next. It is
+    // computed exactly at the start of the block, so that the rounding errors
+    // of the recurrence cannot drift over more than `PHASOR_RESYNC_PERIOD`
+    // samples.
+    let fill_block = |start: usize,
+                      end: usize,
+                      currents: &mut Vec<f64>,
+                      tensions: &mut Vec<f64>,
+                      potentials: &mut Vec<f64>| {
+      let mut phasors = driven
+        .iter()
+        .map(|(_, pulse, ..)| Complex::new(0f64, start as f64 * step * pulse).exp())
+        .collect::<Vec<_>>();
+      for _ in start..end {
+        let (current, tension, potential) = match gains {
+          Some([current_gain, tension_gain, potential_gain]) => {
+            let source_tension = synthesize(&voltages, &phasors);
+            (
+              current_gain * source_tension,
+              tension_gain * source_tension,
+              potential_gain * source_tension,
+            )
+          },
+          None => (
+            synthesize(initial_currents, &phasors),
+            synthesize(initial_tensions, &phasors),
+            synthesize(initial_potentials, &phasors),
+          ),
+        };
+        currents.push(current);
+        tensions.push(tension);
+        potentials.push(potential);
+        for ((_, _, phase_step, _), phasor) in driven.iter().zip(phasors.iter_mut()) {
+          *phasor *= phase_step;
+        }
+      }
+    };
+
+    // The blocks are independent of one another, and are computed the same way
+    // in the serial and in the parallel paths, so that both give bit-identical
+    // results
+    #[cfg(feature = "rayon")]
+    {
+      use rayon::prelude::*;
+      let blocks = (0..nb_iter.div_ceil(PHASOR_RESYNC_PERIOD))
+        .into_par_iter()
+        .map(|block| {
+          let start = block * PHASOR_RESYNC_PERIOD;
+          let end = nb_iter.min(start + PHASOR_RESYNC_PERIOD);
+          let mut buffers = (Vec::new(), Vec::new(), Vec::new());
+          fill_block(start, end, &mut buffers.0, &mut buffers.1, &mut buffers.2);
+          buffers
+        })
+        .collect::<Vec<_>>();
+      for (block_currents, block_tensions, block_potentials) in blocks {
+        currents.extend(block_currents);
+        tensions.extend(block_tensions);
+        potentials.extend(block_potentials);
+      }
+    }
+    #[cfg(not(feature = "rayon"))]
+    for start in (0..nb_iter).step_by(PHASOR_RESYNC_PERIOD) {
+      let end = nb_iter.min(start + PHASOR_RESYNC_PERIOD);
+      fill_block(start, end, currents, tensions, potentials);
+    }
+    Ok(())
+  }
+
+  /// Same as [`Circuit::emulate_one`], but only keeps the `top_n` pulses of
+  /// the source with the largest voltage magnitudes (the zero pulse included),
+  /// which speeds up previews of sources with many harmonics.
+  ///
+  /// The signals are truncated to the contributions of the kept pulses: the
+  /// error on a signal whose spectrum is `c` is at most `Σ 2|c_k|` over the
+  /// dropped pulses at any time, and its mean square is `Σ 2|c_k|²`. It is
+  /// small when the energy of the source is concentrated on a few tones, but
+  /// degrades for broadband sources such as a square wave, whose energy is
+  /// spread over many harmonics decaying slowly: their sharp edges are then
+  /// smoothed and ringing (Gibbs phenomenon) appears.
+  ///
+  /// # Errors
+  ///
+  /// Same as [`Circuit::emulate_one`].
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use std::f64::consts::PI;
+  ///
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::Resistor,
+  /// };
+  ///
+  /// // A strong and a weak tone
+  /// let mut c = Circuit::new();
+  /// c.add_tone(500., 1., -PI / 2.)
+  ///   .add_tone(5e3, 1e-2, -PI / 2.)
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(100.)));
+  ///
+  /// let (_, _, exact) = c.emulate_one(1e-2, 1e-5, &id![])?;
+  /// let (_, _, approx) = c.emulate_one_approx(1e-2, 1e-5, &id![], 1)?;
+  /// // Only the weak tone is dropped
+  /// assert!(exact.iter().zip(&approx).all(|(a, b)| (a - b).abs() < 2e-2));
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn emulate_one_approx(
+    &mut self,
+    duration: f64,
+    step: f64,
+    node_id: &Id,
+    top_n: usize,
+  ) -> Result<EmulationData>
+  {
+    check_time_grid(duration, step)?;
+    self.init()?;
+    let node = self
+      .get_node(node_id)
+      .ok_or_else(|| CircuitSolve(format!("Node of id {node_id:?} not found")))?;
+    let mut ranking = self.voltages().enumerate().collect::<Vec<_>>();
+    ranking.sort_by(|(_, (_, a)), (_, (_, b))| b.norm().total_cmp(&a.norm()));
+    ranking.truncate(top_n);
+    let kept = ranking
+      .into_iter()
+      .map(|(k, &(pulse, _))| (pulse, k))
+      .collect::<Vec<_>>();
+    let source = self.generator();
+    let reconstruct = |spectrum: &[Complex<f64>], time: f64| {
+      kept
+        .iter()
+        .map(|&(pulse, k)| {
+          source.weight(pulse) * (spectrum[k] * Complex::new(0f64, time * pulse).exp()).re
+        })
+        .sum::<f64>()
+    };
+
+    let grounded_potentials = self.grounded_potentials(node)?;
 
     let nb_iter = (duration / step).ceil() as usize;
     let mut currents = Vec::with_capacity(nb_iter);
     let mut tensions = Vec::with_capacity(nb_iter);
     let mut potentials = Vec::with_capacity(nb_iter);
-    let mut elapsed = 0f64;
-
-    while elapsed < duration {
-      let mut current = initial_currents[0].re;
-      let mut tension = initial_tensions[0].re;
-      let mut potential = initial_potentials[0].re;
-      for (k, (pulse, voltage)) in self.voltages().enumerate() {
-        if voltage.is_zero() || pulse.is_zero() {
-          continue;
-        }
-        if pulse.is_zero() {
-          return short_circuit_current(&vec![0u8], voltage, self.impedance());
-        }
-        let factor = Complex::new(0f64, elapsed * *pulse).exp();
-        // This way we know we can approximate a real function such as current or
-        // tension if we only use positive pulses
-        current += 2f64 * (initial_currents[k] * factor).re;
-        tension += 2f64 * (initial_tensions[k] * factor).re;
-        potential += 2f64 * (initial_potentials[k] * factor).re;
-      }
-      currents.push(current);
-      tensions.push(tension);
-      potentials.push(potential);
-      elapsed += step;
+    for k in 0..nb_iter {
+      let elapsed = k as f64 * step;
+      currents.push(reconstruct(&node.currents, elapsed));
+      tensions.push(reconstruct(&node.next_component_tensions, elapsed));
+      potentials.push(reconstruct(&grounded_potentials, elapsed));
     }
     Ok((currents, tensions, potentials))
   }
 
+  /// Emulates the envelope of the potential of a node, that is the magnitude
+  /// of its analytic signal `|v(t) + i·H[v](t)|`, where `H` is the Hilbert
+  /// transform. For a ringing waveform, this gives its decaying amplitude
+  /// instead of the oscillation itself.
+  ///
+  /// This relies on the spectrum of the node being single-sided: only positive
+  /// pulses are stored, so twice their terms form the analytic signal of the
+  /// periodic signal synthesized by the source. The result is only a
+  /// meaningful envelope when the amplitude varies slowly compared to the
+  /// oscillation and the DC component is small, since the latter adds to the
+  /// magnitude.
+  ///
+  /// # Arguments
+  ///
+  /// * `duration` - The duration of the emulation in seconds
+  /// * `step` - The time step used for the emulation in seconds
+  /// * `node_id` - The ID of the node to emulate
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the initialization of the circuit fails, if the node
+  /// does not exist or if the time grid is degenerate (see
+  /// [`Circuit::emulate_one`]).
+  pub fn emulate_envelope(&mut self, duration: f64, step: f64, node_id: &Id) -> Result<Vec<f64>>
+  {
+    check_time_grid(duration, step)?;
+    self.init()?;
+    let node = self
+      .get_node(node_id)
+      .ok_or_else(|| CircuitSolve(format!("Node of id {node_id:?} not found")))?;
+    let potentials = self.grounded_potentials(node)?;
+    let nb_iter = (duration / step).ceil() as usize;
+    Ok(
+      (0..nb_iter)
+        .map(|k| {
+          self
+            .reconstruct_analytic(&potentials, k as f64 * step)
+            .norm()
+        })
+        .collect(),
+    )
+  }
+
+  /// Emulates the tension across a component, that is the potential of its
+  /// fore node minus the potential of its aft node (see
+  /// [`Circuit::terminals`]).
+  ///
+  /// # Arguments
+  ///
+  /// * `id` - The ID of the component
+  /// * `duration` - The duration of the emulation in seconds
+  /// * `step` - The time step used for the emulation in seconds
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the initialization of the circuit fails, if the
+  /// component does not exist or if the time grid is degenerate (see
+  /// [`Circuit::emulate_one`]).
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Inductor,
+  ///     Resistor,
+  ///   },
+  /// };
+  /// use num::Complex;
+  ///
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(0., Complex::from(0.5))
+  ///   .add_pulse(1e3, Complex::new(0., -0.5))
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(100.)))
+  ///   .push_serie(Component::from(Capacitor(1e-6)))
+  ///   .push_serie(Component::from(Inductor(1e-2)));
+  ///
+  /// // Kirchhoff's voltage law along the loop
+  /// let source = c.generator().to_time_series(1e-2, 1e-5)?;
+  /// let mut sum = vec![0.; source.len()];
+  /// for k in 0..3 {
+  ///   for (total, tension) in sum.iter_mut().zip(c.voltage_across(&id![k], 1e-2, 1e-5)?) {
+  ///     *total += tension;
+  ///   }
+  /// }
+  /// assert!(sum.iter().zip(&source).all(|(a, b)| (a - b).abs() < 1e-9));
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn voltage_across(&mut self, id: &Id, duration: f64, step: f64) -> Result<Vec<f64>>
+  {
+    check_time_grid(duration, step)?;
+    self.init()?;
+    let (fore, aft) = self
+      .terminals(id)
+      .ok_or_else(|| CircuitSolve(format!("Component of id {id:?} not found")))?;
+    let (_, _, mut potentials) = self.emulate_initialized(duration, step, &fore)?;
+    let aft_potentials = match aft {
+      Some(aft) => self.emulate_initialized(duration, step, &aft)?.2,
+      // The aft terminal of the source is only at a zero potential if it is the
+      // ground, and is otherwise below the root by the tension of the circuit
+      None if self.ground().is_some() => {
+        let root = self.content().fore_node_id.clone();
+        let (_, tensions, root_potentials) = self.emulate_initialized(duration, step, &root)?;
+        root_potentials
+          .into_iter()
+          .zip(tensions)
+          .map(|(potential, tension)| potential - tension)
+          .collect()
+      },
+      None => return Ok(potentials),
+    };
+    for (potential, aft_potential) in potentials.iter_mut().zip(aft_potentials) {
+      *potential -= aft_potential;
+    }
+    Ok(potentials)
+  }
+
   /// Emulates the circuit for multiple nodes for a given duration and step
   /// size.
   ///
@@ -112,4 +954,115 @@ impl Circuit
     }
     Ok(results)
   }
+
+  /// Emulates the circuit for multiple nodes (see [`Circuit::emulate_many`])
+  /// and writes the results as CSV. The header row is
+  /// `time,current_<id>,tension_<id>,potential_<id>,...` for each node in the
+  /// order of `node_ids`, where `<id>` is the ID of the node as a dotted path
+  /// (e.g. `0.1` for `[0, 1]`, and nothing for the root). Each following row
+  /// holds the values at the time `k * step`.
+  ///
+  /// # Arguments
+  ///
+  /// * `duration` - The duration of the emulation in seconds.
+  /// * `step` - The step size of the emulation in seconds.
+  /// * `node_ids` - The IDs of the nodes to emulate.
+  /// * `writer` - Where to write the CSV.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the emulation fails or if writing fails.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  /// };
+  /// use num::Complex;
+  ///
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(1e3, Complex::from(1.))
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(1e3)))
+  ///   .push_serie(Component::from(Capacitor(1e-6)));
+  ///
+  /// let mut buffer = Vec::new();
+  /// c.emulate_to_csv(1e-2, 2.5e-3, &[id![0], id![1]], &mut buffer)?;
+  /// let csv = String::from_utf8(buffer).unwrap();
+  /// let mut lines = csv.lines();
+  /// assert_eq!(
+  ///   lines.next(),
+  ///   Some("time,current_0,tension_0,potential_0,current_1,tension_1,potential_1")
+  /// );
+  /// assert_eq!(lines.count(), 4);
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn emulate_to_csv(
+    &mut self,
+    duration: f64,
+    step: f64,
+    node_ids: &[Id],
+    mut writer: impl Write,
+  ) -> Result<()>
+  {
+    let results = self.emulate_many(duration, step, node_ids)?;
+
+    write!(writer, "time")?;
+    for node_id in node_ids {
+      let id = node_id
+        .iter()
+        .map(u8::to_string)
+        .collect::<Vec<_>>()
+        .join(".");
+      write!(writer, ",current_{id},tension_{id},potential_{id}")?;
+    }
+    writeln!(writer)?;
+
+    let nb_rows = results.first().map_or(0, |(currents, ..)| currents.len());
+    for k in 0..nb_rows {
+      write!(writer, "{}", k as f64 * step)?;
+      for (currents, tensions, potentials) in results.iter() {
+        write!(writer, ",{},{},{}", currents[k], tensions[k], potentials[k])?;
+      }
+      writeln!(writer)?;
+    }
+    Ok(())
+  }
+
+  /// Computes the worst-case stresses of the simple dipoles of the circuit over
+  /// an emulation, to check them against the ratings of the components.
+  ///
+  /// # Arguments
+  ///
+  /// * `duration` - The duration of the emulation in seconds
+  /// * `step` - The time step used for the emulation in seconds
+  ///
+  /// # Returns
+  ///
+  /// A map from the IDs of the simple dipoles to the peak absolute tension
+  /// across them and the peak absolute current through them.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the emulation fails. See [`Circuit::emulate_one`].
+  pub fn peak_stresses(&mut self, duration: f64, step: f64) -> Result<HashMap<Id, (f64, f64)>>
+  {
+    self.init()?;
+    let ids = collect_dipoles(self.content());
+    let peak = |samples: &[f64]| samples.iter().fold(0f64, |acc, x| acc.max(x.abs()));
+    ids
+      .into_iter()
+      .map(|id| {
+        let (currents, tensions, _) = self.emulate_initialized(duration, step, &id)?;
+        Ok((id, (peak(&tensions), peak(&currents))))
+      })
+      .collect()
+  }
 }
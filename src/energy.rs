@@ -0,0 +1,259 @@
+use std::collections::HashMap;
+
+use crate::{
+  emulation::check_time_grid,
+  error::Result,
+  Circuit,
+  Component,
+  ComponentContent,
+  Dipole,
+  Id,
+};
+
+/// Collects the capacitors and inductors of a component.
+fn collect_storages(component: &Component) -> Vec<&Component>
+{
+  component
+    .iter()
+    .map(|(_, component)| component)
+    .filter(|component| {
+      matches!(
+        component.content,
+        ComponentContent::Simple(Dipole::Capacitor(_) | Dipole::Inductor(_))
+      )
+    })
+    .collect()
+}
+
+impl Circuit
+{
+  /// Computes the energy stored at a given time in the capacitors and in the
+  /// inductors of the circuit, that is the sums of `C v(t)² / 2` over the
+  /// capacitors and of `L i(t)² / 2` over the inductors, in joules. In a
+  /// resonant circuit, both energies oscillate in phase opposition.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the initialization of the circuit fails.
+  ///
+  /// # Returns
+  ///
+  /// Returns `(capacitive_energy, inductive_energy)`.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Inductor,
+  ///     Resistor,
+  ///   },
+  /// };
+  /// use num::Complex;
+  ///
+  /// // Under a constant tension, the capacitor holds the whole tension and no
+  /// // current flows through the inductor
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(0., Complex::from(2.))
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(100.)))
+  ///   .push_serie(Component::from(Capacitor(1e-6)));
+  /// c.get_comp_by_id_mut(&[0])
+  ///   .unwrap()
+  ///   .push_parallel(Component::from(Inductor(1e-3)));
+  ///
+  /// let (capacitive, inductive) = c.energy_breakdown(0.)?;
+  /// assert!((capacitive - 2e-6).abs() < 1e-15);
+  /// assert!(inductive.abs() < 1e-15);
+  /// assert!((c.stored_energy(0.)? - capacitive - inductive).abs() < 1e-15);
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn energy_breakdown(&mut self, time: f64) -> Result<(f64, f64)>
+  {
+    self.init()?;
+    let storages = collect_storages(self.content());
+    Ok(self.storage_energies(&storages, time))
+  }
+
+  /// Computes the energy stored at a given time in each capacitor and in each
+  /// inductor of the circuit, in joules, by reconstructing the tension across
+  /// the capacitors and the current through the inductors from the spectral
+  /// solution. Only the `Capacitor` and `Inductor` simple dipoles have an
+  /// entry, keyed by their ID.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the initialization of the circuit fails.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Inductor,
+  ///     Resistor,
+  ///   },
+  /// };
+  /// use num::Complex;
+  ///
+  /// // A weakly damped RLC circuit driven at its resonance, where the source
+  /// // only makes up for the losses of the resistor
+  /// let (l, cap) = (1e-3f64, 1e-6);
+  /// let resonance = (l * cap).sqrt().recip();
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(resonance, Complex::from(1.))
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(1.)))
+  ///   .push_serie(Component::from(Inductor(l)))
+  ///   .push_serie(Component::from(Capacitor(cap)));
+  ///
+  /// let period = 2. * std::f64::consts::PI / resonance;
+  /// let mut capacitive = vec![];
+  /// for k in 0..8 {
+  ///   let energies = c.energy_at(k as f64 * period / 8.)?;
+  ///   assert_eq!(energies.len(), 2);
+  ///   let (inductor, capacitor) = (energies[&id![1]], energies[&id![2]]);
+  ///   // The energy is exchanged between the inductor and the capacitor, while
+  ///   // the total one is conserved: L I² / 2 with I = 2 V / 1 Ω
+  ///   assert!((inductor + capacitor - 2e-3).abs() < 1e-12);
+  ///   capacitive.push(capacitor);
+  /// }
+  /// assert!(capacitive[0] < 1e-12 && (capacitive[2] - 2e-3).abs() < 1e-12);
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn energy_at(&mut self, time: f64) -> Result<HashMap<Id, f64>>
+  {
+    self.init()?;
+    let storages = collect_storages(self.content());
+    Ok(
+      storages
+        .into_iter()
+        .map(|component| {
+          (
+            component.fore_node_id.clone(),
+            self.storage_energy(component, time),
+          )
+        })
+        .collect(),
+    )
+  }
+
+  /// Computes the energy stored at a given time in a capacitor or an inductor,
+  /// assuming the circuit is initialized.
+  fn storage_energy(&self, component: &Component, time: f64) -> f64
+  {
+    let node = self
+      .get_node(&component.fore_node_id)
+      .expect("Node not found :/");
+    match component.content {
+      ComponentContent::Simple(Dipole::Capacitor(c)) => {
+        let tension = self.reconstruct(&node.next_component_tensions, time);
+        0.5 * c * tension * tension
+      },
+      ComponentContent::Simple(Dipole::Inductor(l)) => {
+        let current = self.reconstruct(&node.currents, time);
+        0.5 * l * current * current
+      },
+      _ => unreachable!(),
+    }
+  }
+
+  /// Computes the capacitive and inductive energies stored in `storages` at a
+  /// given time, assuming the circuit is initialized.
+  fn storage_energies(&self, storages: &[&Component], time: f64) -> (f64, f64)
+  {
+    let (mut capacitive, mut inductive) = (0f64, 0f64);
+    for component in storages {
+      let energy = self.storage_energy(component, time);
+      if matches!(
+        component.content,
+        ComponentContent::Simple(Dipole::Capacitor(_))
+      ) {
+        capacitive += energy;
+      } else {
+        inductive += energy;
+      }
+    }
+    (capacitive, inductive)
+  }
+
+  /// Computes the total energy stored at a given time in the capacitors and
+  /// inductors of the circuit, in joules. See [`Circuit::energy_breakdown`].
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the initialization of the circuit fails.
+  #[inline]
+  pub fn stored_energy(&mut self, time: f64) -> Result<f64>
+  {
+    let (capacitive, inductive) = self.energy_breakdown(time)?;
+    Ok(capacitive + inductive)
+  }
+
+  /// Computes the minimum and the maximum of the total energy stored in the
+  /// circuit (see [`Circuit::stored_energy`]) over an emulation window. The
+  /// swing between them, compared to the average energy, tells how resonant
+  /// the circuit is: the energy of a lossless resonator driven at resonance
+  /// barely changes, whereas the one of a single reactive component swings
+  /// between zero and its peak twice per period.
+  ///
+  /// # Arguments
+  ///
+  /// * `duration` - The duration of the emulation in seconds
+  /// * `step` - The time step used for the emulation in seconds
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the initialization of the circuit fails or if the time
+  /// grid is degenerate (see [`Circuit::emulate_one`]).
+  ///
+  /// # Returns
+  ///
+  /// Returns `(min_energy, max_energy)`, in joules.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  /// };
+  /// use num::Complex;
+  ///
+  /// // The energy of a capacitor swings between 0 and C V² / 2 with V = 2 V
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(1e3, Complex::from(1.))
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(1e-3)))
+  ///   .push_serie(Component::from(Capacitor(1e-6)));
+  ///
+  /// let (min, max) = c.energy_range(2. * std::f64::consts::PI * 1e-3, 1e-6)?;
+  /// assert!(min < 1e-9);
+  /// assert!((max - 2e-6).abs() < 1e-9);
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn energy_range(&mut self, duration: f64, step: f64) -> Result<(f64, f64)>
+  {
+    check_time_grid(duration, step)?;
+    self.init()?;
+    let storages = collect_storages(self.content());
+    let (mut min, mut max) = (f64::INFINITY, f64::NEG_INFINITY);
+    for k in 0..(duration / step).ceil() as usize {
+      let (capacitive, inductive) = self.storage_energies(&storages, k as f64 * step);
+      min = min.min(capacitive + inductive);
+      max = max.max(capacitive + inductive);
+    }
+    Ok((min, max))
+  }
+}
@@ -0,0 +1,306 @@
+use fractios::RatioFrac;
+use num::Complex;
+
+use crate::{
+  util::coefficients,
+  Circuit,
+  Component,
+  ComponentContent,
+  Dipole,
+  Source,
+};
+
+/// Returns `true` if two values differ by at most `epsilon` relatively to the
+/// largest of them. NaN is equal to nothing.
+#[inline]
+fn approx_eq_value(a: f64, b: f64, epsilon: f64) -> bool
+{
+  a == b || (a - b).abs() <= epsilon * a.abs().max(b.abs())
+}
+
+#[inline]
+fn approx_eq_complex(a: Complex<f64>, b: Complex<f64>, epsilon: f64) -> bool
+{
+  a == b || (a - b).norm() <= epsilon * a.norm().max(b.norm())
+}
+
+/// Compares two fractions coefficient by coefficient, ignoring the trailing
+/// zero coefficients of their numerators and denominators.
+fn approx_eq_fraction(
+  a: &RatioFrac<Complex<f64>>,
+  b: &RatioFrac<Complex<f64>>,
+  epsilon: f64,
+) -> bool
+{
+  let approx_eq_coefficients = |a: Vec<Complex<f64>>, b: Vec<Complex<f64>>| {
+    a.len() == b.len()
+      && a
+        .into_iter()
+        .zip(b)
+        .all(|(a, b)| approx_eq_complex(a, b, epsilon))
+  };
+  approx_eq_coefficients(coefficients(&a.numerator), coefficients(&b.numerator))
+    && approx_eq_coefficients(coefficients(&a.denominator), coefficients(&b.denominator))
+}
+
+impl Dipole
+{
+  /// Compares two dipoles of the same kind, whose values differ by at most
+  /// `epsilon` relatively to the largest of them. The fractions of equivalent
+  /// dipoles are compared coefficient by coefficient, and the loads of
+  /// transformers and voltage-controlled voltage sources recursively (see
+  /// [`Component::approx_eq`]). A poisoned dipole is equal to nothing.
+  pub fn approx_eq(&self, other: &Self, epsilon: f64) -> bool
+  {
+    use Dipole::*;
+    match (self, other) {
+      (Resistor(a), Resistor(b)) | (Capacitor(a), Capacitor(b)) | (Inductor(a), Inductor(b)) =>
+        approx_eq_value(*a, *b, epsilon),
+      (Short, Short) | (Open, Open) => true,
+      (Equivalent(a), Equivalent(b)) => approx_eq_fraction(a, b, epsilon),
+      (
+        Transformer {
+          ratio: a,
+          load: a_load,
+        },
+        Transformer {
+          ratio: b,
+          load: b_load,
+        },
+      ) => approx_eq_value(*a, *b, epsilon) && a_load.approx_eq(b_load, epsilon),
+      (
+        Vcvs {
+          control: a_control,
+          gain: a,
+          load: a_load,
+        },
+        Vcvs {
+          control: b_control,
+          gain: b,
+          load: b_load,
+        },
+      ) =>
+        a_control == b_control
+          && approx_eq_value(*a, *b, epsilon)
+          && a_load.approx_eq(b_load, epsilon),
+      _ => false,
+    }
+  }
+}
+
+/// Exact structural equality, see [`Dipole::approx_eq`].
+impl PartialEq for Dipole
+{
+  #[inline]
+  fn eq(&self, other: &Self) -> bool
+  {
+    self.approx_eq(other, 0f64)
+  }
+}
+
+impl ComponentContent
+{
+  /// Compares two contents structurally, see [`Component::approx_eq`].
+  pub fn approx_eq(&self, other: &Self, epsilon: f64) -> bool
+  {
+    use ComponentContent::*;
+    match (self, other) {
+      (Series(a), Series(b)) | (Parallel(a), Parallel(b)) =>
+        a.len() == b.len() && a.iter().zip(b.iter()).all(|(a, b)| a.approx_eq(b, epsilon)),
+      (Simple(a), Simple(b)) => a.approx_eq(b, epsilon),
+      _ => false,
+    }
+  }
+}
+
+/// Exact structural equality, see [`Component::approx_eq`].
+impl PartialEq for ComponentContent
+{
+  #[inline]
+  fn eq(&self, other: &Self) -> bool
+  {
+    self.approx_eq(other, 0f64)
+  }
+}
+
+impl Component
+{
+  /// Compares two components structurally: they are the same combinations of
+  /// the same subcomponents, in the same order, down to dipoles of the same
+  /// kinds whose values differ by at most `epsilon` relatively to the largest
+  /// of them (see [`Dipole::approx_eq`]). The IDs, the impedances and the
+  /// initialisation states are ignored, and a poisoned component is equal to
+  /// nothing, not even to itself.
+  ///
+  /// The comparison is not electrical: a series combination nested in another
+  /// one differs from the flat combination of the same components, and so do
+  /// two components in series taken in the other order. Exact equality, with
+  /// `==`, is `approx_eq` with a zero `epsilon`.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   CircuitBuilder,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Inductor,
+  ///     Poisoned,
+  ///     Resistor,
+  ///   },
+  /// };
+  ///
+  /// // The same RLC circuit, built in three ways
+  /// let mut pushed = Component::from(Resistor(100.));
+  /// pushed
+  ///   .push_serie(Component::from(Inductor(1e-3)))
+  ///   .push_serie(Component::from(Capacitor(1e-6)));
+  /// let built = CircuitBuilder::new()
+  ///   .resistor(100.)
+  ///   .inductor(1e-3)
+  ///   .capacitor(1e-6)
+  ///   .build()?;
+  /// let combined = Component::from(Resistor(100.))
+  ///   + Component::from(Inductor(1e-3))
+  ///   + Component::from(Capacitor(1e-6));
+  /// assert_eq!(&pushed, built.content());
+  /// assert_eq!(pushed, combined);
+  ///
+  /// // The order of the components in series matters, and so does nesting...
+  /// let swapped = Component::from(Resistor(100.))
+  ///   + Component::from(Capacitor(1e-6))
+  ///   + Component::from(Inductor(1e-3));
+  /// assert_ne!(pushed, swapped);
+  /// let nested = Component::from(Resistor(100.))
+  ///   + (Component::from(Inductor(1e-3)) + Component::from(Capacitor(1e-6)));
+  /// assert_ne!(pushed, nested);
+  /// // ...as well as the values, up to the tolerance
+  /// let close = Component::from(Resistor(100. * (1. + 1e-9)))
+  ///   + Component::from(Inductor(1e-3))
+  ///   + Component::from(Capacitor(1e-6));
+  /// assert_ne!(pushed, close);
+  /// assert!(pushed.approx_eq(&close, 1e-6));
+  /// assert!(!pushed.approx_eq(&swapped, 1e-6));
+  ///
+  /// let poisoned = Component::from(Poisoned);
+  /// assert_ne!(poisoned, poisoned.clone());
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  #[inline]
+  pub fn approx_eq(&self, other: &Self, epsilon: f64) -> bool
+  {
+    self.content.approx_eq(&other.content, epsilon)
+  }
+}
+
+/// Exact structural equality, see [`Component::approx_eq`].
+impl PartialEq for Component
+{
+  #[inline]
+  fn eq(&self, other: &Self) -> bool
+  {
+    self.approx_eq(other, 0f64)
+  }
+}
+
+impl Source
+{
+  /// Compares two sources of the same kind (real or complex), whose pulses,
+  /// voltages and internal impedances differ by at most `epsilon` relatively
+  /// to the largest of them.
+  pub fn approx_eq(&self, other: &Self, epsilon: f64) -> bool
+  {
+    let internal_impedances = match (&self.internal_impedance, &other.internal_impedance) {
+      (Some(a), Some(b)) => approx_eq_fraction(a, b, epsilon),
+      (a, b) => a.is_none() && b.is_none(),
+    };
+    self.complex == other.complex
+      && internal_impedances
+      && self.voltages.len() == other.voltages.len()
+      && self
+        .voltages
+        .iter()
+        .zip(other.voltages.iter())
+        .all(|((a_pulse, a), (b_pulse, b))| {
+          approx_eq_value(*a_pulse, *b_pulse, epsilon) && approx_eq_complex(*a, *b, epsilon)
+        })
+  }
+}
+
+/// Exact equality, see [`Source::approx_eq`].
+impl PartialEq for Source
+{
+  #[inline]
+  fn eq(&self, other: &Self) -> bool
+  {
+    self.approx_eq(other, 0f64)
+  }
+}
+
+impl Circuit
+{
+  /// Compares two circuits by their contents (see [`Component::approx_eq`])
+  /// and their sources (see [`Source::approx_eq`]), the added ones included
+  /// (see [`Circuit::add_source`]). The state of the initialization, the
+  /// numeric tolerance, the initial conditions and the ground are ignored.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   Circuit,
+  ///   CircuitBuilder,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Inductor,
+  ///     Resistor,
+  ///   },
+  /// };
+  ///
+  /// let mut c = Circuit::new();
+  /// c.add_tone(50., 1., 0.)
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(100.)))
+  ///   .push_serie(Component::from(Inductor(1e-3)))
+  ///   .push_serie(Component::from(Capacitor(1e-6)));
+  /// c.init()?;
+  ///
+  /// let mut built = CircuitBuilder::new()
+  ///   .resistor(100.)
+  ///   .inductor(1e-3)
+  ///   .capacitor(1e-6)
+  ///   .build()?;
+  /// // The sources differ until the tone is added
+  /// assert_ne!(c, built);
+  /// built.add_tone(50., 1., 0.);
+  /// assert_eq!(c, built);
+  ///
+  /// built.add_tone(100., 1e-12, 0.);
+  /// assert_ne!(c, built);
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  #[inline]
+  pub fn approx_eq(&self, other: &Self, epsilon: f64) -> bool
+  {
+    self.content().approx_eq(other.content(), epsilon)
+      && self.generator().approx_eq(other.generator(), epsilon)
+      && self.sources().len() == other.sources().len()
+      && self
+        .sources()
+        .iter()
+        .zip(other.sources().iter())
+        .all(|((a_id, a), (b_id, b))| a_id == b_id && a.approx_eq(b, epsilon))
+  }
+}
+
+/// Exact structural equality, see [`Circuit::approx_eq`].
+impl PartialEq for Circuit
+{
+  #[inline]
+  fn eq(&self, other: &Self) -> bool
+  {
+    self.approx_eq(other, 0f64)
+  }
+}
@@ -51,27 +51,39 @@ impl From<IOError> for Error
 pub(crate) fn short_circuit_current<U: Debug, V: Debug, T>(
   id: &Id,
   current: U,
+  pulse: f64,
   impedance: &RatioFrac<V>,
 ) -> Result<T>
 {
   Err(Error::CircuitSolve(format!(
     "Short circuit is caused by a non zero constant current source on a zero admittance \
-     component\n\nComponent Id: {:?}\nCurrent: {:?} A\nImpedance (rational fraction of \
-     pulse):\n{:?}\n-------\n{:?}",
-    id, current, impedance.numerator, impedance.denominator
+     component\n\nComponent Id: {:?}\nPulse: {:?} rad/s\nCurrent: {:?} A\nImpedance \
+     (rational fraction of pulse):\n{:?}\n-------\n{:?}",
+    id, pulse, current, impedance.numerator, impedance.denominator
   )))
 }
 
 pub(crate) fn short_circuit_tension<U: Debug, V: Debug, T>(
   id: &Id,
   tension: U,
+  pulse: f64,
   impedance: &RatioFrac<V>,
 ) -> Result<T>
 {
   Err(Error::CircuitSolve(format!(
     "Short circuit is caused by a non zero constant tension source on a zero impedance \
-     component\n\nComponent Id: {:?}\nTension: {:?} V\nImpedance (rational fraction of \
-     pulse):\n{:?}\n-------\n{:?}",
-    id, tension, impedance.numerator, impedance.denominator
+     component\n\nComponent Id: {:?}\nPulse: {:?} rad/s\nTension: {:?} V\nImpedance \
+     (rational fraction of pulse):\n{:?}\n-------\n{:?}",
+    id, pulse, tension, impedance.numerator, impedance.denominator
+  )))
+}
+
+pub(crate) fn resonance<V: Debug, T>(id: &Id, pulse: f64, impedance: &RatioFrac<V>) -> Result<T>
+{
+  Err(Error::CircuitSolve(format!(
+    "Resonance is caused by a source pulse that coincides with a pole or a zero of the impedance \
+     of a lossless component, making its current or tension infinite\n\nComponent Id: \
+     {:?}\nPulse: {:?} rad/s\nImpedance (rational fraction of pulse):\n{:?}\n-------\n{:?}",
+    id, pulse, impedance.numerator, impedance.denominator
   )))
 }
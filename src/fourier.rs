@@ -7,6 +7,39 @@ use rustfft::{
   FftPlanner,
 };
 
+/// A window weighting the samples of a function before its Fourier transform,
+/// see [`fouriers_windowed`]. The windows other than the rectangular one
+/// vanish at the edges of the interval, trading a wider main lobe for a much
+/// faster decay of the leakage.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum FourierWindow
+{
+  /// No weighting, as in [`fouriers`].
+  #[default]
+  Rectangular,
+  /// `0.5 - 0.5 cos(2πx)`.
+  Hann,
+  /// `0.54 - 0.46 cos(2πx)`.
+  Hamming,
+  /// `0.42 - 0.5 cos(2πx) + 0.08 cos(4πx)`.
+  Blackman,
+}
+
+impl FourierWindow
+{
+  /// Weight of the window at the position `x` of the interval, between 0 and 1.
+  fn weight(self, x: f64) -> f64
+  {
+    let phase = 2f64 * std::f64::consts::PI * x;
+    match self {
+      FourierWindow::Rectangular => 1f64,
+      FourierWindow::Hann => 0.5 - 0.5 * phase.cos(),
+      FourierWindow::Hamming => 0.54 - 0.46 * phase.cos(),
+      FourierWindow::Blackman => 0.42 - 0.5 * phase.cos() + 0.08 * (2f64 * phase).cos(),
+    }
+  }
+}
+
 /// This function takes a real valued function g of period 1/Δf, the fundamental
 /// frequency Δf and a number of frequencies n_freqs > 0. It returns the values
 /// of ĝ(0), ĝ(Δf), ĝ(2Δf), ..., ĝ(n_freqs * Δf) where ĝ is the Fourier
@@ -20,6 +53,11 @@ use rustfft::{
 /// * `fundamental` - A `f64` value representing the fundamental frequency Δf.
 /// * `n_freqs_` - An integer value representing the number of frequencies such
 ///   that n_freqs_ > 0.
+/// * `oversample` - The factor by which the 2 * n_freqs + 1 samples of g
+///   required by Shannon-Nyquist are multiplied to compute the integral. Use 1
+///   by default. Oversampling improves the accuracy of the coefficients of
+///   functions with sharp features, at a cost linear in `oversample`. Only the
+///   first n_freqs + 1 coefficients are returned either way.
 ///
 /// # Returns
 ///
@@ -28,22 +66,174 @@ use rustfft::{
 ///
 /// # Time complexity
 ///
-/// O(n_freqs log n_freqs) (using FFT)
+/// O(oversample * n_freqs log(oversample * n_freqs)) (using FFT)
 ///
 /// # Space complexity
 ///
-/// O(n_freqs)
+/// O(oversample * n_freqs)
+pub fn fouriers<F, I>(g: F, fundamental: f64, n_freqs_: I, oversample: usize) -> Vec<Complex<f64>>
+where
+  F: Fn(f64) -> f64,
+  I: PrimInt,
+{
+  fouriers_with(
+    g,
+    fundamental,
+    n_freqs_,
+    oversample,
+    FourierWindow::Rectangular,
+  )
+}
+
+/// Same as [`fouriers`] without oversampling, but the samples of g are
+/// weighted by a window before the FFT, which reduces the spectral leakage
+/// when g is not exactly periodic over the interval: the energy of a tone
+/// that does not fall on a multiple of Δf spreads over a few neighbouring
+/// coefficients only, instead of all of them. The coefficients are divided by
+/// the mean of the window, so that the constant term ĝ(0) and the amplitudes
+/// of the tones are preserved.
+///
+/// # Example
+///
+/// ```
+/// use circuits_simulator::{
+///   fouriers,
+///   fouriers_windowed,
+///   FourierWindow,
+/// };
+///
+/// // A tone between the 10th and the 11th harmonic of the interval [-1, 1]
+/// let g = |t: f64| 2. + (std::f64::consts::PI * 10.3 * t).sin();
+/// let rectangular = fouriers(g, 0.5, 100, 1);
+/// let hann = fouriers_windowed(g, 0.5, 100, FourierWindow::Hann);
+///
+/// // The constant term is kept...
+/// assert!((hann[0].re - rectangular[0].re).abs() < 1e-2);
+/// // ...while the leakage far from the tone is much weaker
+/// let leakage = |coefs: &[num::Complex<f64>]| coefs[30..].iter().map(|c| c.norm_sqr()).sum::<f64>();
+/// assert!(leakage(&hann) < 1e-3 * leakage(&rectangular));
+/// ```
+pub fn fouriers_windowed<F, I>(
+  g: F,
+  fundamental: f64,
+  n_freqs_: I,
+  window: FourierWindow,
+) -> Vec<Complex<f64>>
+where
+  F: Fn(f64) -> f64,
+  I: PrimInt,
+{
+  fouriers_with(g, fundamental, n_freqs_, 1, window)
+}
+
+/// Same as [`fouriers`] without oversampling, for a complex valued function g
+/// of period 1/Δf, whose spectrum has no conjugate symmetry: both the negative
+/// and the positive frequencies are returned.
+///
+/// # Returns
+///
+/// A `Vec` of the 2 * n_freqs + 1 values ĝ(-n_freqs * Δf), ..., ĝ(0), ...,
+/// ĝ(n_freqs * Δf).
+///
+/// # Panics
+///
+/// Panics if `fundamental` is not positive or if `n_freqs_` is zero.
+///
+/// # Example
+///
+/// ```
+/// use circuits_simulator::fouriers_complex;
+/// use num::Complex;
+///
+/// // A complex exponential turning backwards at the third harmonic of [-1, 1]
+/// let g = |t: f64| Complex::new(0., -3. * std::f64::consts::PI * t).exp() * 2.;
+/// let coefs = fouriers_complex(g, 0.5, 5);
+/// assert_eq!(coefs.len(), 11);
+/// for (k, coef) in coefs.iter().enumerate() {
+///   let expected = if k == 2 { 2. } else { 0. };
+///   assert!((coef - expected).norm() < 1e-12);
+/// }
+/// ```
+pub fn fouriers_complex<F, I>(g: F, fundamental: f64, n_freqs_: I) -> Vec<Complex<f64>>
+where
+  F: Fn(f64) -> Complex<f64>,
+  I: PrimInt,
+{
+  let n_freqs = n_freqs_.to_usize().unwrap();
+  assert!(n_freqs > 0);
+  assert!(fundamental > 0f64);
+
+  // Same sampling as in `fouriers_with`, the negative frequencies being read
+  // on the second half of the spectrum
+  let n = 2 * n_freqs + 1;
+  let t = (fundamental * n as f64).recip();
+  let invn = (n as f64).recip();
+  let halft = 0.5f64 / fundamental;
+
+  let mut vals = (0..n)
+    .map(|i| g(t * (i as f64 + 0.5) - halft))
+    .collect::<Vec<_>>();
+  let mut planner = FftPlanner::new();
+  let fft = planner.plan_fft_forward(n);
+  fft.process(&mut vals);
+
+  (-(n_freqs as isize)..=n_freqs as isize)
+    .map(|k| {
+      vals[k.rem_euclid(n as isize) as usize]
+        * Complex::from_polar(invn, std::f64::consts::PI * k as f64 * (1f64 - invn))
+    })
+    .collect()
+}
+
+/// Computes the Fourier coefficients of the periodic signal whose period is
+/// sampled uniformly by `samples`, from the first sample on: the coefficient of
+/// the harmonic k is `(1/n) Σ x_j exp(-2πi kj / n)` for k up to n / 2, so that
+/// the samples are the constant term plus twice the real parts of the other
+/// terms. The term of the Nyquist frequency of an even number of samples,
+/// which stands for itself only, is halved accordingly.
+///
+/// The FFT handles any number of samples, though it is fastest when its prime
+/// factors are small.
+pub(crate) fn fouriers_samples(samples: &[f64]) -> Vec<Complex<f64>>
+{
+  let n = samples.len();
+  let invn = (n as f64).recip();
+  let mut vals = samples
+    .iter()
+    .map(|sample| Complex::from(*sample))
+    .collect::<Vec<_>>();
+  let mut planner = FftPlanner::new();
+  let fft = planner.plan_fft_forward(n);
+  fft.process(&mut vals);
 
-pub fn fouriers<F, I>(g: F, fundamental: f64, n_freqs_: I) -> Vec<Complex<f64>>
+  vals.truncate(n / 2 + 1);
+  for val in vals.iter_mut() {
+    *val *= invn;
+  }
+  if n.is_multiple_of(2) {
+    vals[n / 2] *= 0.5f64;
+  }
+  vals
+}
+
+/// Computes the coefficients of [`fouriers`] and [`fouriers_windowed`].
+fn fouriers_with<F, I>(
+  g: F,
+  fundamental: f64,
+  n_freqs_: I,
+  oversample: usize,
+  window: FourierWindow,
+) -> Vec<Complex<f64>>
 where
   F: Fn(f64) -> f64,
   I: PrimInt,
 {
   let n_freqs = n_freqs_.to_usize().unwrap();
   assert!(n_freqs > 0);
+  assert!(oversample > 0);
 
   if fundamental.is_sign_negative() {
-    let mut result = fouriers(g, -fundamental, n_freqs_);
+    let mut result = fouriers_with(g, -fundamental, n_freqs_, oversample, window);
     for c in result.iter_mut() {
       c.im = -c.im;
     }
@@ -51,12 +241,13 @@ where
   }
 
   let delta_f = fundamental;
-  let n = 2 * n_freqs + 1;
+  let n = oversample * (2 * n_freqs + 1);
   // We take the 0 frequency and make sure we do enough samples for an integration
   // on the interval [-1/2Δf, 1/2Δf]. Since we want the coefficients up to the
   // frequency n_freqs * Δf, we need to take double samples to satisfy
   // Shannon-Nyquist, perform a FFT on the samples and only keep the first
-  // (correct) half of the spectrum.
+  // (correct) half of the spectrum. Oversampling only adds samples, the
+  // coefficients we keep are still the first n_freqs + 1 of the spectrum.
 
   let t = (delta_f * n as f64).recip();
   let invn = (n as f64).recip();
@@ -68,22 +259,31 @@ where
       im: 0f64,
     })
     .collect::<Vec<_>>();
+  // The coefficients are normalized by the sum of the weights of the window,
+  // which is n for the rectangular one
+  let mut scale = invn;
+  if window != FourierWindow::Rectangular {
+    let mut weights_sum = 0f64;
+    for (i, val) in vals.iter_mut().enumerate() {
+      let weight = window.weight((i as f64 + 0.5) * invn);
+      *val *= weight;
+      weights_sum += weight;
+    }
+    scale = weights_sum.recip();
+  }
 
   if fundamental.is_zero() {
-    return vec![vals.iter().sum::<Complex<f64>>() * invn];
+    return vec![vals.iter().sum::<Complex<f64>>() * scale];
   }
 
   let mut planner = FftPlanner::new();
   let fft = planner.plan_fft_forward(n);
   fft.process(&mut vals);
 
-  let mut change_sign = false;
-  for val in vals.iter_mut().take(n_freqs + 1) {
-    *val *= invn;
-    if change_sign {
-      *val = -*val;
-    }
-    change_sign = !change_sign;
+  // The k-th sample is taken at t_k = (k + 1/2) / (nΔf) - 1/2Δf, so the k-th
+  // coefficient of the FFT must be shifted by a phase of πk(1 - 1/n)
+  for (k, val) in vals.iter_mut().take(n_freqs + 1).enumerate() {
+    *val *= Complex::from_polar(scale, std::f64::consts::PI * k as f64 * (1f64 - invn));
   }
   vals.truncate(n_freqs + 1); // We only keep the half of the spectrum that
                               // follows the Shannon-Nyquist criterion
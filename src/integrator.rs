@@ -0,0 +1,520 @@
+//! Time-domain integration of the circuit, as an alternative to the spectral
+//! solution for transients.
+//!
+//! The `transient` module already gives exact transients, as natural modes
+//! superposed on the spectral steady state (see
+//! [`Circuit::emulate_one_with_initial_conditions`]). That requires the
+//! capacitors and inductors to be independent, so that the modes can reproduce
+//! any initial state, and the natural pulses to be found as the zeros of the
+//! impedance of the circuit. Stepping the circuit in time lifts these
+//! restrictions, e.g. for capacitors directly in parallel, at the cost of a
+//! time step small enough for the dynamics of the circuit and of supporting
+//! resistors, capacitors and inductors only.
+//!
+//! The tensions of the capacitors and the currents of the inductors are stepped
+//! forward with the trapezoidal rule, which is second order and A-stable. Over
+//! a step h, a capacitor behaves as a resistance h/2C in series with a tension
+//! depending on its previous state, and an inductor as a resistance 2L/h in
+//! series with such a tension: every component of the tree then reduces to a
+//! Thevenin equivalent, and the circuit is solved by combining them from the
+//! leaves up to the source, then splitting the current and the tension from the
+//! source down to the leaves.
+//!
+//! At t = 0, the capacitors are tension sources fixed by their initial
+//! tensions, and the inductors current sources fixed by their initial
+//! currents. Tension sources in parallel share the current in proportion of
+//! their capacitances, and current sources in series share the tension in
+//! proportion of their inductances, as their derivatives are then equal.
+
+use std::collections::HashMap;
+
+use num::Complex;
+use num_traits::Zero;
+
+use crate::{
+  emulation::{
+    check_time_grid,
+    EmulationData,
+  },
+  error::{
+    Error::{
+      CircuitBuild,
+      CircuitSolve,
+    },
+    Result,
+  },
+  util::is_constant,
+  Circuit,
+  Component,
+  ComponentContent,
+  Dipole,
+  Id,
+};
+
+/// Relation between the tension across a component and the current through
+/// it, at a given time step.
+#[derive(Clone, Copy, Debug)]
+enum Port
+{
+  /// `v = r i + e`.
+  Affine
+  {
+    r: f64, e: f64
+  },
+  /// `v = e` whatever the current. `c` is the capacitance of the tension
+  /// source.
+  Tension
+  {
+    e: f64, c: f64
+  },
+  /// `i = j` whatever the tension. `l` is the inductance of the current source.
+  Current
+  {
+    j: f64, l: f64
+  },
+}
+
+/// Checks that two values fixed by different sources match.
+fn same(a: f64, b: f64) -> bool
+{
+  (a - b).abs() <= 1e-9 * (a.abs() + b.abs())
+}
+
+/// Combines the ports of two components in series.
+fn series(a: Port, b: Port, id: &Id) -> Result<Port>
+{
+  use Port::*;
+  Ok(match (a, b) {
+    (Current { j: j1, l: l1 }, Current { j: j2, l: l2 }) => {
+      if !same(j1, j2) {
+        return Err(CircuitSolve(format!(
+          "The component of id {id:?} puts in series inductors with different currents ({j1} A \
+           and {j2} A)"
+        )));
+      }
+      Current { j: j1, l: l1 + l2 }
+    },
+    (Current { j, l }, _) | (_, Current { j, l }) => Current { j, l },
+    (Tension { e: e1, c: c1 }, Tension { e: e2, c: c2 }) => Tension {
+      e: e1 + e2,
+      c: c1 * c2 / (c1 + c2),
+    },
+    (Affine { r: r1, e: e1 }, Affine { r: r2, e: e2 }) => Affine {
+      r: r1 + r2,
+      e: e1 + e2,
+    },
+    (Affine { r, e: e1 }, Tension { e: e2, .. }) | (Tension { e: e2, .. }, Affine { r, e: e1 }) =>
+      Affine { r, e: e1 + e2 },
+  })
+}
+
+/// Combines the ports of two components in parallel.
+fn parallel(a: Port, b: Port, id: &Id) -> Result<Port>
+{
+  use Port::*;
+  Ok(match (a, b) {
+    (Tension { e: e1, c: c1 }, Tension { e: e2, c: c2 }) => {
+      if !same(e1, e2) {
+        return Err(CircuitSolve(format!(
+          "The component of id {id:?} puts in parallel capacitors with different tensions ({e1} \
+           V and {e2} V)"
+        )));
+      }
+      Tension { e: e1, c: c1 + c2 }
+    },
+    (Tension { e, c }, _) | (_, Tension { e, c }) => Tension { e, c },
+    (Current { j: j1, l: l1 }, Current { j: j2, l: l2 }) => Current {
+      j: j1 + j2,
+      l: l1 * l2 / (l1 + l2),
+    },
+    (Affine { r: r1, e: e1 }, Affine { r: r2, e: e2 }) => {
+      let r = (r1.recip() + r2.recip()).recip();
+      Affine {
+        r,
+        e: r * (e1 / r1 + e2 / r2),
+      }
+    },
+    (Affine { r, e }, Current { j, .. }) | (Current { j, .. }, Affine { r, e }) =>
+      Affine { r, e: e - r * j },
+  })
+}
+
+/// State of the integration, with the components of the circuit flattened in
+/// depth-first order.
+struct Integrator<'a>
+{
+  components: Vec<&'a Component>,
+  /// Index following the subtree of each component.
+  ends:       Vec<usize>,
+  ports:      Vec<Port>,
+  /// The current through each component, the tension across it and the
+  /// potential of its fore node, at the last solved time.
+  values:     Vec<(f64, f64, f64)>,
+}
+
+impl<'a> Integrator<'a>
+{
+  fn new(root: &'a Component) -> Self
+  {
+    fn flatten<'a>(
+      component: &'a Component,
+      components: &mut Vec<&'a Component>,
+      ends: &mut Vec<usize>,
+    )
+    {
+      let index = components.len();
+      components.push(component);
+      ends.push(0);
+      if let ComponentContent::Series(children) | ComponentContent::Parallel(children) =
+        &component.content
+      {
+        for child in children.iter() {
+          flatten(child, components, ends);
+        }
+      }
+      ends[index] = components.len();
+    }
+    let (mut components, mut ends) = (Vec::new(), Vec::new());
+    flatten(root, &mut components, &mut ends);
+    let n = components.len();
+    Self {
+      components,
+      ends,
+      ports: vec![Port::Affine { r: 0f64, e: 0f64 }; n],
+      values: vec![(0f64, 0f64, 0f64); n],
+    }
+  }
+
+  /// Indices of the children of a component.
+  fn children(&self, index: usize) -> impl Iterator<Item = usize> + '_
+  {
+    let mut child = index + 1;
+    std::iter::from_fn(move || {
+      if child >= self.ends[index] {
+        return None;
+      }
+      let current = child;
+      child = self.ends[child];
+      Some(current)
+    })
+  }
+
+  /// Computes the ports of a component and of its subcomponents, for the step
+  /// `step` following the last solved time, or at t = 0 if `step` is `None`.
+  fn init_ports(
+    &mut self,
+    index: usize,
+    step: Option<f64>,
+    initial_conditions: &HashMap<Id, f64>,
+  ) -> Result<Port>
+  {
+    let component = self.components[index];
+    let id = &component.fore_node_id;
+    let port = match &component.content {
+      ComponentContent::Series(_) | ComponentContent::Parallel(_) => {
+        let is_series = matches!(component.content, ComponentContent::Series(_));
+        let mut port = None;
+        for child in self.children(index).collect::<Vec<_>>() {
+          let child_port = self.init_ports(child, step, initial_conditions)?;
+          port = Some(match port {
+            None => child_port,
+            Some(port) if is_series => series(port, child_port, id)?,
+            Some(port) => parallel(port, child_port, id)?,
+          });
+        }
+        port.ok_or_else(|| CircuitBuild(format!("The component of id {id:?} is empty")))?
+      },
+      ComponentContent::Simple(Dipole::Resistor(r)) if *r != 0f64 =>
+        Port::Affine { r: *r, e: 0f64 },
+      ComponentContent::Simple(Dipole::Capacitor(c)) if *c != 0f64 => {
+        let (current, tension, _) = self.values[index];
+        match step {
+          None => Port::Tension {
+            e: initial_conditions.get(id).copied().unwrap_or_default(),
+            c: *c,
+          },
+          Some(step) => {
+            let r = 0.5 * step / c;
+            Port::Affine {
+              r,
+              e: tension + r * current,
+            }
+          },
+        }
+      },
+      ComponentContent::Simple(Dipole::Inductor(l)) if *l != 0f64 => {
+        let (current, tension, _) = self.values[index];
+        match step {
+          None => Port::Current {
+            j: initial_conditions.get(id).copied().unwrap_or_default(),
+            l: *l,
+          },
+          Some(step) => {
+            let r = 2f64 * l / step;
+            Port::Affine {
+              r,
+              e: -r * current - tension,
+            }
+          },
+        }
+      },
+      ComponentContent::Simple(dipole) =>
+        return Err(CircuitBuild(format!(
+          "The transient solver only supports resistors, capacitors and inductors of nonzero \
+           values, found {dipole:?} at id {id:?}"
+        ))),
+      ComponentContent::Poisoned =>
+        return Err(CircuitBuild(format!(
+          "The component of id {id:?} is poisoned"
+        ))),
+    };
+    self.ports[index] = port;
+    Ok(port)
+  }
+
+  /// Splits a current and a tension over a component and its subcomponents,
+  /// according to their ports.
+  fn propagate(&mut self, index: usize, current: f64, tension: f64, potential: f64)
+  {
+    self.values[index] = (current, tension, potential);
+    let children = self.children(index).collect::<Vec<_>>();
+    match self.components[index].content {
+      ComponentContent::Series(_) => {
+        // Tensions of the children, the current sources sharing what remains
+        let mut free_tension = tension;
+        let mut total_inductance = 0f64;
+        let mut tensions = children
+          .iter()
+          .map(|&child| match self.ports[child] {
+            Port::Affine { r, e } => Some(r * current + e),
+            Port::Tension { e, .. } => Some(e),
+            Port::Current { l, .. } => {
+              total_inductance += l;
+              None
+            },
+          })
+          .inspect(|tension| free_tension -= tension.unwrap_or_default())
+          .collect::<Vec<_>>();
+        let mut remaining_potential = potential;
+        for (&child, child_tension) in children.iter().zip(tensions.iter_mut()) {
+          let child_tension = child_tension.unwrap_or_else(|| match self.ports[child] {
+            Port::Current { l, .. } => free_tension * l / total_inductance,
+            _ => unreachable!(),
+          });
+          self.propagate(child, current, child_tension, remaining_potential);
+          remaining_potential -= child_tension;
+        }
+      },
+      ComponentContent::Parallel(_) => {
+        // Currents of the children, the tension sources sharing what remains
+        let mut free_current = current;
+        let mut total_capacitance = 0f64;
+        let mut currents = children
+          .iter()
+          .map(|&child| match self.ports[child] {
+            Port::Affine { r, e } => Some((tension - e) / r),
+            Port::Current { j, .. } => Some(j),
+            Port::Tension { c, .. } => {
+              total_capacitance += c;
+              None
+            },
+          })
+          .inspect(|current| free_current -= current.unwrap_or_default())
+          .collect::<Vec<_>>();
+        for (&child, child_current) in children.iter().zip(currents.iter_mut()) {
+          let child_current = child_current.unwrap_or_else(|| match self.ports[child] {
+            Port::Tension { c, .. } => free_current * c / total_capacitance,
+            _ => unreachable!(),
+          });
+          self.propagate(child, child_current, tension, potential);
+        }
+      },
+      _ => (),
+    }
+  }
+}
+
+impl Circuit
+{
+  /// Integrates the circuit in the time domain from given initial conditions,
+  /// instead of superposing its steady-state responses to the pulses of the
+  /// source. It handles switching transients and sources that are not
+  /// periodic over the emulation, at the cost of a step small enough for the
+  /// dynamics of the circuit. See the `integrator` module for the method.
+  ///
+  /// The circuit must only be made of resistors, capacitors and inductors of
+  /// nonzero values, the internal impedance of the source, if any, must be a
+  /// resistance, and no source must be added with [`Circuit::add_source`].
+  ///
+  /// # Arguments
+  ///
+  /// * `duration` - The duration of the integration in seconds
+  /// * `step` - The time step of the integration in seconds
+  /// * `node_ids` - The IDs of the nodes to return
+  /// * `initial_conditions` - A map from the IDs of capacitors to their
+  ///   tensions and from the IDs of inductors to their currents at t = 0. It
+  ///   completes and overrides the initial conditions stored in the circuit
+  ///   (see [`Circuit::set_initial_voltage`]). Missing capacitors and inductors
+  ///   are considered uncharged.
+  ///
+  /// # Returns
+  ///
+  /// For each node of `node_ids`, the currents, tensions and potentials at
+  /// the times `k * step`, as [`Circuit::emulate_many`] does.
+  ///
+  /// # Errors
+  ///
+  /// Returns a `CircuitBuild` error if the circuit holds other dipoles or
+  /// added sources, if an ID of `node_ids` does not exist or if an ID of
+  /// `initial_conditions` is neither a capacitor nor an inductor. Returns a
+  /// `CircuitSolve` error if the time grid is degenerate or if the initial
+  /// state is inconsistent: capacitors in parallel with different tensions,
+  /// inductors in series with different currents, or capacitors directly fed
+  /// by an ideal source (whose current at t = 0 is then undetermined).
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use std::collections::HashMap;
+  ///
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  /// };
+  /// use num::Complex;
+  ///
+  /// // A sine wave of period 5 ms feeds an RC circuit whose time constant is
+  /// // 1 ms, with the capacitor charged at 1 V
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(400. * std::f64::consts::PI, Complex::new(0., -0.5))
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(1e3)))
+  ///   .push_serie(Component::from(Capacitor(1e-6)));
+  ///
+  /// let (duration, step) = (2e-2, 1e-6);
+  /// let initial_conditions = HashMap::from([(id![1], 1.)]);
+  /// let transient = c.transient(duration, step, &[id![1]], &initial_conditions)?;
+  /// let (_, tensions, _) = &transient[0];
+  /// assert_eq!(tensions.len(), 20000);
+  /// assert!((tensions[0] - 1.).abs() < 1e-12);
+  ///
+  /// // After 15 time constants, the transient has vanished and the integration
+  /// // matches the spectral solution
+  /// let (_, steady_tensions, _) = c.emulate_one(duration, step, &id![1])?;
+  /// for k in 15000..20000 {
+  ///   assert!((tensions[k] - steady_tensions[k]).abs() < 1e-6);
+  /// }
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn transient(
+    &mut self,
+    duration: f64,
+    step: f64,
+    node_ids: &[Id],
+    initial_conditions: &HashMap<Id, f64>,
+  ) -> Result<Vec<EmulationData>>
+  {
+    check_time_grid(duration, step)?;
+    if let Some((id, _)) = self.sources().first() {
+      return Err(CircuitBuild(format!(
+        "The transient solver does not support the source added before the component of \
+         id {id:?}"
+      )));
+    }
+    let initial_conditions = &self.merged_initial_conditions(initial_conditions);
+    for id in initial_conditions.keys() {
+      if !matches!(
+        self.get_comp_by_id(id).map(|component| &component.content),
+        Some(ComponentContent::Simple(
+          Dipole::Capacitor(_) | Dipole::Inductor(_)
+        ))
+      ) {
+        return Err(CircuitBuild(format!(
+          "Component of id {id:?} is neither a capacitor nor an inductor and cannot have an \
+           initial condition"
+        )));
+      }
+    }
+    let internal_resistance = match &self.generator().internal_impedance {
+      None => 0f64,
+      Some(impedance) if is_constant(impedance) => {
+        let value = impedance.eval(Complex::zero());
+        if value.im != 0f64 {
+          return Err(CircuitBuild(format!(
+            "The transient solver requires a real internal resistance, found {value}"
+          )));
+        }
+        value.re
+      },
+      Some(_) =>
+        return Err(CircuitBuild(
+          "The transient solver requires the internal impedance of the source to be a resistance"
+            .to_string(),
+        )),
+    };
+
+    let mut integrator = Integrator::new(self.content());
+    let indices = node_ids
+      .iter()
+      .map(|id| {
+        integrator
+          .components
+          .iter()
+          .position(|component| component.fore_node_id == *id)
+          .ok_or_else(|| CircuitBuild(format!("The node of id {id:?} does not exist")))
+      })
+      .collect::<Result<Vec<_>>>()?;
+    let ground = self
+      .ground()
+      .map(|id| {
+        integrator
+          .components
+          .iter()
+          .position(|component| component.fore_node_id == *id)
+          .ok_or_else(|| CircuitSolve(format!("Ground node of id {id:?} not found")))
+      })
+      .transpose()?;
+
+    let nb_iter = (duration / step).ceil() as usize;
+    let mut results = vec![
+      (
+        Vec::with_capacity(nb_iter),
+        Vec::with_capacity(nb_iter),
+        Vec::with_capacity(nb_iter)
+      );
+      node_ids.len()
+    ];
+    for k in 0..nb_iter {
+      let port = integrator.init_ports(0, (k > 0).then_some(step), initial_conditions)?;
+      let source_tension = self.generator().tension(k as f64 * step);
+      let (current, tension) = match port {
+        Port::Affine { r, e } => {
+          let current = (source_tension - e) / (r + internal_resistance);
+          (current, source_tension - internal_resistance * current)
+        },
+        Port::Current { j, .. } => (j, source_tension - internal_resistance * j),
+        Port::Tension { e, .. } if internal_resistance != 0f64 =>
+          ((source_tension - e) / internal_resistance, e),
+        Port::Tension { .. } =>
+          return Err(CircuitSolve(
+            "The ideal source directly feeds capacitors, whose current at t = 0 is undetermined: \
+             give the source an internal resistance"
+              .to_string(),
+          )),
+      };
+      integrator.propagate(0, current, tension, tension);
+      for (&index, (currents, tensions, potentials)) in indices.iter().zip(results.iter_mut()) {
+        let (current, tension, potential) = integrator.values[index];
+        currents.push(current);
+        tensions.push(tension);
+        potentials.push(potential - ground.map_or(0f64, |ground| integrator.values[ground].2));
+      }
+    }
+    Ok(results)
+  }
+}
@@ -0,0 +1,172 @@
+use crate::{
+  error::{
+    Error::CircuitSolve,
+    Result,
+  },
+  Circuit,
+  Component,
+  ComponentContent,
+  Id,
+};
+
+/// The current through a component, the tension across it and the potential
+/// of its fore node at a given time.
+struct Signals
+{
+  current:   f64,
+  tension:   f64,
+  potential: f64,
+}
+
+impl Circuit
+{
+  /// Reconstructs the signals of a component at a given time.
+  fn signals(&self, id: &Id, time: f64) -> Result<Signals>
+  {
+    let node = self
+      .get_node(id)
+      .ok_or_else(|| CircuitSolve(format!("Node of id {id:?} not found")))?;
+    Ok(Signals {
+      current:   self.reconstruct(&node.currents, time),
+      tension:   self.reconstruct(&node.next_component_tensions, time),
+      potential: self.reconstruct(&node.potentials, time),
+    })
+  }
+
+  /// Checks the Kirchhoff's laws between a component and its subcomponents,
+  /// then in each subcomponent.
+  fn check_kirchhoff(&self, component: &Component, time: f64, tol: f64) -> Result<()>
+  {
+    let components = match &component.content {
+      ComponentContent::Series(components) | ComponentContent::Parallel(components) => components,
+      _ => return Ok(()),
+    };
+    let id = &component.fore_node_id;
+    let outer = self.signals(id, time)?;
+    let inner = components
+      .iter()
+      .map(|component| self.signals(&component.fore_node_id, time))
+      .collect::<Result<Vec<_>>>()?;
+    let kcl = |node: &Id, expected: f64, actual: f64| {
+      if (expected - actual).abs() > tol {
+        return Err(CircuitSolve(format!(
+          "Kirchhoff's current law is violated at the node of id {node:?} at t = {time:?} s: \
+           {expected:?} A enter it but {actual:?} A leave it"
+        )));
+      }
+      Ok(())
+    };
+    let kvl = |expected: f64, actual: f64| {
+      if (expected - actual).abs() > tol {
+        return Err(CircuitSolve(format!(
+          "Kirchhoff's voltage law is violated around the loop of the component of id {id:?} \
+           and its subcomponents at t = {time:?} s: {expected:?} V across the component but \
+           {actual:?} V across the subcomponents"
+        )));
+      }
+      Ok(())
+    };
+
+    if let ComponentContent::Series(_) = component.content {
+      // The same current flows through each node between two subcomponents, and
+      // the potential drops along the chain
+      let mut potential = outer.potential;
+      for (component, signals) in components.iter().zip(inner.iter()) {
+        kcl(&component.fore_node_id, outer.current, signals.current)?;
+        kvl(potential, signals.potential)?;
+        potential -= signals.tension;
+      }
+      kvl(
+        outer.tension,
+        inner.iter().map(|signals| signals.tension).sum(),
+      )?;
+    } else {
+      // The current splits at the fore node, each branch holding the tension of
+      // the component
+      kcl(
+        id,
+        outer.current,
+        inner.iter().map(|signals| signals.current).sum(),
+      )?;
+      for signals in inner.iter() {
+        kvl(outer.tension, signals.tension)?;
+        kvl(outer.potential, signals.potential)?;
+      }
+    }
+
+    for component in components.iter() {
+      self.check_kirchhoff(component, time, tol)?;
+    }
+    Ok(())
+  }
+
+  /// Checks that the solution of the circuit satisfies the Kirchhoff's laws at
+  /// a given time, as a self-check of the solver and of the reconstruction of
+  /// the signals. The circuit is a tree of series and parallel combinations,
+  /// whose constraints are checked between each combination and its
+  /// subcomponents:
+  /// - in a series combination, the current through each subcomponent is the
+  ///   current through the combination (current law at the inner nodes), the
+  ///   tensions of the subcomponents add up to the tension of the combination
+  ///   and the potentials drop accordingly along the chain (voltage law);
+  /// - in a parallel combination, the currents through the subcomponents add up
+  ///   to the current through the combination (current law at the fore node),
+  ///   and each subcomponent holds the tension of the combination (voltage law
+  ///   around the loops formed by two branches);
+  /// - the root component closes the loop of the source: its tension is the
+  ///   potential of its fore node, the aft node being the ground.
+  ///
+  /// # Arguments
+  ///
+  /// * `time` - The time at which the signals are reconstructed, in seconds.
+  /// * `tol` - The absolute tolerance on the currents (in amperes) and on the
+  ///   tensions (in volts).
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the initialization of the circuit fails, or a
+  /// `CircuitSolve` error naming the first node or loop at which a law is
+  /// violated beyond the tolerance.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Inductor,
+  ///     Resistor,
+  ///   },
+  /// };
+  /// use num::Complex;
+  ///
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(1e4, Complex::new(0., -0.5))
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(200.)))
+  ///   .push_serie(Component::from(Capacitor(10e-9)));
+  /// c.get_comp_by_id_mut(&[1])
+  ///   .unwrap()
+  ///   .push_parallel(Component::from(Inductor(100e-3)));
+  ///
+  /// for k in 0..10 {
+  ///   c.verify_kirchhoff(k as f64 * 1e-4, 1e-9)?;
+  /// }
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn verify_kirchhoff(&mut self, time: f64, tol: f64) -> Result<()>
+  {
+    self.init()?;
+    let root = self.signals(&self.content().fore_node_id, time)?;
+    if (root.tension - root.potential).abs() > tol {
+      return Err(CircuitSolve(format!(
+        "Kirchhoff's voltage law is violated around the loop of the source at t = {time:?} s: \
+         the root component holds {:?} V but its fore node is at {:?} V",
+        root.tension, root.potential
+      )));
+    }
+    self.check_kirchhoff(self.content(), time, tol)
+  }
+}
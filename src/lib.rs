@@ -5,14 +5,17 @@
 //! coefficients. This crate makes extensive use of complex number
 //! representation for electronics, including the Fourier transform.
 //!
-//! A circuit can only be made of one source and a tree of components. Said
+//! A circuit is made of a main source and a tree of components. Said
 //! components are either made of parallel or serial combinations of other
-//! components.
+//! components. Other sources can be added in series with the main one, see
+//! [`Circuit::add_source`].
 //!
 //! # Example
 //! ```
 //! // Emulate a serial RLC circuit with a square wave of period
 //! // 4 ms that starts after 2 ms as input
+//! # #[cfg(feature = "fft")]
+//! # {
 //! use std::{
 //!   env,
 //!   time::Instant,
@@ -52,8 +55,8 @@
 //!   .push_serie(Component::from(Capacitor(10e-9))) // 10 nF at position [1]
 //!   .push_serie(Component::from(Inductor(100e-3))); // 100 mH at position [2]
 //!
-//! // With this, the attenuation time is 500 µs
-//! // and the pseudo-period is close to 200 µs
+//! // With this, the attenuation time is 1 ms and the pseudo-period is close
+//! // to 200 µs, as given by `c.second_order_parameters()`
 //!
 //! // Alternatively, we could have used the following:
 //! // `c.get_comp_by_id_mut(&[]).unwrap().push_serie(...)`
@@ -65,16 +68,116 @@
 //!   .emulate_many(duration, step, &vec![vec![], vec![1u8]])
 //!   .unwrap(); // May return an error message if a short circuit is detected
 //! let time_required = start.elapsed().as_secs_f64();
+//! # }
+//! ```
+//!
+//! # Features
+//!
+//! The `fft` feature, enabled by default, builds the sources from functions
+//! or samples through their Fourier series (see `Source::set_fn`), which
+//! requires `rustfft`. Without it, e.g. for embedded hardware-in-the-loop
+//! targets, the circuits, their impedances and their emulations remain
+//! available, the sources being built pulse by pulse (see
+//! [`Source::add_pulse`] and [`Source::add_tone`]). The examples of this
+//! documentation that need the Fourier series are only run with it, the
+//! others, like this one, run either way:
+//!
+//! ```
+//! use circuits_simulator::{
+//!   id,
+//!   Circuit,
+//!   Component,
+//!   Dipole::{
+//!     Capacitor,
+//!     Resistor,
+//!   },
+//! };
+//!
+//! // An RC low-pass filter driven at its cutoff pulse of 1000 rad/s
+//! let mut c = Circuit::new();
+//! c.add_tone(1e3 / (2. * std::f64::consts::PI), 1., 0.)
+//!   .content_mut()
+//!   .push_serie(Component::from(Resistor(1e3)))
+//!   .push_serie(Component::from(Capacitor(1e-6)));
+//!
+//! c.init()?;
+//! assert!((c.content().impedance(1e3).norm() - 1e3 * 2f64.sqrt()).abs() < 1e-9);
+//! let (_, tensions, _) = c.emulate_one(1e-2, 1e-5, &id![1])?;
+//! let peak = tensions
+//!   .iter()
+//!   .fold(0f64, |peak, tension| peak.max(tension.abs()));
+//! assert!((peak - 0.5f64.sqrt()).abs() < 1e-3);
+//! # Ok::<(), circuits_simulator::Error>(())
 //! ```
 
+mod analysis;
+mod builder;
+mod digital;
+mod display;
+mod dual;
 mod emulation;
+mod energy;
+mod equality;
 mod error;
+#[cfg(feature = "fft")]
 mod fourier;
+mod integrator;
+mod kirchhoff;
+mod loading;
+mod monte_carlo;
+mod overrides;
+mod power;
+mod precision;
+mod probe;
+mod report;
+mod resonance;
+mod responses;
+mod sampling;
+mod scaling;
+mod spectrum;
+mod spice;
 mod structs;
+mod superposition;
+mod thevenin;
+mod transfer;
+mod transient;
+mod units;
 mod util;
+mod validation;
 
+pub use analysis::CriticalKind;
+pub use builder::CircuitBuilder;
 pub use error::{
   Error,
   Result,
 };
+#[cfg(feature = "fft")]
+pub use fourier::{
+  fouriers,
+  fouriers_complex,
+  fouriers_windowed,
+  FourierWindow,
+};
+pub use precision::{
+  DefaultPrecision,
+  ExtendedPrecision,
+  Precision,
+  StandardPrecision,
+};
+pub use probe::{
+  ProbeKind,
+  ProbeSet,
+  ProbeSetBuilder,
+};
+pub use sampling::AliasingWarning;
+pub use spectrum::{
+  FrequencyDomainResult,
+  NodePhasors,
+};
 pub use structs::*;
+pub use transfer::{
+  Bode,
+  Quantity,
+};
+pub use transient::TransientMode;
+pub use units::scale_for_display;
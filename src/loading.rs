@@ -0,0 +1,105 @@
+use num::Complex;
+
+use crate::{
+  error::{
+    Error::CircuitBuild,
+    Result,
+  },
+  Circuit,
+  Component,
+  ComponentContent,
+  Dipole,
+};
+
+/// Attaches a load across the output of a component, that is in parallel with
+/// its last series component, or with the whole component if it is not a
+/// series combination. The components holding the load are uninitialized.
+fn attach_load(component: &mut Component, load: Component)
+{
+  let id = match &component.content {
+    ComponentContent::Series(components) => vec![components.len().saturating_sub(1) as u8],
+    _ => vec![],
+  };
+  component
+    .get_comp_by_id_mut_uninit(&id)
+    .expect("A series combination has at least one component")
+    .push_parallel(load);
+}
+
+impl Circuit
+{
+  /// Computes the impedance seen by the source when a load is connected to the
+  /// output of the circuit, which shows how the load reflects back to the
+  /// input. The output is taken across the last component of the root series
+  /// combination (e.g. the capacitor of an RC low-pass filter), or across the
+  /// whole circuit if its root is not a series combination.
+  ///
+  /// The load is attached to a copy of the circuit, which is left untouched.
+  ///
+  /// # Arguments
+  ///
+  /// * `load` - The dipole connected across the output.
+  /// * `pulses` - The pulses at which the input impedance is evaluated.
+  ///
+  /// # Returns
+  ///
+  /// The pairs `(pulse, impedance)` for each pulse of `pulses`.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the circuit is empty or if the impedance of the loaded
+  /// circuit cannot be computed.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  /// };
+  ///
+  /// let mut c = Circuit::new();
+  /// c.content_mut()
+  ///   .push_serie(Component::from(Resistor(1e3)))
+  ///   .push_serie(Component::from(Capacitor(1e-6)));
+  ///
+  /// // Unloaded, the filter draws no constant current. Loaded by 1 kΩ, the
+  /// // source sees both resistors in series
+  /// let table = c.input_impedance_with_load(&Resistor(1e3), &[0.])?;
+  /// assert!((table[0].1 - 2e3).norm() < 1e-9);
+  ///
+  /// // The load is accounted for after the initialization of the circuit too
+  /// c.get_comp_by_id_mut(&[1])
+  ///   .unwrap()
+  ///   .push_parallel(Component::from(Resistor(1e3)));
+  /// c.init()?;
+  /// let table = c.input_impedance_with_load(&Resistor(1e3), &[0.])?;
+  /// assert!((table[0].1 - 1.5e3).norm() < 1e-9);
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn input_impedance_with_load(
+    &self,
+    load: &Dipole,
+    pulses: &[f64],
+  ) -> Result<Vec<(f64, Complex<f64>)>>
+  {
+    if let ComponentContent::Poisoned = self.content().content {
+      return Err(CircuitBuild(
+        "Cannot attach a load to an empty circuit".to_string(),
+      ));
+    }
+    let mut loaded = self.content().clone();
+    attach_load(&mut loaded, Component::from(load.clone()));
+    loaded.init_impedance()?;
+    Ok(
+      pulses
+        .iter()
+        .map(|&pulse| (pulse, loaded.impedance(pulse)))
+        .collect(),
+    )
+  }
+}
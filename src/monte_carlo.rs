@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+
+use crate::{
+  emulation::{
+    check_time_grid,
+    EmulationData,
+  },
+  error::{
+    Error::CircuitBuild,
+    Result,
+  },
+  util::SplitMix64,
+  Circuit,
+  ComponentContent,
+  Dipole,
+  Id,
+};
+
+impl Circuit
+{
+  /// Emulates a node of the circuit `runs` times, for yield analysis, the
+  /// values of some resistors, capacitors and inductors being drawn anew at
+  /// each run: the nominal value of the component of ID `id` is multiplied by
+  /// a factor drawn uniformly between `1 - tolerance` and `1 + tolerance`,
+  /// where `tolerances[id]` is the relative tolerance (e.g. 0.05 for 5 %). The
+  /// circuit itself is left unchanged.
+  ///
+  /// The factors are drawn from a pseudo-random generator seeded with `seed`,
+  /// in the order of the IDs, so that a given seed always gives the same runs.
+  ///
+  /// # Arguments
+  ///
+  /// * `tolerances` - The relative tolerances of the components, by ID
+  /// * `runs` - The number of runs
+  /// * `node_id` - The ID of the node to emulate
+  /// * `duration` - The duration of the emulation in seconds
+  /// * `step` - The time step used for the emulation in seconds
+  /// * `seed` - The seed of the pseudo-random generator
+  ///
+  /// # Returns
+  ///
+  /// The currents, tensions and potentials of the node for each run, as
+  /// [`Circuit::emulate_one`] gives them.
+  ///
+  /// # Errors
+  ///
+  /// Returns a `CircuitBuild` error if a component of `tolerances` does not
+  /// exist or is not a resistor, a capacitor or an inductor, or if a tolerance
+  /// is not between 0 (included) and 1 (excluded). Returns an error if a run
+  /// fails as [`Circuit::emulate_one`] does.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use std::collections::HashMap;
+  ///
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  /// };
+  /// use num::Complex;
+  ///
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(1e3, Complex::new(0., -0.5))
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(1e3)))
+  ///   .push_serie(Component::from(Capacitor(1e-6)));
+  /// let (duration, step) = (1e-2, 1e-4);
+  /// let nominal = c.emulate_one(duration, step, &id![1])?;
+  ///
+  /// // Without tolerance, all the runs are the nominal one
+  /// let tolerances = HashMap::from([(id![0], 0.), (id![1], 0.)]);
+  /// let runs = c.monte_carlo(&tolerances, 5, &id![1], duration, step, 7)?;
+  /// assert_eq!(runs.len(), 5);
+  /// assert!(runs.iter().all(|run| *run == nominal));
+  ///
+  /// // With 5 % resistors and 10 % capacitors, the runs differ but are
+  /// // reproducible
+  /// let tolerances = HashMap::from([(id![0], 0.05), (id![1], 0.1)]);
+  /// let runs = c.monte_carlo(&tolerances, 5, &id![1], duration, step, 7)?;
+  /// assert!(runs.windows(2).all(|pair| pair[0] != pair[1]));
+  /// assert_eq!(
+  ///   runs,
+  ///   c.monte_carlo(&tolerances, 5, &id![1], duration, step, 7)?
+  /// );
+  ///
+  /// // Only the values of resistors, capacitors and inductors can be drawn
+  /// let tolerances = HashMap::from([(id![], 0.05)]);
+  /// assert!(c
+  ///   .monte_carlo(&tolerances, 5, &id![1], duration, step, 7)
+  ///   .is_err());
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn monte_carlo(
+    &self,
+    tolerances: &HashMap<Id, f64>,
+    runs: usize,
+    node_id: &Id,
+    duration: f64,
+    step: f64,
+    seed: u64,
+  ) -> Result<Vec<EmulationData>>
+  {
+    check_time_grid(duration, step)?;
+    let mut tolerances = tolerances.iter().collect::<Vec<_>>();
+    tolerances.sort_by_key(|(id, _)| *id);
+    for &(id, tolerance) in tolerances.iter() {
+      if !(0f64..1f64).contains(tolerance) {
+        return Err(CircuitBuild(format!(
+          "The tolerance of the component of id {id:?} must be between 0 and 1, got {tolerance:?}"
+        )));
+      }
+      match self.get_comp_by_id(id).map(|component| &component.content) {
+        Some(ComponentContent::Simple(
+          Dipole::Resistor(_) | Dipole::Capacitor(_) | Dipole::Inductor(_),
+        )) => (),
+        Some(_) =>
+          return Err(CircuitBuild(format!(
+            "The component of id {id:?} is not a resistor, a capacitor or an inductor and has no \
+             value to draw"
+          ))),
+        None =>
+          return Err(CircuitBuild(format!(
+            "The component of id {id:?} does not exist"
+          ))),
+      }
+    }
+
+    let mut rng = SplitMix64::new(seed);
+    let mut results = Vec::with_capacity(runs);
+    for _ in 0..runs {
+      let mut circuit = self.clone();
+      for &(id, tolerance) in tolerances.iter() {
+        let factor = 1f64 + tolerance * (2f64 * rng.next_f64() - 1f64);
+        if let Some(ComponentContent::Simple(
+          Dipole::Resistor(value) | Dipole::Capacitor(value) | Dipole::Inductor(value),
+        )) = circuit
+          .get_comp_by_id_mut(id)
+          .map(|component| &mut component.content)
+        {
+          *value *= factor;
+        }
+      }
+      results.push(circuit.emulate_one(duration, step, node_id)?);
+    }
+    Ok(results)
+  }
+}
@@ -0,0 +1,170 @@
+use crate::{
+  error::{
+    Error::CircuitBuild,
+    Result,
+  },
+  Circuit,
+  Component,
+  ComponentContent,
+  Dipole,
+};
+
+/// Parses the name of a variable into the kind of the overridden dipole and
+/// its ID, once the prefix is removed. See [`Circuit::override_from_env`] for
+/// the naming scheme.
+fn parse_name(name: &str, suffix: &str) -> Result<(char, Vec<u8>)>
+{
+  let mut chars = suffix.chars();
+  let kind = chars
+    .next()
+    .filter(|kind| matches!(kind, 'R' | 'C' | 'L'))
+    .ok_or_else(|| {
+      CircuitBuild(format!(
+        "The variable {name} does not name a resistor (R), a capacitor (C) or an inductor (L)"
+      ))
+    })?;
+  let path = chars.as_str();
+  if path.is_empty() {
+    return Ok((kind, vec![]));
+  }
+  let id = path
+    .split('_')
+    .map(|index| index.parse::<u8>())
+    .collect::<std::result::Result<Vec<_>, _>>()
+    .map_err(|_| {
+      CircuitBuild(format!(
+        "The variable {name} does not encode a valid ID: expected indices separated by \
+         underscores, found {path:?}"
+      ))
+    })?;
+  Ok((kind, id))
+}
+
+impl Circuit
+{
+  /// Overrides the values of dipoles of the circuit from the environment
+  /// variables, which allows to sweep parameters without recompiling.
+  ///
+  /// A variable is read if its name is `{prefix}_{kind}{id}`, where:
+  /// - `kind` is `R`, `C` or `L`, and must match the resistor, capacitor or
+  ///   inductor found at the ID;
+  /// - `id` is the byte-path of the dipole, its indices being separated by
+  ///   underscores, e.g. `0` for the ID `[0]`, `1_0` for the ID `[1, 0]`, and
+  ///   nothing for the root `[]`.
+  ///
+  /// For instance, `CIRCUIT_R0=220` sets the resistance of the resistor `[0]`
+  /// to 220 Ω with the prefix `CIRCUIT`. The value is given in SI units, and
+  /// is checked as by [`Component::checked`]. The variables whose names do not
+  /// start with `{prefix}_` are ignored, even if they are not valid Unicode,
+  /// while all the others must be valid overrides: the circuit is left
+  /// untouched if any of them is invalid.
+  ///
+  /// # Arguments
+  ///
+  /// * `prefix` - The prefix of the names of the variables to read.
+  ///
+  /// # Errors
+  ///
+  /// Returns a `CircuitBuild` error if a variable has a malformed name or
+  /// value, a value that is not strictly positive and finite, an ID that does
+  /// not exist, or a kind that does not match the dipole found at its ID.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   Circuit,
+  ///   Component,
+  ///   ComponentContent,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  /// };
+  ///
+  /// let mut c = Circuit::new();
+  /// c.content_mut()
+  ///   .push_serie(Component::from(Resistor(1e3)))
+  ///   .push_serie(Component::from(Capacitor(1e-6)));
+  ///
+  /// std::env::set_var("RC_FILTER_R0", "220");
+  /// std::env::set_var("RC_FILTER_C1", "4.7e-6");
+  /// c.override_from_env("RC_FILTER")?;
+  /// assert!(matches!(
+  ///   c.get_comp_by_id(&[0]).unwrap().content,
+  ///   ComponentContent::Simple(Resistor(r)) if r == 220.
+  /// ));
+  ///
+  /// // A resistance must be strictly positive
+  /// std::env::set_var("RC_FILTER_R0", "-220");
+  /// assert!(c.override_from_env("RC_FILTER").is_err());
+  /// std::env::set_var("RC_FILTER_R0", "0");
+  /// assert!(c.override_from_env("RC_FILTER").is_err());
+  /// std::env::set_var("RC_FILTER_R0", "220");
+  ///
+  /// // The kind does not match the capacitor [1]
+  /// std::env::set_var("RC_FILTER_L1", "1e-3");
+  /// assert!(c.override_from_env("RC_FILTER").is_err());
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn override_from_env(&mut self, prefix: &str) -> Result<()>
+  {
+    let head = format!("{prefix}_");
+    let mut overrides = Vec::new();
+    for (name, value) in std::env::vars_os() {
+      // A name that is not valid Unicode is only reported if it starts with the
+      // prefix, as its replaced characters cannot be parsed
+      let name = name.to_string_lossy();
+      let Some(suffix) = name.strip_prefix(&head) else {
+        continue;
+      };
+      let (kind, id) = parse_name(&name, suffix)?;
+      let value = value
+        .to_str()
+        .ok_or_else(|| CircuitBuild(format!("The variable {name} does not hold valid Unicode")))?;
+      let value = value.trim().parse::<f64>().map_err(|_| {
+        CircuitBuild(format!(
+          "The variable {name} holds {value:?}, which is not a number"
+        ))
+      })?;
+      let found = match self.get_comp_by_id(&id).map(|component| &component.content) {
+        Some(ComponentContent::Simple(Dipole::Resistor(_))) => 'R',
+        Some(ComponentContent::Simple(Dipole::Capacitor(_))) => 'C',
+        Some(ComponentContent::Simple(Dipole::Inductor(_))) => 'L',
+        Some(_) =>
+          return Err(CircuitBuild(format!(
+            "The variable {name} overrides the component of id {id:?}, which is not a resistor, \
+             a capacitor or an inductor"
+          ))),
+        None =>
+          return Err(CircuitBuild(format!(
+            "The variable {name} overrides the component of id {id:?}, which does not exist"
+          ))),
+      };
+      if found != kind {
+        return Err(CircuitBuild(format!(
+          "The variable {name} overrides a {kind} but the component of id {id:?} is a {found}"
+        )));
+      }
+      let dipole = match kind {
+        'R' => Dipole::Resistor(value),
+        'C' => Dipole::Capacitor(value),
+        _ => Dipole::Inductor(value),
+      };
+      if let Err(CircuitBuild(message)) = Component::checked(dipole.clone()) {
+        return Err(CircuitBuild(format!(
+          "The variable {name} holds an invalid value: {message}"
+        )));
+      }
+      overrides.push((id, dipole));
+    }
+
+    for (id, dipole) in overrides {
+      self
+        .get_comp_by_id_mut(&id)
+        .expect("The overridden component exists")
+        .content = ComponentContent::Simple(dipole);
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,216 @@
+use std::collections::HashMap;
+
+use num::Complex;
+use num_traits::Zero;
+
+use crate::{
+  emulation::check_time_grid,
+  error::{
+    Error::CircuitSolve,
+    Result,
+  },
+  Circuit,
+  Id,
+};
+
+impl Circuit
+{
+  /// Gives the peak phasors of the real signal whose spectrum, aligned with
+  /// the pulses of the source, is `spectrum`, by non-negative pulse: the
+  /// signal is the sum of the `Re(phasor * exp(i pulse t))`, the phasor of the
+  /// zero pulse being the (real) constant term. For a complex source, whose
+  /// emulated signals are the real parts of the complex ones, the terms of
+  /// opposite pulses add up.
+  pub(crate) fn real_phasors(&self, spectrum: &[Complex<f64>]) -> Vec<(f64, Complex<f64>)>
+  {
+    let complex = self.generator().complex;
+    let mut phasors: Vec<(f64, Complex<f64>)> = Vec::new();
+    let mut indices: HashMap<u64, usize> = HashMap::new();
+    for ((pulse, _), coef) in self.voltages().zip(spectrum) {
+      let (pulse, phasor) = if pulse.is_zero() {
+        (0f64, Complex::from(coef.re))
+      } else if !complex {
+        (*pulse, 2f64 * coef)
+      } else if *pulse > 0f64 {
+        (*pulse, *coef)
+      } else {
+        (-pulse, coef.conj())
+      };
+      match indices.get(&pulse.to_bits()) {
+        Some(&index) => phasors[index].1 += phasor,
+        None => {
+          indices.insert(pulse.to_bits(), phasors.len());
+          phasors.push((pulse, phasor));
+        },
+      }
+    }
+    phasors
+  }
+
+  /// Gives the peak phasors of a signal of the circuit by non-negative pulse,
+  /// as [`Circuit::real_phasors`] does, the sources added with
+  /// [`Circuit::add_source`] included: the phasors of the circuits driven by
+  /// each source alone add up at their common pulses. `spectrum` gives the
+  /// spectrum of the signal in each of these circuits, which must be
+  /// initialized.
+  pub(crate) fn superposed_phasors<F>(&self, spectrum: F) -> Result<Vec<(f64, Complex<f64>)>>
+  where
+    F: Fn(&Circuit) -> Result<Vec<Complex<f64>>>,
+  {
+    let mut phasors: Vec<(f64, Complex<f64>)> = Vec::new();
+    let mut indices: HashMap<u64, usize> = HashMap::new();
+    for circuit in std::iter::once(self).chain(self.superposed.iter()) {
+      for (pulse, phasor) in circuit.real_phasors(&spectrum(circuit)?) {
+        match indices.get(&pulse.to_bits()) {
+          Some(&index) => phasors[index].1 += phasor,
+          None => {
+            indices.insert(pulse.to_bits(), phasors.len());
+            phasors.push((pulse, phasor));
+          },
+        }
+      }
+    }
+    Ok(phasors)
+  }
+
+  /// Emulates the instantaneous power received by a component, that is the
+  /// product of the tension across it and of the current flowing through it,
+  /// in watts. It is negative when the component gives energy back to the
+  /// circuit, as a capacitor discharging.
+  ///
+  /// # Arguments
+  ///
+  /// * `id` - The ID of the component
+  /// * `duration` - The duration of the emulation in seconds
+  /// * `step` - The time step used for the emulation in seconds
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the initialization of the circuit fails, if the
+  /// component does not exist or if the time grid is degenerate (see
+  /// [`Circuit::emulate_one`]).
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  /// };
+  ///
+  /// let mut c = Circuit::new();
+  /// c.add_tone(50., 10., 0.)
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(100.)))
+  ///   .push_serie(Component::from(Capacitor(20e-6)));
+  ///
+  /// // Over a period, the mean of the instantaneous power is the average one
+  /// for k in 0..2 {
+  ///   let power = c.power(&id![k], 20e-3, 1e-5)?;
+  ///   let mean = power.iter().sum::<f64>() / power.len() as f64;
+  ///   assert!((mean - c.average_power(&id![k])?).abs() < 1e-9);
+  /// }
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn power(&mut self, id: &Id, duration: f64, step: f64) -> Result<Vec<f64>>
+  {
+    check_time_grid(duration, step)?;
+    self.init()?;
+    if self.get_node(id).is_none() {
+      return Err(CircuitSolve(format!("Component of id {id:?} not found")));
+    }
+    let (currents, tensions, _) = self.emulate_initialized(duration, step, id)?;
+    Ok(
+      currents
+        .into_iter()
+        .zip(tensions)
+        .map(|(current, tension)| current * tension)
+        .collect(),
+    )
+  }
+
+  /// Computes the average power received by a component in the steady state,
+  /// in watts, from the spectra of its tension and of its current rather than
+  /// from an emulation: it is `½ Σ Re(V_k conj(I_k))` over the peak phasors of
+  /// the pulses of the source, plus the product of the constant terms. The
+  /// terms of distinct pulses average out over a common period. The sources
+  /// added with [`Circuit::add_source`] are superposed, their phasors adding
+  /// up with those of the main source at their common pulses.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the initialization of the circuit fails or if the
+  /// component does not exist.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  /// };
+  /// use num::Complex;
+  ///
+  /// // A constant term and two tones across a resistor, whose RMS value is
+  /// // given by V_rms² = 1² + 2² / 2 + 3² / 2
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(0., Complex::from(1.))
+  ///   .add_tone(50., 2., 0.3)
+  ///   .add_tone(150., 3., -1.)
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(10.)));
+  /// assert!((c.average_power(&id![])? - 7.5 / 10.).abs() < 1e-12);
+  ///
+  /// // An ideal capacitor does not dissipate any energy, unlike the resistor
+  /// // in series with it
+  /// c.content_mut()
+  ///   .push_serie(Component::from(Capacitor(100e-6)));
+  /// assert!(c.average_power(&id![1])?.abs() < 1e-12);
+  /// assert!(c.average_power(&id![0])? > 1e-2);
+  ///
+  /// assert!(c.average_power(&id![2]).is_err());
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn average_power(&mut self, id: &Id) -> Result<f64>
+  {
+    self.init()?;
+    // Both lists of phasors come in the same order of pulses
+    let phasors = |tension: bool| {
+      self.superposed_phasors(|circuit| {
+        let node = circuit
+          .get_node(id)
+          .ok_or_else(|| CircuitSolve(format!("Component of id {id:?} not found")))?;
+        Ok(if tension {
+          node.next_component_tensions.clone()
+        } else {
+          node.currents.clone()
+        })
+      })
+    };
+    let (tensions, currents) = (phasors(true)?, phasors(false)?);
+    Ok(
+      tensions
+        .into_iter()
+        .zip(currents)
+        .map(|((pulse, tension), (_, current))| {
+          let power = (tension * current.conj()).re;
+          if pulse.is_zero() {
+            power
+          } else {
+            0.5 * power
+          }
+        })
+        .sum(),
+    )
+  }
+}
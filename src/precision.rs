@@ -0,0 +1,116 @@
+//! Numeric backends used to evaluate the impedances at real pulses.
+//!
+//! The coefficients of an impedance span many orders of magnitude (e.g. `LC ≈
+//! 1e-9` next to `RC ≈ 1e-6`), so that evaluating the high-degree numerators
+//! and denominators of deep ladders or high-order `Equivalent` dipoles in `f64`
+//! suffers from cancellations. [`ExtendedPrecision`] evaluates them with a
+//! compensated Horner scheme, whose result is as accurate as if it was computed
+//! with twice the working precision (double-double arithmetic), for about
+//! three times the cost of [`StandardPrecision`].
+//!
+//! The `high-precision` feature makes [`ExtendedPrecision`] the backend of
+//! [`Component::impedance`](crate::Component::impedance) and of the steady
+//! state solution computed by [`Circuit::init`](crate::Circuit::init), which
+//! benefit the emulations and the frequency analyses of badly conditioned
+//! circuits. Analyses at complex pulses (poles, zeros and transient modes) are
+//! not affected.
+
+use fractios::RatioFrac;
+use num::Complex;
+use polyx::Polynomial;
+
+/// A way to evaluate the polynomials of an impedance at a real pulse.
+pub trait Precision
+{
+  /// Evaluates a polynomial at a real pulse.
+  fn eval_polynomial(p: &Polynomial<Complex<f64>>, pulse: f64) -> Complex<f64>;
+
+  /// Evaluates a rational fraction (typically an impedance) at a real pulse.
+  #[inline]
+  fn eval(r: &RatioFrac<Complex<f64>>, pulse: f64) -> Complex<f64>
+  {
+    Self::eval_polynomial(&r.numerator, pulse) / Self::eval_polynomial(&r.denominator, pulse)
+  }
+
+  /// Evaluates the inverse of a rational fraction (typically an admittance) at
+  /// a real pulse. Unlike `1 / eval(r, pulse)`, this gives zero and not NaN
+  /// when the fraction has a pole at `pulse`.
+  #[inline]
+  fn eval_inv(r: &RatioFrac<Complex<f64>>, pulse: f64) -> Complex<f64>
+  {
+    Self::eval_polynomial(&r.denominator, pulse) / Self::eval_polynomial(&r.numerator, pulse)
+  }
+}
+
+/// Evaluation in plain `f64` arithmetic with Horner's method.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StandardPrecision;
+
+/// Evaluation with a compensated Horner scheme, as accurate as double-double
+/// arithmetic.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ExtendedPrecision;
+
+/// The backend used by the crate to evaluate impedances, chosen with the
+/// `high-precision` feature.
+#[cfg(not(feature = "high-precision"))]
+pub type DefaultPrecision = StandardPrecision;
+/// The backend used by the crate to evaluate impedances, chosen with the
+/// `high-precision` feature.
+#[cfg(feature = "high-precision")]
+pub type DefaultPrecision = ExtendedPrecision;
+
+impl Precision for StandardPrecision
+{
+  #[inline]
+  fn eval_polynomial(p: &Polynomial<Complex<f64>>, pulse: f64) -> Complex<f64>
+  {
+    p.iter()
+      .rev()
+      .fold(Complex::from(0f64), |acc, c| acc * pulse + c)
+  }
+}
+
+/// Error-free sum: `a + b = s + e` exactly.
+#[inline]
+fn two_sum(a: f64, b: f64) -> (f64, f64)
+{
+  let s = a + b;
+  let b_virtual = s - a;
+  let a_virtual = s - b_virtual;
+  (s, (a - a_virtual) + (b - b_virtual))
+}
+
+/// Error-free product: `a * b = p + e` exactly.
+#[inline]
+fn two_prod(a: f64, b: f64) -> (f64, f64)
+{
+  let p = a * b;
+  (p, a.mul_add(b, -p))
+}
+
+/// Compensated Horner scheme (Graillat, Langlois & Louvet) on real
+/// coefficients, highest order first.
+fn compensated_horner(coefs: impl Iterator<Item = f64>, x: f64) -> f64
+{
+  let (mut value, mut error) = (0f64, 0f64);
+  for c in coefs {
+    let (p, product_error) = two_prod(value, x);
+    let (s, sum_error) = two_sum(p, c);
+    value = s;
+    error = error * x + (product_error + sum_error);
+  }
+  value + error
+}
+
+impl Precision for ExtendedPrecision
+{
+  fn eval_polynomial(p: &Polynomial<Complex<f64>>, pulse: f64) -> Complex<f64>
+  {
+    // The pulse being real, the real and imaginary parts are independent
+    Complex::new(
+      compensated_horner(p.iter().rev().map(|c| c.re), pulse),
+      compensated_horner(p.iter().rev().map(|c| c.im), pulse),
+    )
+  }
+}
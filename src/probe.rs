@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+
+use crate::{
+  error::{
+    Error::CircuitBuild,
+    Result,
+  },
+  Circuit,
+  Id,
+};
+
+/// A quantity of the circuit that can be probed during an emulation.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ProbeKind
+{
+  /// The potential of a node.
+  Node(Id),
+  /// The current flowing through the component following a node.
+  BranchCurrent(Id),
+  /// The potential of the first node minus the potential of the second one.
+  Differential(Id, Id),
+}
+
+/// A set of probes to emulate at once with [`Circuit::emulate`].
+#[derive(Clone, Debug, Default)]
+pub struct ProbeSet
+{
+  probes: Vec<ProbeKind>,
+}
+
+impl ProbeSet
+{
+  /// Iterator over the probes of the set.
+  #[inline]
+  pub fn iter(&self) -> impl Iterator<Item = &ProbeKind>
+  {
+    self.probes.iter()
+  }
+
+  /// Returns the IDs of the nodes to emulate to get the probes, without
+  /// duplicates.
+  fn node_ids(&self) -> Vec<Id>
+  {
+    let mut ids = Vec::new();
+    for probe in self.probes.iter() {
+      let probe_ids = match probe {
+        ProbeKind::Node(id) | ProbeKind::BranchCurrent(id) => vec![id],
+        ProbeKind::Differential(id1, id2) => vec![id1, id2],
+      };
+      for id in probe_ids {
+        if !ids.contains(id) {
+          ids.push(id.clone());
+        }
+      }
+    }
+    ids
+  }
+}
+
+/// A builder of [`ProbeSet`], obtained with [`Circuit::probes`].
+///
+/// # Example
+///
+/// ```
+/// use circuits_simulator::{
+///   Circuit,
+///   Component,
+///   Dipole::{
+///     Capacitor,
+///     Inductor,
+///     Resistor,
+///   },
+///   ProbeKind,
+/// };
+/// use num::Complex;
+///
+/// let mut c = Circuit::new();
+/// c.add_pulse(1., Complex::new(0., -0.5))
+///   .content_mut()
+///   .push_serie(Component::from(Resistor(200.)))
+///   .push_serie(Component::from(Capacitor(10e-9)))
+///   .push_serie(Component::from(Inductor(100e-3)));
+///
+/// let probes = c
+///   .probes()
+///   .node(&[1])
+///   .branch_currents(&[2])
+///   .differential(&[1], &[2])
+///   .build()?;
+/// let results = c.emulate(1e-3, 1e-5, &probes)?;
+/// assert_eq!(results[&ProbeKind::Node(vec![1])].len(), 100);
+/// # Ok::<(), circuits_simulator::Error>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct ProbeSetBuilder<'a>
+{
+  circuit: &'a Circuit,
+  probes:  Vec<ProbeKind>,
+}
+
+impl ProbeSetBuilder<'_>
+{
+  /// Probes the potential of a node.
+  #[inline]
+  pub fn node(mut self, id: &[u8]) -> Self
+  {
+    self.probes.push(ProbeKind::Node(id.to_vec()));
+    self
+  }
+
+  /// Probes the current flowing through the component following a node.
+  #[inline]
+  pub fn branch_currents(mut self, id: &[u8]) -> Self
+  {
+    self.probes.push(ProbeKind::BranchCurrent(id.to_vec()));
+    self
+  }
+
+  /// Probes the potential difference between two nodes.
+  #[inline]
+  pub fn differential(mut self, id1: &[u8], id2: &[u8]) -> Self
+  {
+    self
+      .probes
+      .push(ProbeKind::Differential(id1.to_vec(), id2.to_vec()));
+    self
+  }
+
+  /// Builds the set of probes.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if a probed node does not exist in the circuit.
+  pub fn build(self) -> Result<ProbeSet>
+  {
+    let probes = ProbeSet {
+      probes: self.probes,
+    };
+    for id in probes.node_ids() {
+      if self.circuit.get_comp_by_id(&id).is_none() {
+        return Err(CircuitBuild(format!("Probed node of id {id:?} not found")));
+      }
+    }
+    Ok(probes)
+  }
+}
+
+impl Circuit
+{
+  /// Starts building a set of probes for this circuit. See
+  /// [`ProbeSetBuilder`].
+  #[inline]
+  pub fn probes(&self) -> ProbeSetBuilder<'_>
+  {
+    ProbeSetBuilder {
+      circuit: self,
+      probes:  Vec::new(),
+    }
+  }
+
+  /// Emulates the circuit for a given duration and step size and returns the
+  /// values of each probe of `probes`.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the emulation of a probed node fails.
+  pub fn emulate(
+    &mut self,
+    duration: f64,
+    step: f64,
+    probes: &ProbeSet,
+  ) -> Result<HashMap<ProbeKind, Vec<f64>>>
+  {
+    let node_ids = probes.node_ids();
+    let data = node_ids
+      .iter()
+      .cloned()
+      .zip(self.emulate_many(duration, step, &node_ids)?)
+      .collect::<HashMap<_, _>>();
+    Ok(
+      probes
+        .iter()
+        .map(|probe| {
+          let values = match probe {
+            ProbeKind::Node(id) => data[id].2.clone(),
+            ProbeKind::BranchCurrent(id) => data[id].0.clone(),
+            ProbeKind::Differential(id1, id2) => data[id1]
+              .2
+              .iter()
+              .zip(data[id2].2.iter())
+              .map(|(p1, p2)| p1 - p2)
+              .collect(),
+          };
+          (probe.clone(), values)
+        })
+        .collect(),
+    )
+  }
+}
@@ -0,0 +1,95 @@
+use std::fmt::Write;
+
+use num_traits::Zero;
+
+use crate::{
+  Circuit,
+  CircuitInitState,
+};
+
+/// Rounds a number to 4 decimals for display, without negative zeros.
+fn round(x: f64) -> f64
+{
+  (x * 1e4).round() / 1e4 + 0f64
+}
+
+impl Circuit
+{
+  /// Summarizes the steady state of an initialized circuit: for each node,
+  /// sorted by ID, the amplitude and the phase of its potential at the dominant
+  /// pulse of the source, that is its pulse of largest voltage (the zero pulse
+  /// being only chosen for a constant source). See
+  /// [`Circuit::steady_state_phasor`] for the definition of the amplitude and
+  /// of the phase, the opposite pulses of a complex source being merged.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  /// };
+  /// use num::Complex;
+  ///
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(1e3, Complex::from(0.5))
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(1e3)))
+  ///   .push_serie(Component::from(Capacitor(1e-6)));
+  /// c.init()?;
+  ///
+  /// assert_eq!(
+  ///   c.describe_solution(),
+  ///   "Steady state at the pulse 1000 rad/s:\n\
+  ///    [] : 1 V, 0°\n\
+  ///    [0] : 1 V, 0°\n\
+  ///    [1] : 0.7071 V, -45°\n"
+  /// );
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn describe_solution(&self) -> String
+  {
+    if self.init_state() < CircuitInitState::Source {
+      return "The circuit is not initialized\n".to_string();
+    }
+    let voltages = self
+      .voltages()
+      .map(|(_, voltage)| *voltage)
+      .collect::<Vec<_>>();
+    let dominant = self
+      .real_phasors(&voltages)
+      .into_iter()
+      .enumerate()
+      .max_by(|(_, (pulse_a, voltage_a)), (_, (pulse_b, voltage_b))| {
+        (!pulse_a.is_zero(), voltage_a.norm())
+          .partial_cmp(&(!pulse_b.is_zero(), voltage_b.norm()))
+          .unwrap_or(std::cmp::Ordering::Equal)
+      })
+      .filter(|(_, (_, voltage))| !voltage.is_zero());
+    let Some((index, (pulse, _))) = dominant else {
+      return "The source is silent\n".to_string();
+    };
+
+    let mut nodes = self.nodes().collect::<Vec<_>>();
+    nodes.sort_by(|a, b| a.id.cmp(&b.id));
+    let mut description = format!("Steady state at the pulse {pulse} rad/s:\n");
+    for node in nodes {
+      // The phasors of all the spectra come in the order of the pulses of the
+      // source
+      let (_, phasor) = self.real_phasors(&node.potentials)[index];
+      writeln!(
+        description,
+        "{:?} : {} V, {}°",
+        node.id,
+        round(phasor.norm()),
+        round(phasor.arg().to_degrees())
+      )
+      .unwrap();
+    }
+    description
+  }
+}
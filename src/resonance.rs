@@ -0,0 +1,192 @@
+//! Quality factor and bandwidth of resonant responses.
+//!
+//! The resonant peak of the response of a node is looked for around the
+//! resonant pulses of the circuit (see [`Circuit::resonant_frequencies`]), and
+//! its half-power (-3 dB) points are located on both sides of it by sweeping
+//! the frequency response outwards, then by bisection.
+
+use crate::{
+  error::{
+    Error::CircuitSolve,
+    Result,
+  },
+  precision::{
+    DefaultPrecision,
+    Precision,
+  },
+  Circuit,
+  Id,
+};
+
+/// Number of iterations of the searches of the peaks and of the half-power
+/// points, each dividing the searched interval by at least 1.6.
+const SEARCH_ITERATIONS: usize = 100;
+
+/// Ratio between two successive pulses of the sweeps looking for the
+/// half-power points.
+const SWEEP_RATIO: f64 = 1.01;
+
+/// Largest ratio between a half-power pulse and the pulse of the peak.
+const SWEEP_RANGE: f64 = 1e6;
+
+/// Relative distance to the ends of the searched interval under which a
+/// maximum is not a peak, the response being monotonic there.
+const EDGE_TOLERANCE: f64 = 1e-6;
+
+impl Circuit
+{
+  /// Locates the highest resonant peak of the response of a node.
+  ///
+  /// # Returns
+  ///
+  /// The lower half-power pulse, the pulse of the peak and the upper
+  /// half-power pulse, in rad/s.
+  fn half_power_pulses(&mut self, node_id: &Id) -> Result<(f64, f64, f64)>
+  {
+    let not_resonant = || {
+      CircuitSolve(format!(
+        "The response of the node of id {node_id:?} has no resonant peak"
+      ))
+    };
+    let mut resonances = self.resonant_frequencies()?;
+    let transfer = self.source_transfer(node_id)?;
+    let gain = |pulse: f64| DefaultPrecision::eval(&transfer, pulse).norm();
+
+    // Maximizes the gain by golden-section search over the logarithm of the
+    // pulse, between the resonances around each one
+    resonances.retain(|pulse| *pulse > 0f64);
+    let mut peak: Option<(f64, f64)> = None;
+    for (index, resonance) in resonances.iter().enumerate() {
+      let lower = match index {
+        0 => resonance / 2f64,
+        _ => (resonances[index - 1] * resonance).sqrt(),
+      };
+      let upper = match resonances.get(index + 1) {
+        Some(next) => (resonance * next).sqrt(),
+        None => resonance * 2f64,
+      };
+      let (mut a, mut b) = (lower.ln(), upper.ln());
+      let ratio = (5f64.sqrt() - 1f64) / 2f64;
+      for _ in 0..SEARCH_ITERATIONS {
+        let (x, y) = (b - ratio * (b - a), a + ratio * (b - a));
+        if gain(x.exp()) > gain(y.exp()) {
+          b = y;
+        } else {
+          a = x;
+        }
+      }
+      let pulse = ((a + b) / 2f64).exp();
+      if (pulse / lower - 1f64).abs() < EDGE_TOLERANCE
+        || (upper / pulse - 1f64).abs() < EDGE_TOLERANCE
+      {
+        continue;
+      }
+      if peak.is_none_or(|(_, best)| gain(pulse) > best) {
+        peak = Some((pulse, gain(pulse)));
+      }
+    }
+    let (pulse, peak_gain) = peak.ok_or_else(not_resonant)?;
+    if !peak_gain.is_finite() {
+      return Err(CircuitSolve(format!(
+        "The response of the node of id {node_id:?} is unbounded at its resonance of {pulse} rad/s"
+      )));
+    }
+
+    // Sweeps away from the peak until the gain falls below its half-power
+    // level, then bisects between the last two pulses
+    let level = peak_gain / 2f64.sqrt();
+    let half_power = |ratio: f64| {
+      let mut inner = pulse;
+      let mut outer = pulse * ratio;
+      while gain(outer) >= level {
+        if (outer / pulse).ln().abs() > SWEEP_RANGE.ln() {
+          return None;
+        }
+        inner = outer;
+        outer *= ratio;
+      }
+      for _ in 0..SEARCH_ITERATIONS {
+        let middle = (inner * outer).sqrt();
+        if gain(middle) >= level {
+          inner = middle;
+        } else {
+          outer = middle;
+        }
+      }
+      Some((inner * outer).sqrt())
+    };
+    let lower = half_power(SWEEP_RATIO.recip()).ok_or_else(not_resonant)?;
+    let upper = half_power(SWEEP_RATIO).ok_or_else(not_resonant)?;
+    Ok((lower, pulse, upper))
+  }
+
+  /// Computes the quality factor of the resonant peak of the response of a
+  /// node, that is the gain from the source to the potential of the node (see
+  /// [`Circuit::frequency_response`]). It is the ratio of the pulse of the
+  /// peak to its bandwidth, measured between its half-power (-3 dB) points
+  /// (see [`Circuit::bandwidth`]). The peak is looked for around the resonant
+  /// pulses of the circuit (see [`Circuit::resonant_frequencies`]), the
+  /// highest one being kept.
+  ///
+  /// # Errors
+  ///
+  /// Returns a `CircuitSolve` error if the node does not exist, if its
+  /// response has no resonant peak (e.g. a monotonic low-pass response) or if
+  /// the peak does not fall by 3 dB on both sides, or if the gain is infinite
+  /// at the peak, as for a lossless resonator. Returns an error if the
+  /// resonant pulses cannot be computed.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Inductor,
+  ///     Resistor,
+  ///   },
+  /// };
+  ///
+  /// // The tension across the resistor of a serial RLC circuit is a band-pass
+  /// // response of quality factor √(L / C) / R
+  /// let (r, l, c) = (10., 10e-3, 1e-6);
+  /// let mut rlc = Circuit::new();
+  /// rlc
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Inductor(l)))
+  ///   .push_serie(Component::from(Capacitor(c)))
+  ///   .push_serie(Component::from(Resistor(r)));
+  /// let q = (l / c).sqrt() / r;
+  /// assert!((rlc.quality_factor(&id![2])? - q).abs() < 1e-6 * q);
+  /// assert!((rlc.bandwidth(&id![2])? - r / l).abs() < 1e-6 * r / l);
+  ///
+  /// // An RC low-pass filter does not resonate
+  /// let mut rc = Circuit::new();
+  /// rc.content_mut()
+  ///   .push_serie(Component::from(Resistor(1e3)))
+  ///   .push_serie(Component::from(Capacitor(1e-6)));
+  /// assert!(rc.quality_factor(&id![1]).is_err());
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn quality_factor(&mut self, node_id: &Id) -> Result<f64>
+  {
+    let (lower, pulse, upper) = self.half_power_pulses(node_id)?;
+    Ok(pulse / (upper - lower))
+  }
+
+  /// Computes the bandwidth of the resonant peak of the response of a node,
+  /// in rad/s, that is the distance between the pulses at which the gain is
+  /// `1/√2` times its peak value. See [`Circuit::quality_factor`].
+  ///
+  /// # Errors
+  ///
+  /// Returns an error in the same cases as [`Circuit::quality_factor`].
+  pub fn bandwidth(&mut self, node_id: &Id) -> Result<f64>
+  {
+    let (lower, _, upper) = self.half_power_pulses(node_id)?;
+    Ok(upper - lower)
+  }
+}
@@ -0,0 +1,182 @@
+//! Step and impulse responses.
+
+use num::Complex;
+
+use crate::{
+  emulation::{
+    check_time_grid,
+    EmulationData,
+  },
+  error::Result,
+  Circuit,
+  Id,
+  Source,
+};
+
+impl Circuit
+{
+  /// Emulates a node of the circuit driven by `source` instead of its own
+  /// sources, its internal impedance being kept. The circuit itself is left
+  /// unchanged.
+  fn emulate_with_source(
+    &self,
+    mut source: Source,
+    duration: f64,
+    step: f64,
+    node_id: &Id,
+  ) -> Result<EmulationData>
+  {
+    let mut circuit = self.clone();
+    source.internal_impedance = self.generator().internal_impedance.clone();
+    circuit
+      .clear_sources()
+      .replace_source(source)
+      .emulate_one(duration, step, node_id)
+  }
+
+  /// Gives the number of harmonics of the canonical sources, whose pulses
+  /// `k π / duration` remain below the Nyquist pulse `π / step`.
+  fn canonical_harmonics(duration: f64, step: f64) -> Result<usize>
+  {
+    check_time_grid(duration, step)?;
+    Ok((duration / step).floor() as usize)
+  }
+
+  /// Emulates the response of a node of the circuit to a step of tension of
+  /// height `amplitude` at `t = 0`, from 0 to `duration` with a time step of
+  /// `step`. The source of the circuit is replaced by the Fourier series of
+  /// the step, the circuit itself being left unchanged.
+  ///
+  /// The emulations give the steady state of the circuit under a periodic
+  /// source, so that the step is emulated through its periodic extension over
+  /// the window `[-duration, duration]`, a square wave null before 0. Its
+  /// Fourier series is truncated at the Nyquist pulse `π / step` of the time
+  /// step, which limits its bandwidth: the step rises over about a time step,
+  /// with a Gibbs ringing of about 9 % of the amplitude around its edges. The
+  /// response is faithful when the circuit filters out the harmonics beyond
+  /// the Nyquist pulse and settles within `duration`, so that the previous
+  /// period does not leak into it.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the time grid is degenerate or if the emulation
+  /// fails (see [`Circuit::emulate_one`]).
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  /// };
+  ///
+  /// // The tension across the capacitor of an RC circuit rises as
+  /// // A (1 - exp(-t / RC))
+  /// let (r, c, amplitude) = (1e3, 1e-6, 5.);
+  /// let mut rc = Circuit::new();
+  /// rc.content_mut()
+  ///   .push_serie(Component::from(Resistor(r)))
+  ///   .push_serie(Component::from(Capacitor(c)));
+  ///
+  /// let (duration, step) = (10. * r * c, 1e-5);
+  /// let (_, tensions, _) = rc.step_response(amplitude, duration, step, &id![1])?;
+  /// for k in [100, 200, 500] {
+  ///   let t = k as f64 * step;
+  ///   let expected = amplitude * (1. - (-t / (r * c)).exp());
+  ///   assert!((tensions[k] - expected).abs() < 1e-2 * amplitude);
+  /// }
+  /// assert!((tensions.last().unwrap() - amplitude).abs() < 1e-2 * amplitude);
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn step_response(
+    &self,
+    amplitude: f64,
+    duration: f64,
+    step: f64,
+    node_id: &Id,
+  ) -> Result<EmulationData>
+  {
+    let harmonics = Self::canonical_harmonics(duration, step)?;
+    // The square wave has the mean A/2 and the odd harmonics -iA / kπ
+    let mut source = Source::new();
+    source.add_pulse(0f64, Complex::from(amplitude / 2f64));
+    for k in (1..=harmonics).step_by(2) {
+      let k = k as f64;
+      source.add_pulse(
+        k * std::f64::consts::PI / duration,
+        Complex::new(0f64, -amplitude / (k * std::f64::consts::PI)),
+      );
+    }
+    self.emulate_with_source(source, duration, step, node_id)
+  }
+
+  /// Emulates the response of a node of the circuit to an impulse of tension
+  /// of area `amplitude` (in V·s) at `t = 0`, from 0 to `duration` with a time
+  /// step of `step`. The source of the circuit is replaced by the Fourier
+  /// series of the impulse, the circuit itself being left unchanged.
+  ///
+  /// As with [`Circuit::step_response`], the impulse is emulated through its
+  /// periodic extension, a train of impulses at `0, ±2 duration...`, whose
+  /// Fourier series is truncated at the Nyquist pulse `π / step`: the impulse
+  /// is smeared over a few time steps, and the circuit must settle within
+  /// `duration`.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the time grid is degenerate or if the emulation
+  /// fails (see [`Circuit::emulate_one`]).
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  /// };
+  ///
+  /// // The tension across the capacitor of an RC circuit decays as
+  /// // A / RC exp(-t / RC)
+  /// let (r, c, amplitude) = (1e3, 1e-6, 1e-3);
+  /// let mut rc = Circuit::new();
+  /// rc.content_mut()
+  ///   .push_serie(Component::from(Resistor(r)))
+  ///   .push_serie(Component::from(Capacitor(c)));
+  ///
+  /// let (duration, step) = (10. * r * c, 1e-5);
+  /// let (_, tensions, _) = rc.impulse_response(amplitude, duration, step, &id![1])?;
+  /// for k in [50, 100, 300] {
+  ///   let t = k as f64 * step;
+  ///   let expected = amplitude / (r * c) * (-t / (r * c)).exp();
+  ///   assert!((tensions[k] - expected).abs() < 1e-2 * amplitude / (r * c));
+  /// }
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn impulse_response(
+    &self,
+    amplitude: f64,
+    duration: f64,
+    step: f64,
+    node_id: &Id,
+  ) -> Result<EmulationData>
+  {
+    let harmonics = Self::canonical_harmonics(duration, step)?;
+    // The train of impulses has the same coefficient A / T at all its
+    // harmonics, T = 2 duration being its period
+    let coefficient = Complex::from(amplitude / (2f64 * duration));
+    let mut source = Source::new();
+    for k in 0..=harmonics {
+      source.add_pulse(k as f64 * std::f64::consts::PI / duration, coefficient);
+    }
+    self.emulate_with_source(source, duration, step, node_id)
+  }
+}
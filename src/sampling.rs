@@ -0,0 +1,157 @@
+use std::fmt::{
+  Display,
+  Formatter,
+};
+
+use crate::{
+  emulation::{
+    check_time_grid,
+    EmulationData,
+  },
+  error::Result,
+  Circuit,
+  Id,
+};
+
+/// A warning that the time step of an emulation under-samples the highest
+/// harmonic of the sources of the circuit, see [`Circuit::aliasing_warning`].
+/// The harmonics above the Nyquist frequency then alias onto lower frequencies
+/// in the emulated samples.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AliasingWarning
+{
+  /// The highest frequency of the sources, in Hz (see
+  /// [`Circuit::max_representable_frequency`]).
+  pub max_frequency:     f64,
+  /// The Nyquist frequency of the time step, `1 / (2 step)`, in Hz.
+  pub nyquist_frequency: f64,
+}
+
+impl AliasingWarning
+{
+  /// Gives the largest time step that samples the highest harmonic of the
+  /// sources without aliasing, `1 / (2 max_frequency)`, in seconds.
+  #[inline]
+  pub fn max_step(&self) -> f64
+  {
+    (2f64 * self.max_frequency).recip()
+  }
+}
+
+impl Display for AliasingWarning
+{
+  fn fmt(&self, f: &mut Formatter) -> std::fmt::Result
+  {
+    write!(
+      f,
+      "The sources reach {} Hz, above the Nyquist frequency of {} Hz of the time step: use a \
+       step below {} s to avoid aliasing",
+      self.max_frequency,
+      self.nyquist_frequency,
+      self.max_step()
+    )
+  }
+}
+
+impl Circuit
+{
+  /// Gives the highest frequency represented by the sources of the circuit, in
+  /// Hz, that is the largest absolute pulse of their spectra over 2π, the
+  /// sources added with [`Circuit::add_source`] included. After
+  /// `Circuit::set_generator_fn` with `n_freqs` frequencies over `duration`,
+  /// it is `(n_freqs - 1) / (2 duration)`. By Shannon's theorem, the
+  /// emulations only sample it faithfully with a time step below `1 / (2
+  /// max_representable_frequency)` (see [`Circuit::aliasing_warning`]). It is
+  /// 0 for a source without any pulse.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # #[cfg(feature = "fft")]
+  /// # {
+  /// use circuits_simulator::Circuit;
+  ///
+  /// // 100 frequencies over a window of 2 ms, spaced by 1 / 2 ms = 500 Hz
+  /// let square_wave = |t: f64| if t.rem_euclid(1e-3) < 5e-4 { 1. } else { -1. };
+  /// let mut c = Circuit::new();
+  /// c.set_generator_fn(square_wave, 1e-3, 100);
+  /// assert!((c.max_representable_frequency() - 99. * 500.).abs() < 1e-6);
+  /// # }
+  /// ```
+  pub fn max_representable_frequency(&self) -> f64
+  {
+    let max_pulse = self
+      .sources()
+      .iter()
+      .map(|(_, source)| source)
+      .chain([self.generator()])
+      .flat_map(|source| source.voltages())
+      .fold(0f64, |max_pulse, (pulse, _)| max_pulse.max(pulse.abs()));
+    max_pulse / (2f64 * std::f64::consts::PI)
+  }
+
+  /// Checks that a time step samples the highest harmonic of the sources of
+  /// the circuit (see [`Circuit::max_representable_frequency`]) above the
+  /// Nyquist rate, i.e. that the frequency of the harmonic does not exceed the
+  /// Nyquist frequency `1 / (2 step)`.
+  ///
+  /// # Returns
+  ///
+  /// An [`AliasingWarning`] if the step is too coarse, `None` otherwise.
+  #[inline]
+  pub fn aliasing_warning(&self, step: f64) -> Option<AliasingWarning>
+  {
+    let warning = AliasingWarning {
+      max_frequency:     self.max_representable_frequency(),
+      nyquist_frequency: (2f64 * step).recip(),
+    };
+    (warning.max_frequency > warning.nyquist_frequency).then_some(warning)
+  }
+
+  /// Same as [`Circuit::emulate_one`], but also checks the time step against
+  /// the highest harmonic of the sources (see [`Circuit::aliasing_warning`]).
+  /// The emulation itself is unchanged.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error in the same cases as [`Circuit::emulate_one`].
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::Resistor,
+  /// };
+  ///
+  /// // A tone of 500 Hz and its 99th harmonic
+  /// let mut c = Circuit::new();
+  /// c.add_tone(500., 1., 0.)
+  ///   .add_tone(99. * 500., 1. / 99., 0.)
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(1e3)));
+  ///
+  /// // The harmonics reach 49.5 kHz, which a step of 10 µs samples...
+  /// let (_, warning) = c.emulate_one_checked(1e-3, 1e-5, &id![])?;
+  /// assert!(warning.is_none());
+  /// // ...unlike a step of 20 µs, whose Nyquist frequency is 25 kHz
+  /// let (_, warning) = c.emulate_one_checked(1e-3, 2e-5, &id![])?;
+  /// let warning = warning.unwrap();
+  /// assert!((warning.nyquist_frequency - 25e3).abs() < 1e-6);
+  /// assert!((warning.max_step() - 1. / 99e3).abs() < 1e-12);
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn emulate_one_checked(
+    &mut self,
+    duration: f64,
+    step: f64,
+    node_id: &Id,
+  ) -> Result<(EmulationData, Option<AliasingWarning>)>
+  {
+    check_time_grid(duration, step)?;
+    let warning = self.aliasing_warning(step);
+    Ok((self.emulate_one(duration, step, node_id)?, warning))
+  }
+}
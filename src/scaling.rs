@@ -0,0 +1,200 @@
+//! Impedance and frequency scaling.
+//!
+//! Filters are usually designed as normalized prototypes, with a cutoff pulse
+//! of 1 rad/s and terminations of 1 Ω, then denormalized. Scaling the
+//! impedances by a factor `a` multiplies the resistances and the inductances
+//! by `a` and divides the capacitances by `a`, so that the impedance of the
+//! circuit is multiplied by `a` at each pulse. Scaling the frequencies by a
+//! factor `b` divides the inductances and the capacitances by `b`, so that the
+//! impedance of the circuit at the pulse `b ω` is its former impedance at `ω`.
+
+use fractios::RatioFrac;
+use num::Complex;
+use polyx::Polynomial;
+
+use crate::{
+  error::{
+    Error::CircuitBuild,
+    Result,
+  },
+  util::coefficients,
+  Circuit,
+  Component,
+  ComponentContent,
+  Dipole,
+};
+
+/// Scales a fraction of the pulse so that its value at `frequency * ω` is
+/// `impedance` times its former value at `ω`.
+fn scale_fraction(
+  fraction: &RatioFrac<Complex<f64>>,
+  impedance: f64,
+  frequency: f64,
+) -> RatioFrac<Complex<f64>>
+{
+  let scale = |polynomial: &Polynomial<Complex<f64>>, gain: f64| {
+    let mut factor = gain;
+    Polynomial::from(
+      coefficients(polynomial)
+        .into_iter()
+        .map(|coefficient| {
+          let result = coefficient * factor;
+          factor /= frequency;
+          result
+        })
+        .collect::<Vec<_>>(),
+    )
+  };
+  RatioFrac::from((
+    scale(&fraction.numerator, impedance),
+    scale(&fraction.denominator, 1f64),
+  ))
+}
+
+/// Scales the impedances of the dipoles of a component by `impedance` and
+/// their pulses by `frequency`, loads included, uninitializing the component.
+fn scale_component(component: &mut Component, impedance: f64, frequency: f64)
+{
+  for (_, component) in component.dipoles_mut() {
+    let ComponentContent::Simple(dipole) = &mut component.content else {
+      continue;
+    };
+    match dipole {
+      Dipole::Resistor(r) => *r *= impedance,
+      Dipole::Capacitor(c) => *c /= impedance * frequency,
+      Dipole::Inductor(l) => *l *= impedance / frequency,
+      Dipole::Equivalent(e) => *e = scale_fraction(e, impedance, frequency),
+      // The ratio of a transformer and the gain of a controlled source have no
+      // dimension
+      Dipole::Transformer { load, .. } | Dipole::Vcvs { load, .. } =>
+        scale_component(load, impedance, frequency),
+      Dipole::Short | Dipole::Open | Dipole::Poisoned => (),
+    }
+  }
+  component.uninit_all();
+}
+
+/// Checks that a scaling factor is positive and finite.
+fn check_factor(name: &str, factor: f64) -> Result<()>
+{
+  if factor > 0f64 && factor.is_finite() {
+    Ok(())
+  } else {
+    Err(CircuitBuild(format!(
+      "The {name} scaling factor must be positive and finite, got {factor:?}"
+    )))
+  }
+}
+
+impl Circuit
+{
+  /// Scales the impedances of the circuit by `factor`: the resistances and
+  /// the inductances are multiplied by `factor` and the capacitances divided
+  /// by it, as well as the equivalent dipoles, the loads of the transformers
+  /// and of the controlled sources, and the internal impedance of the source.
+  /// The currents are then divided by `factor` and the tensions unchanged.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if `factor` is not positive and finite.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Inductor,
+  ///     Resistor,
+  ///   },
+  /// };
+  ///
+  /// let mut c = Circuit::new();
+  /// c.content_mut()
+  ///   .push_serie(Component::from(Resistor(1.)))
+  ///   .push_serie(Component::from(Capacitor(1.)));
+  /// c.get_comp_by_id_mut(&[1])
+  ///   .unwrap()
+  ///   .push_parallel(Component::from(Inductor(2.)));
+  /// c.init_impedance()?;
+  /// let before = c.content().impedance(0.7);
+  ///
+  /// c.scale_impedance(50.)?.init_impedance()?;
+  /// assert!((c.content().impedance(0.7) - 50. * before).norm() < 1e-9);
+  /// assert!(c.scale_impedance(0.).is_err());
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn scale_impedance(&mut self, factor: f64) -> Result<&mut Self>
+  {
+    check_factor("impedance", factor)?;
+    self.scale(factor, 1f64);
+    Ok(self)
+  }
+
+  /// Scales the frequencies of the circuit by `factor`: the inductances and
+  /// the capacitances are divided by `factor`, as well as those of the
+  /// equivalent dipoles, of the loads of the transformers and of the
+  /// controlled sources, and of the internal impedance of the source. The
+  /// impedance of the circuit at the pulse `factor * ω` is then its former
+  /// impedance at `ω`. The pulses of the source are unchanged.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if `factor` is not positive and finite.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use std::f64::consts::PI;
+  ///
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   ComponentContent::Simple,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  /// };
+  ///
+  /// // A normalized RC low-pass filter, whose cutoff pulse is 1 rad/s,
+  /// // denormalized to a cutoff of 1 kHz with 1 kΩ terminations
+  /// let mut c = Circuit::new();
+  /// c.add_tone(1e3, 1., 0.)
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(1.)))
+  ///   .push_serie(Component::from(Capacitor(1.)));
+  /// c.scale_frequency(2. * PI * 1e3)?.scale_impedance(1e3)?;
+  ///
+  /// assert!(matches!(
+  ///   c.get_comp_by_id(&[1]).unwrap().content,
+  ///   Simple(Capacitor(capacitance)) if (capacitance - 1. / (2e6 * PI)).abs() < 1e-18
+  /// ));
+  ///
+  /// // The output is 3 dB below the input at 1 kHz
+  /// c.init()?;
+  /// let output = c.get_node(&id![1]).unwrap().next_component_tensions[0];
+  /// let input = c.get_generator_voltage(0);
+  /// assert!(((output / input).norm() - 0.5f64.sqrt()).abs() < 1e-12);
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn scale_frequency(&mut self, factor: f64) -> Result<&mut Self>
+  {
+    check_factor("frequency", factor)?;
+    self.scale(1f64, factor);
+    Ok(self)
+  }
+
+  fn scale(&mut self, impedance: f64, frequency: f64)
+  {
+    scale_component(self.content_mut(), impedance, frequency);
+    if let Some(internal_impedance) = &self.generator().internal_impedance {
+      let internal_impedance = scale_fraction(internal_impedance, impedance, frequency);
+      self.set_internal_impedance(Some(internal_impedance));
+    }
+    self.uninit_all();
+  }
+}
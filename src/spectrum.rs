@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+
+use num::Complex;
+
+use crate::{
+  error::Result,
+  Circuit,
+  Id,
+};
+
+/// The `(potential, current)` coefficients of the nodes of a circuit at one
+/// pulse, by node ID. See [`FrequencyDomainResult`].
+pub type NodePhasors = HashMap<Id, (Complex<f64>, Complex<f64>)>;
+
+/// The steady-state solution of a circuit in the frequency domain, returned by
+/// [`Circuit::frequency_domain_solution`].
+///
+/// For each pulse of the source, in the same order, it maps the ID of each
+/// node to the complex coefficients of its potential and of the current
+/// through the component following it. The coefficients are those of the
+/// voltages of the source: for a real source, only positive pulses are
+/// stored, so that the coefficient `c` of a nonzero pulse `ω` stands for the
+/// real signal `2·Re(c·exp(iωt))`, and that of the zero pulse for the
+/// constant `Re(c)`. For a complex source (see [`crate::Source::complex`]),
+/// the pulses may be negative and each coefficient stands for `c·exp(iωt)`,
+/// the emulated signal being the real part of their sum.
+#[derive(Clone, Debug, Default)]
+pub struct FrequencyDomainResult
+{
+  harmonics: Vec<(f64, NodePhasors)>,
+}
+
+impl FrequencyDomainResult
+{
+  /// Iterator over the pulses and the `(potential, current)` coefficients of
+  /// the nodes at each of them.
+  #[inline]
+  pub fn iter(&self) -> impl Iterator<Item = &(f64, NodePhasors)>
+  {
+    self.harmonics.iter()
+  }
+
+  /// Returns the `(potential, current)` coefficients of a node at the pulse
+  /// of a given index, if both exist.
+  #[inline]
+  pub fn get(&self, index: usize, id: &Id) -> Option<&(Complex<f64>, Complex<f64>)>
+  {
+    self.harmonics.get(index)?.1.get(id)
+  }
+
+  /// Returns the number of pulses.
+  #[inline]
+  pub fn len(&self) -> usize
+  {
+    self.harmonics.len()
+  }
+
+  /// Returns `true` if the source has no pulse.
+  #[inline]
+  pub fn is_empty(&self) -> bool
+  {
+    self.harmonics.is_empty()
+  }
+}
+
+impl Circuit
+{
+  /// Gives the complete steady-state solution of the circuit in the frequency
+  /// domain, for post-processing (filtering harmonics, computing the spectral
+  /// power at some nodes...). It is the data of the nodes, transposed so as to
+  /// be indexed by pulse first.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the initialization of the circuit fails.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  /// };
+  /// use num::Complex;
+  ///
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(0., Complex::from(2.))
+  ///   .add_pulse(1e3, Complex::from(0.5))
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(1e3)))
+  ///   .push_serie(Component::from(Capacitor(1e-6)));
+  ///
+  /// let solution = c.frequency_domain_solution()?;
+  /// assert_eq!(solution.len(), 2);
+  /// // No constant current flows through the capacitor, which holds the whole
+  /// // constant tension
+  /// let (potential, current) = solution.get(0, &id![1]).unwrap();
+  /// assert!((potential - 2.).norm() < 1e-12 && current.norm() < 1e-12);
+  /// // At 1000 rad/s, the capacitor divides the tension by 1 + i
+  /// let (potential, _) = solution.get(1, &id![1]).unwrap();
+  /// assert!((potential - Complex::new(0.25, -0.25)).norm() < 1e-12);
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn frequency_domain_solution(&mut self) -> Result<FrequencyDomainResult>
+  {
+    self.init()?;
+    let harmonics = self
+      .voltages()
+      .enumerate()
+      .map(|(index, &(pulse, _))| {
+        let nodes = self
+          .nodes()
+          .map(|node| {
+            (
+              node.id.clone(),
+              (node.potentials[index], node.currents[index]),
+            )
+          })
+          .collect();
+        (pulse, nodes)
+      })
+      .collect();
+    Ok(FrequencyDomainResult { harmonics })
+  }
+}
@@ -0,0 +1,412 @@
+use num::Complex;
+
+use crate::{
+  error::{
+    Error::CircuitBuild,
+    Result,
+  },
+  Circuit,
+  Component,
+  ComponentContent,
+  Dipole,
+};
+
+/// An element of a netlist connecting two nodes. The component is oriented
+/// from the first node to the second one.
+struct Edge
+{
+  from:      String,
+  to:        String,
+  component: Component,
+}
+
+impl Edge
+{
+  /// Swaps the nodes of the edge, reversing the component accordingly.
+  fn reverse(&mut self)
+  {
+    std::mem::swap(&mut self.from, &mut self.to);
+    reverse(&mut self.component);
+  }
+
+  fn other_end(&self, node: &str) -> &str
+  {
+    if self.from == node {
+      &self.to
+    } else {
+      &self.from
+    }
+  }
+}
+
+/// Reverses the order of the series components of a component, so that it
+/// goes through the same dipoles from its back port to its fore port.
+fn reverse(component: &mut Component)
+{
+  use ComponentContent::*;
+  match &mut component.content {
+    Series(components) => {
+      components.reverse();
+      components.iter_mut().for_each(reverse);
+    },
+    Parallel(components) => components.iter_mut().for_each(reverse),
+    _ => (),
+  }
+}
+
+/// Combines two components in series or in parallel, flattening the
+/// combinations of the same kind.
+fn combine(first: Component, second: Component, serie: bool) -> Component
+{
+  use ComponentContent::*;
+  let mut components = Vec::new();
+  for component in [first, second] {
+    match component.content {
+      Series(sub_components) if serie => components.extend(sub_components),
+      Parallel(sub_components) if !serie => components.extend(sub_components),
+      content => components.push(Component {
+        content,
+        ..Component::default()
+      }),
+    }
+  }
+  Component {
+    content: if serie {
+      Series(components)
+    } else {
+      Parallel(components)
+    },
+    ..Component::default()
+  }
+}
+
+/// Parses a SPICE number, with its optional scale suffix: `f`, `p`, `n`, `u`,
+/// `m`, `k`, `meg`, `g` or `t`, case-insensitive. As in SPICE, the letters
+/// following the suffix are ignored, so that `10nF` is `1e-8` but `10F` is
+/// `1e-14` (femto).
+fn parse_value(token: &str) -> Option<f64>
+{
+  let bytes = token.as_bytes();
+  let mut end = 0;
+  while end < bytes.len() {
+    let is_exponent = matches!(bytes[end], b'e' | b'E')
+      && bytes[end + 1..]
+        .iter()
+        .find(|byte| !matches!(byte, b'+' | b'-'))
+        .is_some_and(u8::is_ascii_digit);
+    if bytes[end].is_ascii_alphabetic() && !is_exponent {
+      break;
+    }
+    end += 1;
+  }
+  let value = token[..end].parse::<f64>().ok()?;
+  let suffix = token[end..].to_ascii_lowercase();
+  let scale = if suffix.starts_with("meg") {
+    1e6
+  } else {
+    match suffix.chars().next() {
+      Some('f') => 1e-15,
+      Some('p') => 1e-12,
+      Some('n') => 1e-9,
+      Some('u') => 1e-6,
+      Some('m') => 1e-3,
+      Some('k') => 1e3,
+      Some('g') => 1e9,
+      Some('t') => 1e12,
+      _ => 1.,
+    }
+  };
+  Some(value * scale).filter(|value| value.is_finite())
+}
+
+/// Parses the specification of a voltage source: either a constant tension,
+/// `[DC] value`, or a sine wave, `SIN(offset amplitude frequency)`. Returns the
+/// `(pulse, voltage)` pairs of the source.
+fn parse_source(name: &str, tokens: &[&str]) -> Result<Vec<(f64, Complex<f64>)>>
+{
+  let spec = tokens.join(" ").replace(['(', ')', ','], " ");
+  let words = spec.split_whitespace().collect::<Vec<_>>();
+  let invalid = || {
+    CircuitBuild(format!(
+      "The source {name} must be specified as `[DC] value` or `SIN(offset amplitude \
+       frequency)`, found {:?}",
+      tokens.join(" ")
+    ))
+  };
+  let values = |words: &[&str]| {
+    words
+      .iter()
+      .map(|word| parse_value(word))
+      .collect::<Option<Vec<_>>>()
+      .ok_or_else(invalid)
+  };
+  match words.as_slice() {
+    [kind, rest @ ..] if kind.eq_ignore_ascii_case("sin") => match values(rest)?[..] {
+      [offset, amplitude, frequency] if frequency > 0. => Ok(vec![
+        (0., Complex::from(offset)),
+        // amplitude * sin(ωt) = 2 Re(-i amplitude / 2 * exp(iωt))
+        (
+          2. * std::f64::consts::PI * frequency,
+          Complex::new(0., -0.5 * amplitude),
+        ),
+      ]),
+      _ => Err(invalid()),
+    },
+    [kind, rest @ ..] if kind.eq_ignore_ascii_case("dc") => match values(rest)?[..] {
+      [value] => Ok(vec![(0., Complex::from(value))]),
+      _ => Err(invalid()),
+    },
+    [_] => Ok(vec![(0., Complex::from(values(&words)?[0]))]),
+    _ => Err(invalid()),
+  }
+}
+
+impl Circuit
+{
+  /// Builds a circuit from a SPICE netlist, as found in `.cir` files.
+  ///
+  /// As in SPICE, the first line is the title of the netlist, the lines
+  /// starting with `*` are comments, the ones starting with `+` continue the
+  /// previous line, the text following a `;` is ignored and so are the
+  /// directives starting with `.`, until `.end`. Node names are
+  /// case-insensitive. The supported elements are:
+  /// - `Rname n1 n2 value`, `Cname n1 n2 value` and `Lname n1 n2 value` for the
+  ///   resistors, capacitors and inductors;
+  /// - `Vname n+ n- [DC] value` or `Vname n+ n- SIN(offset amplitude
+  ///   frequency)` for the voltage source, of which there must be exactly one.
+  ///
+  /// Values accept the SPICE scale suffixes `f`, `p`, `n`, `u`, `m`, `k`,
+  /// `meg`, `g` and `t`. The elements are combined into a tree of series and
+  /// parallel components going from the node `n+` of the source to its node
+  /// `n-`, in the order they appear in the netlist.
+  ///
+  /// # Errors
+  ///
+  /// Returns a `CircuitBuild` error if a line is malformed, if there is not
+  /// exactly one voltage source, or if the elements do not form a
+  /// series-parallel network between the nodes of the source. For instance,
+  /// bridges, elements short-circuited by their own nodes, dangling elements or
+  /// disconnected parts cannot be represented.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   Circuit,
+  ///   ComponentContent::{
+  ///     Parallel,
+  ///     Series,
+  ///     Simple,
+  ///   },
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Inductor,
+  ///     Resistor,
+  ///   },
+  /// };
+  /// use num::Complex;
+  ///
+  /// let mut c = Circuit::from_spice(
+  ///   "series RLC
+  ///    V1 in 0 DC 1
+  ///    R1 in n1 200
+  ///    * The inductor and the capacitor
+  ///    L1 n1 n2 100mH
+  ///    C1 n2 0 10n
+  ///    .end",
+  /// )?;
+  /// assert!(matches!(&c.content().content, Series(components) if components.len() == 3));
+  /// assert!(matches!(c.get_comp_by_id(&[0]).unwrap().content, Simple(Resistor(r)) if r == 200.));
+  /// assert!(matches!(c.get_comp_by_id(&[1]).unwrap().content, Simple(Inductor(l)) if l == 0.1));
+  /// assert!(matches!(c.get_comp_by_id(&[2]).unwrap().content, Simple(Capacitor(c)) if (c - 1e-8).abs() < 1e-20));
+  /// // The capacitor holds the constant tension
+  /// c.init()?;
+  /// assert!((c.get_node(&vec![2]).unwrap().next_component_tensions[0] - 1.).norm() < 1e-12);
+  ///
+  /// let c = Circuit::from_spice(
+  ///   "parallel RC
+  ///    V1 1 0 SIN(0 1 1k)
+  ///    R1 1 0 1k
+  ///    C1 0 1 1u",
+  /// )?;
+  /// assert!(matches!(&c.content().content, Parallel(components) if components.len() == 2));
+  /// assert!(matches!(c.get_comp_by_id(&[0]).unwrap().content, Simple(Resistor(r)) if r == 1e3));
+  /// assert!(matches!(c.get_comp_by_id(&[1]).unwrap().content, Simple(Capacitor(c)) if c == 1e-6));
+  /// let (pulse, voltage) = c.voltages().nth(1).unwrap();
+  /// assert!((pulse - 2e3 * std::f64::consts::PI).abs() < 1e-9);
+  /// assert!((voltage - Complex::new(0., -0.5)).norm() < 1e-12);
+  ///
+  /// // A Wheatstone bridge is not a series-parallel network
+  /// assert!(Circuit::from_spice(
+  ///   "bridge
+  ///    V1 a 0 1
+  ///    R1 a b 1k
+  ///    R2 a c 1k
+  ///    R3 b 0 1k
+  ///    R4 c 0 1k
+  ///    R5 b c 1k",
+  /// )
+  /// .is_err());
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn from_spice(netlist: &str) -> Result<Self>
+  {
+    // Join the continuation lines and strip the comments, skipping the title
+    let mut lines: Vec<String> = Vec::new();
+    for line in netlist.lines().skip(1) {
+      let line = line.split(';').next().unwrap().trim();
+      if let Some(continuation) = line.strip_prefix('+') {
+        let previous = lines
+          .last_mut()
+          .ok_or_else(|| CircuitBuild("The netlist starts with a continuation line".to_string()))?;
+        previous.push(' ');
+        previous.push_str(continuation);
+      } else if !line.is_empty() && !line.starts_with('*') {
+        lines.push(line.to_string());
+      }
+    }
+
+    let mut edges = Vec::new();
+    let mut source = None;
+    for line in lines {
+      if line.starts_with('.') {
+        if line.to_ascii_lowercase().starts_with(".end") {
+          break;
+        }
+        continue;
+      }
+      let tokens = line.split_whitespace().collect::<Vec<_>>();
+      let [name, from, to, rest @ ..] = tokens.as_slice() else {
+        return Err(CircuitBuild(format!(
+          "The element line {line:?} does not name two nodes"
+        )));
+      };
+      let (from, to) = (from.to_ascii_lowercase(), to.to_ascii_lowercase());
+      let kind = name.chars().next().unwrap().to_ascii_uppercase();
+      if kind == 'V' {
+        if source.is_some() {
+          return Err(CircuitBuild(format!(
+            "The source {name} is not the only voltage source of the netlist"
+          )));
+        }
+        source = Some((from, to, parse_source(name, rest)?));
+        continue;
+      }
+      let value = match rest {
+        [value] => parse_value(value),
+        _ => None,
+      }
+      .ok_or_else(|| {
+        CircuitBuild(format!(
+          "The element {name} must be followed by its two nodes and its value, found {line:?}"
+        ))
+      })?;
+      let dipole = match kind {
+        'R' => Dipole::Resistor(value),
+        'C' => Dipole::Capacitor(value),
+        'L' => Dipole::Inductor(value),
+        _ =>
+          return Err(CircuitBuild(format!(
+            "The element {name} is not a resistor (R), a capacitor (C), an inductor (L) or a \
+             voltage source (V)"
+          ))),
+      };
+      if from == to {
+        return Err(CircuitBuild(format!(
+          "The element {name} is short-circuited: both its nodes are {from}"
+        )));
+      }
+      edges.push(Edge {
+        from,
+        to,
+        component: Component::from(dipole),
+      });
+    }
+
+    let (plus, minus, voltages) =
+      source.ok_or_else(|| CircuitBuild("The netlist has no voltage source".to_string()))?;
+    if plus == minus {
+      return Err(CircuitBuild(format!(
+        "The voltage source is short-circuited: both its nodes are {plus}"
+      )));
+    }
+
+    // Reduce the network until a single component remains between the nodes
+    // of the source
+    loop {
+      let parallel = (0..edges.len()).find_map(|i| {
+        (i + 1..edges.len())
+          .find(|&j| {
+            (edges[i].from == edges[j].from && edges[i].to == edges[j].to)
+              || (edges[i].from == edges[j].to && edges[i].to == edges[j].from)
+          })
+          .map(|j| (i, j))
+      });
+      if let Some((i, j)) = parallel {
+        let mut second = edges.remove(j);
+        if second.from != edges[i].from {
+          second.reverse();
+        }
+        let first = std::mem::take(&mut edges[i].component);
+        edges[i].component = combine(first, second.component, false);
+        continue;
+      }
+
+      let inner_node = edges.iter().find_map(|edge| {
+        [&edge.from, &edge.to]
+          .into_iter()
+          .find(|&node| {
+            *node != plus
+              && *node != minus
+              && edges
+                .iter()
+                .filter(|edge| edge.from == *node || edge.to == *node)
+                .count()
+                == 2
+          })
+          .cloned()
+      });
+      if let Some(node) = inner_node {
+        let mut indices =
+          (0..edges.len()).filter(|&i| edges[i].from == node || edges[i].to == node);
+        let (i, j) = (indices.next().unwrap(), indices.next().unwrap());
+        let mut second = edges.remove(j);
+        if second.from != node {
+          second.reverse();
+        }
+        if edges[i].to != node {
+          edges[i].reverse();
+        }
+        let first = std::mem::take(&mut edges[i].component);
+        edges[i].component = combine(first, second.component, true);
+        edges[i].to = second.to;
+        continue;
+      }
+      break;
+    }
+
+    let mut edge = match edges.len() {
+      1 if edges[0].other_end(&plus) == minus && (edges[0].from == plus || edges[0].to == plus) =>
+        edges.pop().unwrap(),
+      0 => return Err(CircuitBuild("The netlist has no element".to_string())),
+      _ =>
+        return Err(CircuitBuild(format!(
+          "The elements of the netlist do not form a series-parallel network between the nodes \
+           {plus} and {minus} of the source: bridges, dangling elements and disconnected parts \
+           are not supported"
+        ))),
+    };
+    if edge.from != plus {
+      edge.reverse();
+    }
+
+    let mut circuit = Circuit::new();
+    for (pulse, voltage) in voltages {
+      circuit.add_pulse(pulse, voltage);
+    }
+    let content = circuit.content_mut();
+    *content = edge.component;
+    content.set_id(vec![]);
+    Ok(circuit)
+  }
+}
@@ -4,24 +4,39 @@ use std::{
 };
 
 use fractios::RatioFrac;
-use num::{
-  complex::Complex,
-  PrimInt,
-};
+use num::complex::Complex;
+#[cfg(feature = "fft")]
+use num::PrimInt;
 use num_traits::Zero;
 
+#[cfg(feature = "fft")]
+use super::PeriodicityDiagnostic;
 use super::{
   Component,
+  ComponentContent,
+  Dipole,
   Id,
   Node,
   Source,
 };
 use crate::{
   error::{
+    resonance,
     short_circuit_current,
+    Error::CircuitBuild,
     Result,
   },
-  util::is_multiple_of_x,
+  precision::{
+    DefaultPrecision,
+    Precision,
+  },
+  util::{
+    is_constant,
+    is_multiple_of_x_within,
+    is_near_root,
+    is_negligible,
+    ROOT_TOLERANCE,
+  },
 };
 
 /// The initialisation state of a circuit.
@@ -44,15 +59,30 @@ pub enum CircuitInitState
 pub struct Circuit
 {
   /// The initialisation state of the circuit.
-  pub(super) init_state: CircuitInitState,
+  pub(super) init_state:         CircuitInitState,
   /// The source component of the circuit.
-  pub(super) source:     Source,
+  pub(super) source:             Source,
   /// The main component of the circuit.
-  pub(super) content:    Component,
+  pub(super) content:            Component,
   /// A HashMap that is used to access a Node's voltage and current once the
   /// simulation has started. This won't be used at all during the setup and
   /// shall be initialized when the simulation starts.
-  pub(super) nodes:      HashMap<Id, Node>,
+  pub(super) nodes:              HashMap<Id, Node>,
+  /// The magnitude under which numbers are considered zero during the
+  /// initialization. See [`Circuit::set_numeric_tolerance`].
+  pub(super) tolerance:          f64,
+  /// The tensions of the capacitors and the currents of the inductors at
+  /// t = 0, by ID. See [`Circuit::set_initial_voltage`].
+  pub(super) initial_conditions: HashMap<Id, f64>,
+  /// The node to which the emulated potentials are relative, the aft terminal
+  /// of the source if `None`. See [`Circuit::set_ground`].
+  pub(super) ground:             Option<Id>,
+  /// The sources added in series with the main one, by the ID of the
+  /// component before which they are inserted. See [`Circuit::add_source`].
+  pub(crate) sources:            Vec<(Id, Source)>,
+  /// The circuits driven by each of the added sources alone, whose signals add
+  /// up to those of the circuit driven by the main source alone.
+  pub(crate) superposed:         Vec<Circuit>,
 }
 
 impl Circuit
@@ -61,10 +91,29 @@ impl Circuit
   pub fn new() -> Self
   {
     Self {
-      init_state: CircuitInitState::default(),
-      source:     Source::new(),
-      content:    Component::default(),
-      nodes:      HashMap::new(),
+      init_state:         CircuitInitState::default(),
+      source:             Source::new(),
+      content:            Component::default(),
+      nodes:              HashMap::new(),
+      tolerance:          0f64,
+      initial_conditions: HashMap::new(),
+      ground:             None,
+      sources:            Vec::new(),
+      superposed:         Vec::new(),
+    }
+  }
+
+  /// Creates a new circuit whose map of nodes is preallocated for `n` nodes,
+  /// to avoid rehashing it while building and initializing a large network.
+  /// A circuit has one node per component, whether simple or composite, the
+  /// components of the loads of its `Vcvs` dipoles included (see
+  /// [`Component::count_nodes`]).
+  #[inline]
+  pub fn with_capacity(n: usize) -> Self
+  {
+    Self {
+      nodes: HashMap::with_capacity(n),
+      ..Self::new()
     }
   }
 
@@ -82,9 +131,43 @@ impl Circuit
   /// circuit here : the current will simply be zero. Same for the tension if an
   /// inductor receives a constant current.
   ///
+  /// An error is also returned if a pulse of the source coincides with a zero
+  /// of the impedance of the circuit (e.g. a lossless LC circuit driven at
+  /// its resonance), or more generally if a current or a tension of a
+  /// component becomes infinite or undetermined because of a pole or a zero
+  /// of the impedance of a lossless component.
+  ///
+  /// The messages of both errors give the pulse of the source at fault.
+  ///
   /// # Returns
   ///
   /// Returns `Ok(self)` if the circuit was successfully initialized.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Inductor,
+  ///   },
+  /// };
+  /// use num::Complex;
+  ///
+  /// // A tank is a wire under the constant term of the source only
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(1e3, Complex::from(1.))
+  ///   .add_pulse(0., Complex::from(1.))
+  ///   .content_mut()
+  ///   .push_parallel(Component::from(Capacitor(1e-6)))
+  ///   .push_parallel(Component::from(Inductor(1e-3)));
+  ///
+  /// let message = c.init().unwrap_err().to_string();
+  /// assert!(message.contains("Short circuit"));
+  /// assert!(message.contains("Pulse: 0.0 rad/s"));
+  /// ```
   pub fn init(&mut self) -> Result<&mut Self>
   {
     if self.init_state == CircuitInitState::Source {
@@ -92,23 +175,95 @@ impl Circuit
     }
     self.init_nodes();
     self.content.init_impedance()?;
+    let total_impedance = self.loaded_impedance();
+    // Part of the tension of the source that reaches the circuit
+    let divider = self.source.internal_impedance.as_ref().map(|_| {
+      let mut divider = total_impedance.clone();
+      divider.inv_inplace();
+      divider *= &self.content.impedance;
+      divider.reduce();
+      divider
+    });
+    // A purely resistive circuit scales all the pulses alike, so its response
+    // to a unit tension is computed once and scaled by the spectrum of the source
+    let unit_current = DefaultPrecision::eval_inv(&total_impedance, 0f64);
+    if self.is_resistive()
+      && unit_current.is_finite()
+      && self
+        .source
+        .voltages
+        .iter()
+        .any(|(_, voltage)| !is_negligible(*voltage, self.tolerance))
+    {
+      let unit_tension = divider.as_ref().map_or(Complex::from(1f64), |divider| {
+        DefaultPrecision::eval(divider, 0f64)
+      });
+      self.content.init_current_tension_potential_within(
+        unit_current,
+        unit_tension,
+        unit_tension,
+        0f64,
+        &mut self.nodes,
+        self.tolerance,
+      )?;
+      let scale = |gain: Complex<f64>| -> Vec<Complex<f64>> {
+        self
+          .source
+          .voltages
+          .iter()
+          .map(|(_, voltage)| gain * voltage)
+          .collect()
+      };
+      for node in self.nodes.values_mut() {
+        node.currents = scale(node.currents[0]);
+        node.next_component_tensions = scale(node.next_component_tensions[0]);
+        node.potentials = scale(node.potentials[0]);
+      }
+      self.init_superposed()?;
+      self.init_state = CircuitInitState::Source;
+      return Ok(self);
+    }
     for (pulse, voltage) in self.source.voltages.iter() {
-      if voltage.is_zero() {
+      if is_negligible(*voltage, self.tolerance) {
+        // Keep the spectra of the nodes aligned with the pulses of the source
+        for node in self.nodes.values_mut() {
+          node.currents.push(Complex::zero());
+          node.next_component_tensions.push(Complex::zero());
+          node.potentials.push(Complex::zero());
+        }
         continue;
       }
-      if pulse.is_zero() && is_multiple_of_x(&self.content.impedance) {
-        return short_circuit_current(&vec![0u8], voltage, &self.content.impedance);
+      let is_constant_pulse = pulse.abs() <= self.tolerance;
+      // A wire across the source is a short circuit at all pulses
+      if (is_constant_pulse && is_multiple_of_x_within(&total_impedance, self.tolerance))
+        || self.content.is_short() && self.source.internal_impedance.is_none()
+      {
+        return short_circuit_current(&vec![0u8], voltage, *pulse, &total_impedance);
+      }
+      if !is_constant_pulse
+        && is_near_root(
+          &total_impedance.numerator,
+          Complex::from(*pulse),
+          ROOT_TOLERANCE,
+        )
+      {
+        return resonance(&self.content.fore_node_id, *pulse, &total_impedance);
       }
-      let initial_tension = *voltage;
-      let initial_current = initial_tension * self.impedance().eval_inv(Complex::from(*pulse));
-      self.content.init_current_tension_potential(
+      let initial_tension = match &divider {
+        Some(divider) => voltage * DefaultPrecision::eval(divider, *pulse),
+        None => *voltage,
+      };
+      let initial_current = voltage * DefaultPrecision::eval_inv(&total_impedance, *pulse);
+      self.content.init_current_tension_potential_within(
         initial_current,
         initial_tension,
         initial_tension,
         *pulse,
         &mut self.nodes,
+        self.tolerance,
       )?;
     }
+    self.init_superposed()?;
     self.init_state = CircuitInitState::Source;
     Ok(self)
   }
@@ -119,6 +274,7 @@ impl Circuit
   {
     self.init_state = CircuitInitState::None;
     self.nodes.clear();
+    self.superposed.clear();
     self.content.uninit_all();
     self
   }
@@ -128,6 +284,7 @@ impl Circuit
   pub fn uninit_source(&mut self) -> &mut Self
   {
     self.init_state = self.init_state.min(CircuitInitState::CircuitNodes);
+    self.superposed.clear();
     for node in self.nodes.values_mut() {
       node.next_component_tensions.clear();
       node.currents.clear();
@@ -153,6 +310,21 @@ impl Circuit
     self.nodes.get(id)
   }
 
+  /// Gives the initialisation state of the circuit.
+  #[inline]
+  pub fn init_state(&self) -> CircuitInitState
+  {
+    self.init_state
+  }
+
+  /// Non-consuming iterator over the nodes of the circuit, in no particular
+  /// order.
+  #[inline]
+  pub fn nodes(&self) -> impl Iterator<Item = &Node>
+  {
+    self.nodes.values()
+  }
+
   /// Retrieves a mutable node from the circuit based on its ID.
   ///
   /// # Arguments
@@ -179,6 +351,439 @@ impl Circuit
     &self.content.impedance
   }
 
+  /// Returns `true` if the circuit and the internal impedance of the source
+  /// are purely resistive (see [`Component::is_resistive`]). The tensions and
+  /// currents of such a circuit are scaled copies of the tension of the source,
+  /// which [`Circuit::init`] takes advantage of, and so do the emulations as
+  /// long as the scaling gains are real, the source tension then being
+  /// synthesized alone.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Equivalent,
+  ///     Inductor,
+  ///     Resistor,
+  ///   },
+  /// };
+  /// use fractios::RatioFrac;
+  /// use num::Complex;
+  /// use polyx::{
+  ///   polynomial,
+  ///   Polynomial,
+  /// };
+  ///
+  /// let divider = |with_wire: bool| {
+  ///   let mut c = Circuit::new();
+  ///   c.add_pulse(1e3, Complex::new(0., -0.5))
+  ///     .content_mut()
+  ///     .push_serie(Component::from(Resistor(100.)))
+  ///     .push_serie(Component::from(Resistor(50.)));
+  ///   if with_wire {
+  ///     // A zero inductance behaves as a wire but forces the general path
+  ///     c.get_comp_by_id_mut(&[0])
+  ///       .unwrap()
+  ///       .push_serie(Component::from(Inductor(0.)));
+  ///   }
+  ///   c
+  /// };
+  /// let (mut resistive, mut general) = (divider(false), divider(true));
+  /// assert!(resistive.is_resistive() && !general.is_resistive());
+  ///
+  /// let (_, _, fast) = resistive.emulate_one(1e-2, 1e-4, &id![1u8])?;
+  /// let (_, _, slow) = general.emulate_one(1e-2, 1e-4, &id![1u8])?;
+  /// assert_eq!(fast.len(), slow.len());
+  /// assert!(fast.iter().zip(&slow).all(|(a, b)| (a - b).abs() < 1e-12));
+  ///
+  /// // A constant complex impedance gives complex gains, which the emulations
+  /// // do not take as real
+  /// let impedance = Complex::new(100., 50.);
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(1e3, Complex::from(0.5))
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Equivalent(RatioFrac::from(polynomial![
+  ///     impedance
+  ///   ]))));
+  /// assert!(c.is_resistive());
+  /// let (currents, ..) = c.emulate_one(1e-2, 1e-4, &id![])?;
+  /// for (k, current) in currents.iter().enumerate() {
+  ///   let expected = Complex::new(0., 1e3 * k as f64 * 1e-4).exp() / impedance;
+  ///   assert!((current - expected.re).abs() < 1e-12);
+  /// }
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  #[inline]
+  pub fn is_resistive(&self) -> bool
+  {
+    self.content.is_resistive()
+      && self
+        .source
+        .internal_impedance
+        .as_ref()
+        .is_none_or(is_constant)
+  }
+
+  /// Computes the impedance seen by the source, that is the impedance of the
+  /// circuit in series with the internal impedance of the source, if any.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the impedance of the circuit cannot be computed.
+  pub fn total_impedance(&mut self) -> Result<RatioFrac<Complex<f64>>>
+  {
+    self.init_impedance()?;
+    Ok(self.loaded_impedance())
+  }
+
+  /// Tells whether driving the circuit at a given pulse would short-circuit the
+  /// source, without propagating currents and tensions through the circuit.
+  /// This is the case if the impedance seen by the source vanishes at the
+  /// pulse: at the zero pulse, if it is a multiple of the pulse (e.g. an
+  /// inductor under a constant tension), and at another pulse, if it is a zero
+  /// of the impedance (e.g. a lossless serial LC circuit at its resonance). In
+  /// both cases, [`Circuit::init`] fails if the source has a non-zero voltage
+  /// at that pulse. As in [`Circuit::init`], the zero pulse and the constant
+  /// coefficient of the impedance are compared to the tolerance of
+  /// [`Circuit::set_numeric_tolerance`], while a nonzero pulse is a zero of the
+  /// impedance if its numerator vanishes there, up to a fixed relative
+  /// tolerance of 1e-9 of the magnitudes of its terms.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the impedance of the circuit cannot be computed.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Inductor,
+  ///   },
+  /// };
+  ///
+  /// let mut c = Circuit::new();
+  /// c.content_mut()
+  ///   .push_serie(Component::from(Inductor(1e-3)))
+  ///   .push_serie(Component::from(Capacitor(1e-3)));
+  ///
+  /// // The capacitor blocks the constant current, but the circuit resonates at
+  /// // 1 / √(LC)
+  /// assert!(!c.would_short_circuit(0.)?);
+  /// assert!(c.would_short_circuit(1e3)?);
+  /// assert!(!c.would_short_circuit(2e3)?);
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn would_short_circuit(&mut self, pulse: f64) -> Result<bool>
+  {
+    self.init_impedance()?;
+    let total_impedance = self.loaded_impedance();
+    Ok(if pulse.abs() <= self.tolerance {
+      is_multiple_of_x_within(&total_impedance, self.tolerance)
+    } else {
+      is_near_root(
+        &total_impedance.numerator,
+        Complex::from(pulse),
+        ROOT_TOLERANCE,
+      )
+    })
+  }
+
+  /// Same as [`Circuit::total_impedance`], assuming the impedances are
+  /// initialized.
+  fn loaded_impedance(&self) -> RatioFrac<Complex<f64>>
+  {
+    let mut impedance = self.content.impedance.clone();
+    if let Some(internal_impedance) = &self.source.internal_impedance {
+      impedance += internal_impedance;
+      impedance.reduce();
+    }
+    impedance
+  }
+
+  /// Sets the magnitude under which numbers are considered zero during the
+  /// initialization of the circuit (see [`Circuit::init`]), to absorb the
+  /// floating-point residues left by the reduction and the evaluation of the
+  /// impedances. It is an absolute tolerance, to be chosen according to the
+  /// scale of the circuit. The default is 0, that is exact comparisons.
+  ///
+  /// The tolerance affects the following decisions:
+  /// - a pulse of the source whose voltage is negligible is skipped;
+  /// - a negligible pulse is treated as the zero pulse (DC);
+  /// - at the zero pulse, an impedance whose constant coefficient is negligible
+  ///   is a wire (short circuit), and an impedance whose denominator has a
+  ///   negligible constant coefficient is an open circuit;
+  /// - a negligible current through an open circuit, or tension across a wire,
+  ///   is considered zero instead of leading to a short-circuit error.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `tolerance` is negative or not finite.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Equivalent,
+  ///     Resistor,
+  ///   },
+  /// };
+  /// use fractios::RatioFrac;
+  /// use num::Complex;
+  /// use polyx::{
+  ///   polynomial,
+  ///   Polynomial,
+  /// };
+  ///
+  /// // An inductor whose impedance carries a tiny constant residue
+  /// let impedance = RatioFrac::from(polynomial![Complex::from(1e-15), Complex::new(0., 1e-3)]);
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(0., Complex::from(1.))
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Equivalent(impedance)));
+  ///
+  /// // The residue gives a huge spurious current...
+  /// assert!(c.init().is_ok());
+  /// // ...unless it is below the tolerance, which reveals the short circuit
+  /// assert!(c.set_numeric_tolerance(1e-12).init().is_err());
+  /// ```
+  #[inline]
+  pub fn set_numeric_tolerance(&mut self, tolerance: f64) -> &mut Self
+  {
+    assert!(
+      tolerance >= 0f64 && tolerance.is_finite(),
+      "The numeric tolerance must be non-negative and finite, got {tolerance:?}"
+    );
+    self.tolerance = tolerance;
+    self.uninit_source()
+  }
+
+  /// Gives the numeric tolerance of the circuit. See
+  /// [`Circuit::set_numeric_tolerance`].
+  #[inline]
+  pub fn numeric_tolerance(&self) -> f64
+  {
+    self.tolerance
+  }
+
+  /// Sets the internal impedance of the source, in series with the circuit.
+  /// `None` makes the source ideal, which is the default.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::Resistor,
+  /// };
+  /// use fractios::RatioFrac;
+  /// use num::Complex;
+  ///
+  /// // A 1 V source with a 50 Ω output impedance loaded by 50 Ω
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(0., Complex::from(1.))
+  ///   .set_internal_impedance(Some(RatioFrac::from(Complex::from(50.))))
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(50.)));
+  ///
+  /// // The output tension droops to half the open-circuit tension
+  /// let (_, _, potentials) = c.emulate_one(1., 0.5, &id![])?;
+  /// assert!((potentials[0] - 0.5).abs() < 1e-12);
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  #[inline]
+  pub fn set_internal_impedance(
+    &mut self,
+    internal_impedance: Option<RatioFrac<Complex<f64>>>,
+  ) -> &mut Self
+  {
+    self.source.internal_impedance = internal_impedance;
+    self.uninit_source()
+  }
+
+  /// Sets the tension at t = 0 of the capacitor of a given ID, which is
+  /// uncharged by default. The initial conditions are used by the transient
+  /// solvers, [`Circuit::transient`] and [`Circuit::transient_modes`], and do
+  /// not change the steady state given by the spectral solution.
+  ///
+  /// # Errors
+  ///
+  /// Returns a `CircuitBuild` error if the component of ID `id` is not a
+  /// capacitor.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use std::collections::HashMap;
+  ///
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  /// };
+  /// use num::Complex;
+  ///
+  /// // A capacitor charged at 5 V discharges through a resistor, with a time
+  /// // constant of 1 ms
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(0., Complex::from(0.))
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(1e3)))
+  ///   .push_serie(Component::from(Capacitor(1e-6)));
+  /// c.set_initial_voltage(&id![1], 5.)?;
+  /// assert!(c.set_initial_voltage(&id![0], 5.).is_err());
+  ///
+  /// let (step, duration) = (1e-6, 5e-3);
+  /// let (_, tensions, _) = c
+  ///   .transient(duration, step, &[id![1]], &HashMap::new())?
+  ///   .remove(0);
+  /// let (_, modal_tensions, _) =
+  ///   c.emulate_one_with_initial_conditions(duration, step, &id![1], &HashMap::new())?;
+  /// for (k, (tension, modal_tension)) in tensions.iter().zip(&modal_tensions).enumerate() {
+  ///   let expected = 5. * (-(k as f64) * step / 1e-3).exp();
+  ///   assert!((tension - expected).abs() < 1e-6);
+  ///   assert!((modal_tension - expected).abs() < 1e-9);
+  /// }
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn set_initial_voltage(&mut self, id: &Id, voltage: f64) -> Result<&mut Self>
+  {
+    self.set_initial_condition(id, voltage, true)
+  }
+
+  /// Sets the current at t = 0 of the inductor of a given ID, which is zero by
+  /// default. See [`Circuit::set_initial_voltage`].
+  ///
+  /// # Errors
+  ///
+  /// Returns a `CircuitBuild` error if the component of ID `id` is not an
+  /// inductor.
+  pub fn set_initial_current(&mut self, id: &Id, current: f64) -> Result<&mut Self>
+  {
+    self.set_initial_condition(id, current, false)
+  }
+
+  fn set_initial_condition(&mut self, id: &Id, value: f64, is_capacitor: bool)
+    -> Result<&mut Self>
+  {
+    let expected = match (
+      self.get_comp_by_id(id).map(|component| &component.content),
+      is_capacitor,
+    ) {
+      (Some(ComponentContent::Simple(Dipole::Capacitor(_))), true)
+      | (Some(ComponentContent::Simple(Dipole::Inductor(_))), false) => None,
+      (_, true) => Some("capacitor"),
+      (_, false) => Some("inductor"),
+    };
+    if let Some(expected) = expected {
+      return Err(CircuitBuild(format!(
+        "Component of id {id:?} is not a {expected} and cannot have an initial {}",
+        if is_capacitor { "voltage" } else { "current" }
+      )));
+    }
+    self.initial_conditions.insert(id.clone(), value);
+    Ok(self.uninit_source())
+  }
+
+  /// Gives the initial conditions set with [`Circuit::set_initial_voltage`]
+  /// and [`Circuit::set_initial_current`], by ID.
+  #[inline]
+  pub fn initial_conditions(&self) -> &HashMap<Id, f64>
+  {
+    &self.initial_conditions
+  }
+
+  /// Declares a node as the ground of the circuit, the reference of the
+  /// potentials given by the emulation: the potential of this node is
+  /// subtracted from the potentials of all the nodes. By default, the
+  /// potentials are relative to the aft terminal of the source. The tensions,
+  /// the currents and the spectra of the nodes do not depend on the ground.
+  ///
+  /// The node is looked up when emulating, which fails if it does not exist.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::Resistor,
+  /// };
+  /// use num::Complex;
+  ///
+  /// // A chain of four equal resistors, whose inner divider spans the nodes
+  /// // [1] to [3], its midpoint being the node [2]
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(0., Complex::from(0.5))
+  ///   .add_pulse(1e3, Complex::new(0., -0.5));
+  /// for _ in 0..4 {
+  ///   c.content_mut().push_serie(Component::from(Resistor(1e3)));
+  /// }
+  /// let (_, tensions, potentials) = c.emulate_one(1e-2, 1e-5, &id![1])?;
+  /// assert!(potentials.iter().any(|potential| *potential > 0.1));
+  ///
+  /// c.set_ground(id![2]);
+  /// let (_, _, top) = c.emulate_one(1e-2, 1e-5, &id![1])?;
+  /// let (_, _, middle) = c.emulate_one(1e-2, 1e-5, &id![2])?;
+  /// let (_, _, bottom) = c.emulate_one(1e-2, 1e-5, &id![3])?;
+  /// for k in 0..top.len() {
+  ///   assert!(middle[k].abs() < 1e-12);
+  ///   assert!((top[k] + bottom[k]).abs() < 1e-12);
+  ///   assert!((top[k] - tensions[k]).abs() < 1e-12);
+  /// }
+  /// // The tensions do not depend on the ground, even down to the source
+  /// let (_, last_tensions, _) = c.emulate_one(1e-2, 1e-5, &id![3])?;
+  /// let across = c.voltage_across(&id![3], 1e-2, 1e-5)?;
+  /// assert!(across
+  ///   .iter()
+  ///   .zip(&last_tensions)
+  ///   .all(|(a, b)| (a - b).abs() < 1e-12));
+  ///
+  /// c.set_ground(id![4]);
+  /// assert!(c.emulate_one(1e-2, 1e-5, &id![1]).is_err());
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  #[inline]
+  pub fn set_ground(&mut self, id: Id) -> &mut Self
+  {
+    self.ground = Some(id);
+    self
+  }
+
+  /// Makes the aft terminal of the source the ground of the circuit again,
+  /// which is the default. See [`Circuit::set_ground`].
+  #[inline]
+  pub fn clear_ground(&mut self) -> &mut Self
+  {
+    self.ground = None;
+    self
+  }
+
+  /// Gives the ground of the circuit, `None` being the aft terminal of the
+  /// source. See [`Circuit::set_ground`].
+  #[inline]
+  pub fn ground(&self) -> Option<&Id>
+  {
+    self.ground.as_ref()
+  }
+
   /// Gives a reference to the main component of the circuit.
   #[inline]
   pub fn content(&self) -> &Component
@@ -213,7 +818,8 @@ impl Circuit
 
   /// Gives a mutable reference to a component of the circuit based on its ID.
   /// This method assumes that the circuit will be modified and uninitializes it
-  /// completely.
+  /// completely, along with the components containing the one found (see
+  /// [`Component::get_comp_by_id_mut_uninit`]).
   ///
   /// # Arguments
   ///
@@ -226,7 +832,53 @@ impl Circuit
   #[inline]
   pub fn get_comp_by_id_mut(&mut self, id: &[u8]) -> Option<&mut Component>
   {
-    self.uninit_all().content.get_comp_by_id_mut(id)
+    self.uninit_all().content.get_comp_by_id_mut_uninit(id)
+  }
+
+  /// Gives the IDs of the fore node and of the aft node of a component, the
+  /// latter being `None` for the ground. The nodes are set up if needed. See
+  /// [`Component::aft_node_id`].
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Inductor,
+  ///     Resistor,
+  ///   },
+  /// };
+  ///
+  /// let mut c = Circuit::new();
+  /// c.content_mut()
+  ///   .push_serie(Component::from(Resistor(100.)))
+  ///   .push_serie(Component::from(Capacitor(1e-6)))
+  ///   .push_serie(Component::from(Inductor(1e-3)));
+  /// c.get_comp_by_id_mut(&[1])
+  ///   .unwrap()
+  ///   .push_parallel(Component::from(Resistor(1e3)));
+  ///
+  /// // Along the series chain, each component ends where the next one starts...
+  /// assert_eq!(c.terminals(&[0]), Some((id![0], Some(id![1]))));
+  /// assert_eq!(c.terminals(&[1]), Some((id![1], Some(id![2]))));
+  /// assert_eq!(c.terminals(&[2]), Some((id![2], None)));
+  /// // ...and the branches of a parallel combination end where it ends
+  /// assert_eq!(c.terminals(&[1, 0]), Some((id![1, 0], Some(id![2]))));
+  /// assert_eq!(c.terminals(&[1, 1]), Some((id![1, 1], Some(id![2]))));
+  /// assert_eq!(c.terminals(&[]), Some((id![], None)));
+  /// assert_eq!(c.terminals(&[3]), None);
+  /// ```
+  pub fn terminals(&mut self, id: &[u8]) -> Option<(Id, Option<Id>)>
+  {
+    let component = self.init_nodes().content.get_comp_by_id(id)?;
+    Some((
+      component.fore_node_id.clone(),
+      component.aft_node_id.clone(),
+    ))
   }
 
   /// Sets up the nodes IDs of the `Circuit` and its components.
@@ -236,11 +888,26 @@ impl Circuit
     if self.init_state > CircuitInitState::CircuitNodes {
       return self;
     }
-    self.content.init_nodes(&mut self.nodes);
+    self.nodes.reserve(self.content.count_nodes());
+    self.content.init_nodes(&mut self.nodes, None);
     self.init_state = CircuitInitState::CircuitNodes;
     self
   }
 
+  /// Sets up the nodes IDs of the `Circuit` and computes the impedances of its
+  /// components, without requiring a source.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if a component is in a `Poisoned` state.
+  #[inline]
+  pub fn init_impedance(&mut self) -> Result<&mut Self>
+  {
+    self.init_nodes();
+    self.content.init_impedance()?;
+    Ok(self)
+  }
+
   /// Sets the voltage at a specific index in the `voltages` vector.
   #[inline]
   pub fn set_voltage(&mut self, index: usize, voltage: Complex<f64>) -> &mut Self
@@ -259,6 +926,45 @@ impl Circuit
     self.uninit_source()
   }
 
+  /// Adds the tone `amplitude * cos(2π frequency t + phase)` to the
+  /// generator, as a single pulse. See [`Source::add_tone`].
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  /// };
+  ///
+  /// // A RC low-pass filter driven at its cutoff frequency
+  /// let (r, cap) = (1e3, 1e-6);
+  /// let cutoff = (2. * std::f64::consts::PI * r * cap).recip();
+  /// let mut c = Circuit::new();
+  /// c.add_tone(cutoff, 2., 0.)
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(r)))
+  ///   .push_serie(Component::from(Capacitor(cap)));
+  ///
+  /// // The output is 3 dB below the input
+  /// let period = cutoff.recip();
+  /// let (_, _, output) = c.emulate_one(period, period / 1e3, &id![1])?;
+  /// let peak = output.iter().cloned().fold(0., f64::max);
+  /// assert!((20. * (peak / 2.).log10() + 3.0103).abs() < 1e-3);
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  #[inline]
+  pub fn add_tone(&mut self, frequency: f64, amplitude: f64, phase: f64) -> &mut Self
+  {
+    self.source.add_tone(frequency, amplitude, phase);
+    self.uninit_source()
+  }
+
   /// Removes the pulse at the specified index from the generator.
   #[inline]
   pub fn remove_pulse(&mut self, index: usize) -> &mut Self
@@ -275,12 +981,24 @@ impl Circuit
     self.uninit_source()
   }
 
+  /// Replaces the whole generator at once, uninitializing the source-dependent
+  /// state of the circuit only once instead of once per pulse as with
+  /// [`Circuit::clear_source`] followed by calls to [`Circuit::add_pulse`].
+  /// The structure of the circuit and its impedances are kept.
+  #[inline]
+  pub fn replace_source(&mut self, source: Source) -> &mut Self
+  {
+    self.source = source;
+    self.uninit_source()
+  }
+
   /// Clears and updates the generator using a real valued function that
   /// generates voltage values using its Fourier transform. The function takes
   /// a time value as input and returns a voltage value. The `duration`
   /// parameter specifies the total duration of the voltage source (henceforth
   /// the duration of the simulation). The `n_freqs_` parameter specifies the
   /// number of frequencies to use in the Fourier series.
+  #[cfg(feature = "fft")]
   #[inline]
   pub fn set_generator_fn<I, F>(&mut self, f: F, duration: f64, n_freqs: I) -> &mut Self
   where
@@ -291,6 +1009,82 @@ impl Circuit
     self.uninit_source()
   }
 
+  /// Same as [`Circuit::set_generator_fn`], but also returns a diagnostic of
+  /// the periodicity of `f` over the analysis window. See
+  /// [`Source::set_fn_checked`].
+  #[cfg(feature = "fft")]
+  #[inline]
+  pub fn set_generator_fn_checked<I, F>(
+    &mut self,
+    f: F,
+    duration: f64,
+    n_freqs: I,
+  ) -> PeriodicityDiagnostic
+  where
+    F: Fn(f64) -> f64,
+    I: PrimInt + Debug,
+  {
+    let diagnostic = self.source.set_fn_checked(f, duration, n_freqs);
+    self.uninit_source();
+    diagnostic
+  }
+
+  /// Same as [`Circuit::set_generator_fn`] for a complex valued function,
+  /// whose negative pulses are kept. The emulations then give the real part of
+  /// the complex signals. See [`Source::set_complex_fn`].
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  /// };
+  /// use num::Complex;
+  ///
+  /// // A RC low-pass filter driven by a phasor at its cutoff pulse, over a
+  /// // window holding one of its periods
+  /// let (pulse, duration) = (1e3, std::f64::consts::PI * 1e-3);
+  /// let mut c = Circuit::new();
+  /// c.set_generator_complex_fn(|t| Complex::new(0., pulse * t).exp(), duration, 3)
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(1e3)))
+  ///   .push_serie(Component::from(Capacitor(1e-6)));
+  /// c.init()?;
+  ///
+  /// // The output lags by 45° and is attenuated by √2...
+  /// let index = c
+  ///   .voltages()
+  ///   .position(|(p, _)| (p - pulse).abs() < 1e-6)
+  ///   .unwrap();
+  /// let output = c.get_node(&id![1]).unwrap().potentials[index];
+  /// assert!((output.arg() + std::f64::consts::FRAC_PI_4).abs() < 1e-9);
+  /// assert!((output.norm() - 0.5f64.sqrt()).abs() < 1e-9);
+  /// // ...and the emulation gives its real part, without the doubling of the
+  /// // terms of a real source
+  /// let (_, _, potentials) = c.emulate_one(duration, 1e-5, &id![1])?;
+  /// for (k, potential) in potentials.iter().enumerate() {
+  ///   let expected = 0.5f64.sqrt() * (pulse * k as f64 * 1e-5 - std::f64::consts::FRAC_PI_4).cos();
+  ///   assert!((potential - expected).abs() < 1e-9);
+  /// }
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  #[cfg(feature = "fft")]
+  #[inline]
+  pub fn set_generator_complex_fn<I, F>(&mut self, f: F, duration: f64, n_freqs: I) -> &mut Self
+  where
+    F: Fn(f64) -> Complex<f64>,
+    I: PrimInt + Debug,
+  {
+    self.source.set_complex_fn(f, duration, n_freqs);
+    self.uninit_source()
+  }
+
   /// Non-consuming iterator over the pulses and their voltages
   #[inline]
   pub fn voltages(&self) -> impl Iterator<Item = &(f64, Complex<f64>)>
@@ -1,4 +1,10 @@
-use std::collections::HashMap;
+use std::{
+  collections::HashMap,
+  ops::{
+    Add,
+    BitOr,
+  },
+};
 
 use fractios::RatioFrac;
 use num::complex::Complex;
@@ -17,15 +23,22 @@ use super::{
 use crate::{
   error::{
     self,
+    resonance,
     short_circuit_current,
     short_circuit_tension,
     Error::CircuitBuild,
   },
+  precision::{
+    DefaultPrecision,
+    Precision,
+  },
   util::{
     evaluate_zero_without_invx,
     evaluate_zero_without_x,
-    is_multiple_of_invx,
-    is_multiple_of_x,
+    is_constant,
+    is_multiple_of_invx_within,
+    is_multiple_of_x_within,
+    is_negligible,
   },
 };
 
@@ -96,6 +109,12 @@ pub struct Component
   pub impedance:    RatioFrac<Complex<f64>>,
   /// The ID of the node connected to the component's fore port.
   pub fore_node_id: Id,
+  /// The ID of the node connected to the component's aft port, `None`
+  /// standing for the ground. It is the fore node of the next component of a
+  /// series combination, and the aft node of the enclosing combination
+  /// otherwise. Only set by
+  /// [`Circuit::init_nodes`](crate::Circuit::init_nodes).
+  pub aft_node_id:  Option<Id>,
   pub init_state:   ComponentInitState,
 }
 
@@ -107,11 +126,265 @@ impl Component
     Component::default()
   }
 
-  /// Returns the impedance of the component for a given pulse.
+  /// Returns the impedance of the component for a given pulse, evaluated with
+  /// the [`DefaultPrecision`] backend.
   #[inline]
   pub fn impedance(&self, pulse: f64) -> Complex<f64>
   {
-    self.impedance.eval(Complex::from(pulse))
+    self.impedance_with::<DefaultPrecision>(pulse)
+  }
+
+  /// Returns the impedance of the component for a given pulse, evaluated with
+  /// a chosen numeric backend. See the [`Precision`] trait.
+  #[inline]
+  pub fn impedance_with<P: Precision>(&self, pulse: f64) -> Complex<f64>
+  {
+    P::eval(&self.impedance, pulse)
+  }
+
+  /// Returns the magnitude of the impedance of the component for a given
+  /// pulse, in ohms. See [`Component::impedance`].
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  /// };
+  ///
+  /// let mut rc = Component::from(Resistor(1e3)) + Component::from(Capacitor(1e-6));
+  /// rc.init_impedance()?;
+  /// assert!((rc.impedance_magnitude(1e3) - 1e3 * 2f64.sqrt()).abs() < 1e-9);
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  #[inline]
+  pub fn impedance_magnitude(&self, pulse: f64) -> f64
+  {
+    self.impedance(pulse).norm()
+  }
+
+  /// Returns the admittance of the component for a given pulse, that is the
+  /// inverse of its impedance, evaluated with the [`DefaultPrecision`]
+  /// backend. It is evaluated directly rather than as `1 / impedance(pulse)`,
+  /// so that it is zero and not NaN at a pole of the impedance.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Inductor,
+  ///     Resistor,
+  ///   },
+  /// };
+  ///
+  /// let mut rlc = Component::from(Resistor(200.));
+  /// rlc
+  ///   .push_serie(Component::from(Capacitor(10e-9)))
+  ///   .push_serie(Component::from(Inductor(100e-3)));
+  /// rlc.init_impedance()?;
+  /// for pulse in [1e2, 1e3, 1e4, 1e5, 1e6] {
+  ///   let expected = 1. / rlc.impedance(pulse);
+  ///   assert!((rlc.admittance(pulse) - expected).norm() <= 1e-12 * expected.norm());
+  /// }
+  ///
+  /// // The admittance of a capacitor vanishes at the zero pulse
+  /// let mut capacitor = Component::from(Capacitor(1e-6));
+  /// capacitor.init_impedance()?;
+  /// assert_eq!(capacitor.admittance(0.), 0f64.into());
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  #[inline]
+  pub fn admittance(&self, pulse: f64) -> Complex<f64>
+  {
+    self.admittance_with::<DefaultPrecision>(pulse)
+  }
+
+  /// Returns the admittance of the component for a given pulse, evaluated with
+  /// a chosen numeric backend. See the [`Precision`] trait.
+  #[inline]
+  pub fn admittance_with<P: Precision>(&self, pulse: f64) -> Complex<f64>
+  {
+    P::eval_inv(&self.impedance, pulse)
+  }
+
+  /// Returns the admittance of the component as a rational fraction of the
+  /// pulse, that is a copy of its impedance inverted. The impedance itself is
+  /// left untouched.
+  #[inline]
+  pub fn admittance_ratiofrac(&self) -> RatioFrac<Complex<f64>>
+  {
+    let mut admittance = self.impedance.clone();
+    admittance.inv_inplace();
+    admittance
+  }
+
+  /// Returns `true` if the component is only made of capacitors and inductors,
+  /// that is if it does not dissipate any energy, transformers being reactive
+  /// if their load is. `Equivalent` dipoles are never considered reactive.
+  pub fn is_reactive(&self) -> bool
+  {
+    use ComponentContent::*;
+    match &self.content {
+      Series(components) | Parallel(components) => components.iter().all(Component::is_reactive),
+      Simple(Dipole::Capacitor(_) | Dipole::Inductor(_)) => true,
+      Simple(Dipole::Transformer { load, .. }) => load.is_reactive(),
+      _ => false,
+    }
+  }
+
+  /// Returns the number of simple dipoles of the component, a poisoned
+  /// component having none.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   Component,
+  ///   ComponentContent::Poisoned,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Inductor,
+  ///     Resistor,
+  ///   },
+  /// };
+  ///
+  /// // R1 + (C1 // (R2 + L1)) + C2
+  /// let branch = Component::from(Resistor(2.)) + Component::from(Inductor(1e-3));
+  /// let root = Component::from(Resistor(1.))
+  ///   + (Component::from(Capacitor(1e-6)) | branch)
+  ///   + Component::from(Capacitor(2e-6));
+  /// assert_eq!(root.count_dipoles(), 5);
+  /// assert_eq!(root.depth(), 3);
+  /// assert_eq!(root.count_nodes(), 8);
+  ///
+  /// let mut poisoned = Component::from(Resistor(1.));
+  /// poisoned.content = Poisoned;
+  /// assert_eq!(poisoned.count_dipoles(), 0);
+  /// assert_eq!(Component::from(Resistor(1.)).depth(), 0);
+  /// ```
+  pub fn count_dipoles(&self) -> usize
+  {
+    use ComponentContent::*;
+    match &self.content {
+      Series(components) | Parallel(components) =>
+        components.iter().map(Component::count_dipoles).sum(),
+      Simple(_) => 1,
+      Poisoned => 0,
+    }
+  }
+
+  /// Returns the depth of the component, that is the length of the longest ID
+  /// of its subcomponents relative to it: 0 for a simple dipole, and one more
+  /// than the deepest of its subcomponents for a combination. The loads of
+  /// transformers and controlled sources are not counted.
+  pub fn depth(&self) -> usize
+  {
+    use ComponentContent::*;
+    match &self.content {
+      Series(components) | Parallel(components) => components
+        .iter()
+        .map(|component| component.depth() + 1)
+        .max()
+        .unwrap_or(0),
+      Simple(_) | Poisoned => 0,
+    }
+  }
+
+  /// Returns the number of nodes of a circuit made of the component, as given
+  /// by [`Circuit::nodes`](crate::Circuit::nodes): each component, the
+  /// combinations and the component itself included, has its fore node, and
+  /// so do the components of the loads of the voltage-controlled voltage
+  /// sources.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///     Vcvs,
+  ///   },
+  /// };
+  ///
+  /// let mut c = Circuit::new();
+  /// c.content_mut()
+  ///   .push_serie(Component::from(Resistor(1e3)))
+  ///   .push_serie(Component::from(Resistor(1e3)));
+  /// c.get_comp_by_id_mut(&[1])
+  ///   .unwrap()
+  ///   .push_parallel(Component::from(Vcvs {
+  ///     control: id![1],
+  ///     gain:    10.,
+  ///     load:    Box::new(Component::from(Resistor(100.)) + Component::from(Capacitor(1e-6))),
+  ///   }));
+  /// c.init_nodes();
+  /// assert_eq!(c.content().count_nodes(), 8);
+  /// assert_eq!(c.nodes().count(), c.content().count_nodes());
+  /// ```
+  pub fn count_nodes(&self) -> usize
+  {
+    use ComponentContent::*;
+    1 + match &self.content {
+      Series(components) | Parallel(components) =>
+        components.iter().map(Component::count_nodes).sum(),
+      Simple(Dipole::Vcvs { load, .. }) => load.count_nodes(),
+      Simple(_) | Poisoned => 0,
+    }
+  }
+
+  /// Returns `true` if the component is only made of resistors, wires, open
+  /// circuits and `Equivalent` dipoles whose impedance does not depend on the
+  /// pulse, or of transformers loaded by such components, so that its response
+  /// is the same at all frequencies.
+  pub fn is_resistive(&self) -> bool
+  {
+    use ComponentContent::*;
+    match &self.content {
+      Series(components) | Parallel(components) => components.iter().all(Component::is_resistive),
+      Simple(Dipole::Resistor(_) | Dipole::Short | Dipole::Open) => true,
+      Simple(Dipole::Equivalent(impedance)) => is_constant(impedance),
+      Simple(Dipole::Transformer { load, .. }) => load.is_resistive(),
+      _ => false,
+    }
+  }
+
+  /// Returns `true` if the component is a wire at all pulses, that is a
+  /// `Short` dipole, a series combination of wires or a parallel combination
+  /// with at least one wire.
+  pub fn is_short(&self) -> bool
+  {
+    use ComponentContent::*;
+    match &self.content {
+      Series(components) => components.iter().all(Component::is_short),
+      Parallel(components) => components.iter().any(Component::is_short),
+      Simple(Dipole::Short) => true,
+      _ => false,
+    }
+  }
+
+  /// Returns `true` if the component is an open circuit at all pulses, that is
+  /// an `Open` dipole, a series combination with at least one open circuit or
+  /// a parallel combination of open circuits.
+  pub fn is_open(&self) -> bool
+  {
+    use ComponentContent::*;
+    match &self.content {
+      Series(components) => components.iter().any(Component::is_open),
+      Parallel(components) => components.iter().all(Component::is_open),
+      Simple(Dipole::Open) => true,
+      _ => false,
+    }
   }
 }
 
@@ -141,6 +414,7 @@ impl From<ComponentContent> for Component
       content,
       impedance: RatioFrac::default(),
       fore_node_id: Id::default(),
+      aft_node_id: None,
       init_state: ComponentInitState::default(),
     }
   }
@@ -155,8 +429,76 @@ impl From<Dipole> for Component
   }
 }
 
+/// Series combination, see [`Component::series`].
+impl Add for Component
+{
+  type Output = Component;
+
+  #[inline]
+  fn add(self, other: Component) -> Component
+  {
+    Component::series(self, other)
+  }
+}
+
+/// Parallel combination, see [`Component::parallel`].
+impl BitOr for Component
+{
+  type Output = Component;
+
+  #[inline]
+  fn bitor(self, other: Component) -> Component
+  {
+    Component::parallel(self, other)
+  }
+}
+
 impl Component
 {
+  /// Creates a simple component from a dipole, checking its value, unlike
+  /// [`Component::from`] which accepts any dipole. So does the
+  /// `TryFrom<Dipole>` implementation derived from `From<Dipole>` (e.g.
+  /// `dipole.try_into()`), which never fails: only this function validates
+  /// the dipole.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the dipole is a resistor, a capacitor or an inductor
+  /// whose value is not strictly positive and finite. Ideal wires and open
+  /// circuits are given by the `Short` and `Open` dipoles.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   Component,
+  ///   Dipole::Resistor,
+  /// };
+  ///
+  /// assert!(Component::checked(Resistor(10.)).is_ok());
+  /// assert!(Component::checked(Resistor(0.)).is_err());
+  /// assert!(Component::checked(Resistor(-5.)).is_err());
+  /// assert!(Component::checked(Resistor(f64::INFINITY)).is_err());
+  /// assert!(Component::checked(Resistor(f64::NAN)).is_err());
+  /// ```
+  pub fn checked(dipole: Dipole) -> error::Result<Self>
+  {
+    let (name, value) = match dipole {
+      Dipole::Resistor(r) => ("resistance", r),
+      Dipole::Capacitor(c) => ("capacitance", c),
+      Dipole::Inductor(l) => ("inductance", l),
+      _ => return Ok(Self::from(dipole)),
+    };
+    // NaN values are rejected as well
+    if value > 0f64 && value.is_finite() {
+      Ok(Self::from(dipole))
+    } else {
+      Err(CircuitBuild(format!(
+        "The {name} of a dipole must be strictly positive and finite, got {value}"
+      )))
+    }
+  }
+
   /// Pushes a component onto self in series.
   ///
   /// # Arguments
@@ -285,13 +627,115 @@ impl Component
     self
   }
 
-  // Swaps two components in a branch
+  /// Combines two components in series into a new one, whose IDs are set from
+  /// scratch, unlike [`Component::push_serie`]. Same as `first + second`.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Inductor,
+  ///     Resistor,
+  ///   },
+  /// };
+  /// use num::Complex;
+  ///
+  /// let mut rc = Component::series(
+  ///   Component::from(Resistor(100.)),
+  ///   Component::from(Capacitor(1e-6)),
+  /// );
+  /// rc.init_impedance()?;
+  /// let pulse = 1e3;
+  /// let expected = Complex::from(100.) + (Complex::i() * 1e-6 * pulse).inv();
+  /// assert!((rc.impedance(pulse) - expected).norm() < 1e-9);
+  ///
+  /// // The operators nest the combinations, with fresh IDs
+  /// let tank = (Component::from(Inductor(1e-3)) | Component::from(Capacitor(1e-6)))
+  ///   + Component::from(Resistor(10.));
+  /// assert_eq!(
+  ///   tank.get_comp_by_id(&[0, 1]).unwrap().fore_node_id,
+  ///   id![0, 1]
+  /// );
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn series(first: Component, second: Component) -> Component
+  {
+    let mut component = first;
+    component.push_serie(second).uninit_all().set_id(Id::new());
+    component
+  }
+
+  /// Combines two components in parallel into a new one, whose IDs are set
+  /// from scratch, unlike [`Component::push_parallel`]. Same as
+  /// `first | second`.
+  pub fn parallel(first: Component, second: Component) -> Component
+  {
+    let mut component = first;
+    component
+      .push_parallel(second)
+      .uninit_all()
+      .set_id(Id::new());
+    component
+  }
+
+  /// Swaps two components in a branch. The IDs of the swapped components and
+  /// of their subcomponents are updated to match their new positions.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if `self` is not a branch or if an index is out of
+  /// bounds.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  /// };
+  /// use num::Complex;
+  ///
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(0., Complex::from(1.))
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(100.)))
+  ///   .push_serie(Component::from(Capacitor(1e-6)));
+  /// c.content_mut().swap(0, 1)?;
+  ///
+  /// // The node [1] now precedes the resistor, which holds no constant tension
+  /// let (_, tensions, potentials) = c.emulate_one(1., 0.5, &id![1u8])?;
+  /// assert!(tensions[0].abs() < 1e-12);
+  /// assert!(potentials[0].abs() < 1e-12);
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
   #[inline]
   pub fn swap(&mut self, index1: usize, index2: usize) -> error::Result<&mut Self>
   {
     use ComponentContent::*;
     match &mut self.content {
-      Series(components) | Parallel(components) => components.swap(index1, index2),
+      Series(components) | Parallel(components) => {
+        if index1.max(index2) >= components.len() {
+          return Err(CircuitBuild(format!(
+            "Cannot swap components {index1} and {index2} in a branch of {} components",
+            components.len()
+          )));
+        }
+        components.swap(index1, index2);
+        for index in [index1, index2] {
+          let mut id = self.fore_node_id.clone();
+          id.push(index as u8);
+          components[index].set_id(id);
+        }
+      },
       _ =>
         return Err(CircuitBuild(
           "Cannot swap components in a non-branch component".to_string(),
@@ -300,6 +744,282 @@ impl Component
     Ok(self)
   }
 
+  /// Removes a subcomponent from its branch and returns it. The IDs of the
+  /// following components of the branch and of their subcomponents are
+  /// updated to match their new positions, while the removed component keeps
+  /// its former IDs.
+  ///
+  /// # Arguments
+  ///
+  /// * `id` - The ID of the component to remove, relative to `self`.
+  ///
+  /// # Errors
+  ///
+  /// Returns a `CircuitBuild` error if `id` is empty (the component cannot
+  /// remove itself), if it does not point to a component of a branch, or if
+  /// that component is the only one of its branch, which would be left empty.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   Component,
+  ///   ComponentContent::Simple,
+  ///   Dipole::Resistor,
+  /// };
+  ///
+  /// let mut component = Component::from(Resistor(100.));
+  /// component
+  ///   .push_serie(Component::from(Resistor(200.)))
+  ///   .push_serie(Component::from(Resistor(300.)));
+  ///
+  /// let removed = component.remove_by_id(&[1])?;
+  /// assert!(matches!(removed.content, Simple(Resistor(r)) if r == 200.));
+  ///
+  /// // The last resistor moved to the position [1]
+  /// let last = component.get_comp_by_id(&[1]).unwrap();
+  /// assert!(matches!(last.content, Simple(Resistor(r)) if r == 300.));
+  /// assert_eq!(last.fore_node_id, vec![1u8]);
+  /// assert!(component.get_comp_by_id(&[2]).is_none());
+  ///
+  /// component.init_impedance()?;
+  /// assert_eq!(component.impedance(0.), 400f64.into());
+  ///
+  /// assert!(component.remove_by_id(&[]).is_err());
+  /// assert!(component.remove_by_id(&[0, 0]).is_err());
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn remove_by_id(&mut self, id: &[u8]) -> error::Result<Component>
+  {
+    use ComponentContent::*;
+    let Some((&index, parent_id)) = id.split_last() else {
+      return Err(CircuitBuild(
+        "Cannot remove a component from itself".to_string(),
+      ));
+    };
+    let index = index as usize;
+    // The impedances of all the components containing the removed one become
+    // stale
+    let parent = self
+      .get_comp_by_id_mut_uninit(parent_id)
+      .ok_or_else(|| CircuitBuild(format!("Component of id {parent_id:?} not found")))?;
+    let removed = match &mut parent.content {
+      Series(components) | Parallel(components) if index < components.len() => {
+        if components.len() == 1 {
+          return Err(CircuitBuild(format!(
+            "Cannot remove the component of id {id:?}, the only one of its branch"
+          )));
+        }
+        let removed = components.remove(index);
+        for (index, component) in components.iter_mut().enumerate().skip(index) {
+          let mut id = parent.fore_node_id.clone();
+          id.push(index as u8);
+          component.set_id(id);
+        }
+        removed
+      },
+      _ => return Err(CircuitBuild(format!("Component of id {id:?} not found"))),
+    };
+    Ok(removed)
+  }
+
+  /// Removes the degenerate series and parallel combinations of a single
+  /// component, which are electrically the same as that component, by
+  /// replacing them with it, anywhere in the tree. The IDs of the components
+  /// are then renumbered to match their new positions, so that they may become
+  /// shorter. Unlike a flattening of nested combinations of the same kind, the
+  /// structure of the tree is otherwise kept.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   Component,
+  ///   ComponentContent::{
+  ///     Parallel,
+  ///     Series,
+  ///     Simple,
+  ///   },
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  /// };
+  ///
+  /// // A resistor wrapped in a series of one parallel combination of one
+  /// // component, in series with a capacitor
+  /// let wrapped = Component::from(Series(vec![Component::from(Parallel(vec![
+  ///   Component::from(Resistor(100.)),
+  /// ]))]));
+  /// let mut component = Component::from(Series(vec![wrapped, Component::from(Capacitor(1e-6))]));
+  /// component.simplify();
+  ///
+  /// let simplified = component.get_comp_by_id(&[0]).unwrap();
+  /// assert!(matches!(
+  ///   simplified.content,
+  ///   circuits_simulator::ComponentContent::Simple(_)
+  /// ));
+  /// assert_eq!(simplified.fore_node_id, vec![0u8]);
+  /// assert!(component.get_comp_by_id(&[0, 0]).is_none());
+  ///
+  /// let mut reference = Component::from(Resistor(100.));
+  /// reference.push_serie(Component::from(Capacitor(1e-6)));
+  /// component.init_impedance()?;
+  /// reference.init_impedance()?;
+  /// assert!((component.impedance(1e3) - reference.impedance(1e3)).norm() < 1e-9);
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn simplify(&mut self) -> &mut Self
+  {
+    self.collapse_single_branches();
+    self.set_id(self.fore_node_id.clone());
+    self
+  }
+
+  /// Replaces the combinations of a single component by that component,
+  /// without updating the IDs.
+  fn collapse_single_branches(&mut self)
+  {
+    use ComponentContent::*;
+    if let Series(components) | Parallel(components) = &mut self.content {
+      for component in components.iter_mut() {
+        component.collapse_single_branches();
+      }
+      if components.len() == 1 {
+        let child = components.pop().unwrap();
+        self.content = child.content;
+      }
+      self.uninit_all();
+    }
+  }
+
+  /// Merges the adjacent resistors, capacitors and inductors of the same kind
+  /// in each series and parallel combination of the tree into a single dipole
+  /// of equivalent value: resistances and inductances add up in series and
+  /// combine reciprocally in parallel, and conversely for capacitances. The
+  /// combinations left with a single component are then replaced by it, and
+  /// the IDs are renumbered to match the new positions, as in
+  /// [`Component::simplify`]. The equivalent dipoles are left untouched, and
+  /// so is the order of the components.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   ComponentContent::Simple,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Inductor,
+  ///     Resistor,
+  ///   },
+  /// };
+  /// use num::Complex;
+  ///
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(1e4, Complex::new(0., -0.5))
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(100.)))
+  ///   .push_serie(Component::from(Resistor(120.)))
+  ///   .push_serie(Component::from(Capacitor(20e-9)))
+  ///   .push_serie(Component::from(Capacitor(20e-9)))
+  ///   .push_serie(Component::from(Inductor(50e-3)));
+  /// c.get_comp_by_id_mut(&[4])
+  ///   .unwrap()
+  ///   .push_parallel(Component::from(Inductor(50e-3)));
+  /// let before = c.emulate_one(1e-3, 1e-5, &id![])?;
+  ///
+  /// c.content_mut().merge_adjacent();
+  /// let merged = c.content();
+  /// let value = |id: &[u8]| match merged.get_comp_by_id(id).map(|comp| &comp.content) {
+  ///   Some(Simple(Resistor(x) | Capacitor(x) | Inductor(x))) => *x,
+  ///   _ => panic!("Not a dipole"),
+  /// };
+  /// assert_eq!(value(&[0]), 220.);
+  /// assert!((value(&[1]) - 10e-9).abs() < 1e-20);
+  /// assert!((value(&[2]) - 25e-3).abs() < 1e-15);
+  /// assert!(merged.get_comp_by_id(&[3]).is_none());
+  ///
+  /// let after = c.emulate_one(1e-3, 1e-5, &id![])?;
+  /// for (a, b) in before.0.iter().zip(after.0.iter()) {
+  ///   assert!((a - b).abs() < 1e-12);
+  /// }
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn merge_adjacent(&mut self) -> &mut Self
+  {
+    self.merge_adjacent_dipoles();
+    self.collapse_single_branches();
+    self.set_id(self.fore_node_id.clone());
+    self
+  }
+
+  /// Merges the adjacent dipoles of the same kind, without updating the IDs.
+  fn merge_adjacent_dipoles(&mut self)
+  {
+    use ComponentContent::*;
+    use Dipole::*;
+    // Combination of two values adding up reciprocally, a zero value standing
+    // for a short or an open circuit that absorbs the other one
+    let reciprocal = |a: f64, b: f64| {
+      if a == 0f64 || b == 0f64 {
+        0f64
+      } else {
+        a * b / (a + b)
+      }
+    };
+    let in_series = matches!(self.content, Series(_));
+    if let Series(components) | Parallel(components) = &mut self.content {
+      let mut merged: Vec<Component> = Vec::with_capacity(components.len());
+      for mut component in components.drain(..) {
+        component.merge_adjacent_dipoles();
+        if let (Some(last), Simple(next)) = (merged.last_mut(), &component.content) {
+          let combined = match (&last.content, next, in_series) {
+            (Simple(Resistor(a)), Resistor(b), true) => Some(Resistor(a + b)),
+            (Simple(Resistor(a)), Resistor(b), false) => Some(Resistor(reciprocal(*a, *b))),
+            (Simple(Inductor(a)), Inductor(b), true) => Some(Inductor(a + b)),
+            (Simple(Inductor(a)), Inductor(b), false) => Some(Inductor(reciprocal(*a, *b))),
+            (Simple(Capacitor(a)), Capacitor(b), true) => Some(Capacitor(reciprocal(*a, *b))),
+            (Simple(Capacitor(a)), Capacitor(b), false) => Some(Capacitor(a + b)),
+            _ => None,
+          };
+          if let Some(combined) = combined {
+            last.content = Simple(combined);
+            last.uninit_all();
+            continue;
+          }
+        }
+        merged.push(component);
+      }
+      *components = merged;
+      self.uninit_all();
+    }
+  }
+
+  /// Sets the ID of the component, and the IDs of its subcomponents
+  /// accordingly.
+  pub(crate) fn set_id(&mut self, id: Id)
+  {
+    use ComponentContent::*;
+    match &mut self.content {
+      Series(components) | Parallel(components) =>
+        for (index, component) in components.iter_mut().enumerate() {
+          let mut sub_id = id.clone();
+          sub_id.push(index as u8);
+          component.set_id(sub_id);
+        },
+      Simple(Dipole::Vcvs { load, .. }) => {
+        let mut load_id = id.clone();
+        load_id.push(0u8);
+        load.set_id(load_id);
+      },
+      _ => (),
+    }
+    self.fore_node_id = id;
+  }
+
   /// Initializes the impedance of the component.
   ///
   /// This method calculates and sets the impedance of the component based on
@@ -339,29 +1059,56 @@ impl Component
     use ComponentContent::*;
     match &mut self.content {
       Series(components) => {
+        if components
+          .iter()
+          .any(|component| matches!(component.content, Simple(Dipole::Vcvs { .. })))
+        {
+          return Err(CircuitBuild(
+            "A controlled source draws no current and cannot be in series".to_string(),
+          ));
+        }
         let mut impedance = RatioFrac::default();
         for component in components.iter_mut() {
           component.init_impedance()?;
           impedance += &component.impedance;
         }
         impedance.reduce();
-        self.impedance = impedance;
+        // An open circuit absorbs the other impedances, whose sum with an
+        // infinite one is not reduced to it
+        self.impedance = if components.iter().any(Component::is_open) {
+          Dipole::Open.impedance()?
+        } else {
+          impedance
+        };
       },
       Parallel(components) => {
         let mut impedance = RatioFrac::default();
         for component in components.iter_mut() {
           component.init_impedance()?;
-          // This is a bit tricky, but it should make the computation faster because
-          // only one additional ratiofrac is created, instead of 2 without inv_inplace
-          component.impedance.inv_inplace();
-          impedance += &component.impedance;
-          component.impedance.inv_inplace();
+          impedance += &component.admittance_ratiofrac();
         }
         impedance.inv_inplace();
         impedance.reduce();
-        self.impedance = impedance;
+        // Same for a wire and the other admittances
+        self.impedance = if components.iter().any(Component::is_short) {
+          Dipole::Short.impedance()?
+        } else {
+          impedance
+        };
+      },
+      Simple(dipole) => {
+        match dipole {
+          // The load may have been modified through the transformer
+          Dipole::Transformer { load, .. } => {
+            load.uninit_all().init_impedance()?;
+          },
+          Dipole::Vcvs { load, .. } => {
+            load.init_impedance()?;
+          },
+          _ => (),
+        }
+        self.impedance = dipole.impedance()?;
       },
-      Simple(dipole) => self.impedance = dipole.impedance()?,
       Poisoned =>
         return Err(CircuitBuild(
           "Cannot initialize impedance of poisoned component".to_string(),
@@ -385,8 +1132,10 @@ impl Component
   /// # Errors
   ///
   /// Returns an error if the component's initialization state is not
-  /// appropriate or if there is a short circuit in the circuit. For more
-  /// details on short circuits, see `[Circuit::init]`.
+  /// appropriate, if there is a short circuit in the circuit or if a current or
+  /// a tension becomes infinite because of a resonance. For more details, see
+  /// `[Circuit::init]`.
+  #[inline]
   pub fn init_current_tension_potential(
     &mut self,
     current: Complex<f64>,
@@ -395,6 +1144,23 @@ impl Component
     pulse: f64,
     nodes: &mut HashMap<Id, Node>,
   ) -> error::Result<&mut Self>
+  {
+    self.init_current_tension_potential_within(current, tension, fore_potential, pulse, nodes, 0f64)
+  }
+
+  /// Same as [`Component::init_current_tension_potential`], where pulses,
+  /// currents, tensions and constant coefficients of impedances whose
+  /// magnitude does not exceed `tolerance` are considered zero. See
+  /// [`Circuit::set_numeric_tolerance`](crate::Circuit::set_numeric_tolerance).
+  pub(crate) fn init_current_tension_potential_within(
+    &mut self,
+    current: Complex<f64>,
+    tension: Complex<f64>,
+    fore_potential: Complex<f64>,
+    pulse: f64,
+    nodes: &mut HashMap<Id, Node>,
+    tolerance: f64,
+  ) -> error::Result<&mut Self>
   {
     if self.init_state > ComponentInitState::CurrentTensionPotential {
       return Ok(self);
@@ -405,6 +1171,10 @@ impl Component
       ));
     }
 
+    if !(current.is_finite() && tension.is_finite() && fore_potential.is_finite()) {
+      return resonance(&self.fore_node_id, pulse, &self.impedance);
+    }
+
     let node = nodes
       .get_mut(self.fore_node_id.as_slice())
       .expect("Node not found :/");
@@ -414,76 +1184,172 @@ impl Component
 
     use ComponentContent::*;
     match &mut self.content {
+      Series(components) if components.iter().any(Component::is_open) => {
+        // No current flows through the combination, so that the first open
+        // circuit takes the whole tension and the other components none
+        let mut remaining_potential = fore_potential;
+        let mut open_found = false;
+        for component in components.iter_mut() {
+          let next_tension = if !open_found && component.is_open() {
+            open_found = true;
+            tension
+          } else {
+            Complex::zero()
+          };
+          component.init_current_tension_potential_within(
+            Complex::zero(),
+            next_tension,
+            remaining_potential,
+            pulse,
+            nodes,
+            tolerance,
+          )?;
+          remaining_potential -= next_tension;
+        }
+      },
+      Parallel(components) if components.iter().any(Component::is_short) => {
+        // A wire under a non zero tension draws an infinite current
+        if !is_negligible(tension, tolerance) {
+          return short_circuit_tension(&self.fore_node_id, tension, pulse, &self.impedance);
+        }
+        // Otherwise, the first wire takes the whole current and the other
+        // components none
+        let mut short_found = false;
+        for component in components.iter_mut() {
+          let next_current = if !short_found && component.is_short() {
+            short_found = true;
+            current
+          } else {
+            Complex::zero()
+          };
+          component.init_current_tension_potential_within(
+            next_current,
+            Complex::zero(),
+            fore_potential,
+            pulse,
+            nodes,
+            tolerance,
+          )?;
+        }
+      },
       Series(components) => {
         let mut remaining_potential = fore_potential;
         for component in components.iter_mut() {
-          if !pulse.is_zero() || !is_multiple_of_invx(&component.impedance) {
-            let next_tension = current * component.impedance.eval(Complex::from(pulse));
-            component.init_current_tension_potential(
+          if pulse.abs() > tolerance || !is_multiple_of_invx_within(&component.impedance, tolerance)
+          {
+            let next_tension = current * DefaultPrecision::eval(&component.impedance, pulse);
+            component.init_current_tension_potential_within(
               current,
               next_tension,
               remaining_potential,
               pulse,
               nodes,
+              tolerance,
             )?;
             remaining_potential -= next_tension;
-          } else if current.is_zero() {
+          } else if is_negligible(current, tolerance) {
             /* We suppose a zero current is always due to a zero admittance
             Otherwise, the emulation for this pulse would not have started or have
             panicked */
-            assert!(is_multiple_of_invx(&self.impedance));
+            assert!(is_multiple_of_invx_within(&self.impedance, tolerance));
             // We factor by the "impedance ratio"
             let next_tension = tension * evaluate_zero_without_invx(&component.impedance)
               / evaluate_zero_without_invx(&self.impedance);
-            component.init_current_tension_potential(
+            component.init_current_tension_potential_within(
               current,
               next_tension,
               remaining_potential,
               pulse,
               nodes,
+              tolerance,
             )?;
             remaining_potential -= next_tension;
           } else {
-            return short_circuit_current(&component.fore_node_id, current, &component.impedance);
+            return short_circuit_current(
+              &component.fore_node_id,
+              current,
+              pulse,
+              &component.impedance,
+            );
           }
         }
       },
       Parallel(components) => {
         for component in components.iter_mut() {
-          if !pulse.is_zero() || !is_multiple_of_x(&component.impedance) {
-            // Better to do inv_inplace instead of calling .inv() on the impendance because
-            // NaN.inv() = NaN and not 0, which can lead to false short-circuit detection
-            component.impedance.inv_inplace();
-            let evaluated_admittance = component.impedance.eval(Complex::from(pulse));
-            component.impedance.inv_inplace();
-
-            component.init_current_tension_potential(
+          if pulse.abs() > tolerance || !is_multiple_of_x_within(&component.impedance, tolerance) {
+            // Better to evaluate the admittance directly instead of calling .inv() on the
+            // evaluated impedance because NaN.inv() = NaN and not 0, which can lead to
+            // false short-circuit detection
+            let evaluated_admittance = component.admittance(pulse);
+
+            component.init_current_tension_potential_within(
               tension * evaluated_admittance,
               tension,
               fore_potential,
               pulse,
               nodes,
+              tolerance,
             )?;
-          } else if tension.is_zero() {
+          } else if is_negligible(tension, tolerance) {
             /* We suppose a zero tension is always due to a zero impedance
             Otherwise, the emulation for this pulse would not have started or have
             panicked */
-            assert!(is_multiple_of_x(&self.impedance));
+            assert!(is_multiple_of_x_within(&self.impedance, tolerance));
             let current_factor = evaluate_zero_without_x(&self.impedance)
               / evaluate_zero_without_x(&component.impedance);
             // We factor by the "admittance ratio"
-            component.init_current_tension_potential(
+            component.init_current_tension_potential_within(
               current * current_factor,
               tension,
               fore_potential,
               pulse,
               nodes,
+              tolerance,
             )?;
           } else {
-            return short_circuit_tension(&component.fore_node_id, tension, &component.impedance);
+            return short_circuit_tension(
+              &component.fore_node_id,
+              tension,
+              pulse,
+              &component.impedance,
+            );
           }
         }
       },
+      Simple(Dipole::Vcvs {
+        control,
+        gain,
+        load,
+      }) => {
+        // The potentials of the solved nodes are pushed pulse after pulse
+        let index = nodes[self.fore_node_id.as_slice()].potentials.len() - 1;
+        let control_potential = nodes
+          .get(control.as_slice())
+          .and_then(|node| node.potentials.get(index))
+          .copied()
+          .ok_or_else(|| {
+            CircuitBuild(format!(
+              "The controlling node {control:?} of the source of id {:?} does not exist or is \
+               not solved before it",
+              self.fore_node_id
+            ))
+          })?;
+        let output = *gain * control_potential;
+        let mut load_id = self.fore_node_id.clone();
+        load_id.push(0u8);
+        if load.fore_node_id != load_id {
+          load.set_id(load_id);
+        }
+        let load_current = output * load.admittance(pulse);
+        load.init_current_tension_potential_within(
+          load_current,
+          output,
+          output,
+          pulse,
+          nodes,
+          tolerance,
+        )?;
+      },
       _ => (),
     };
     self.init_state = ComponentInitState::CurrentTensionPotential;
@@ -564,28 +1430,265 @@ impl Component
     }
   }
 
+  /// Same as [`Component::get_comp_by_id_mut`], but also uninitializes the
+  /// components along the ID, from `self` to the component found: their
+  /// impedances become stale as soon as the component is modified. The other
+  /// components keep their initialization.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   Component,
+  ///   ComponentContent,
+  ///   Dipole::Resistor,
+  /// };
+  ///
+  /// let mut component = Component::from(Resistor(100.))
+  ///   + (Component::from(Resistor(100.)) | Component::from(Resistor(100.)));
+  /// component.init_impedance()?;
+  /// assert_eq!(component.impedance(0.), 150f64.into());
+  ///
+  /// if let Some(ComponentContent::Simple(Resistor(r))) = component
+  ///   .get_comp_by_id_mut_uninit(&[1, 0])
+  ///   .map(|component| &mut component.content)
+  /// {
+  ///   *r = 300.;
+  /// }
+  /// component.init_impedance()?;
+  /// assert_eq!(component.impedance(0.), 175f64.into());
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn get_comp_by_id_mut_uninit(&mut self, id: &[u8]) -> Option<&mut Component>
+  {
+    use ComponentContent::*;
+    self.uninit_all();
+    let Some((&index, id)) = id.split_first() else {
+      return Some(self);
+    };
+    match self.content {
+      Series(ref mut components) | Parallel(ref mut components) => components
+        .get_mut(index as usize)?
+        .get_comp_by_id_mut_uninit(id),
+      _ => None,
+    }
+  }
+
+  /// Depth-first iterator over the component and all its subcomponents, in
+  /// pre-order. Each component comes with its ID relative to `self`, that is
+  /// the path to give to [`Component::get_comp_by_id`] to find it again. It
+  /// does not rely on the IDs stored in the components. The loads of the
+  /// `Transformer` and `Vcvs` dipoles are not visited, as they do not belong to
+  /// the tree of the component.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   Component,
+  ///   ComponentContent::Simple,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Inductor,
+  ///     Resistor,
+  ///   },
+  /// };
+  ///
+  /// // R1 + (C1 // (R2 + L1)) + C2
+  /// let mut branch = Component::from(Resistor(2.));
+  /// branch.push_serie(Component::from(Inductor(1e-3)));
+  /// let mut parallel = Component::from(Capacitor(1e-6));
+  /// parallel.push_parallel(branch);
+  /// let mut root = Component::from(Resistor(1.));
+  /// root
+  ///   .push_serie(parallel)
+  ///   .push_serie(Component::from(Capacitor(2e-6)));
+  ///
+  /// let ids = root.iter().map(|(id, _)| id).collect::<Vec<_>>();
+  /// assert_eq!(
+  ///   ids,
+  ///   [
+  ///     vec![],
+  ///     vec![0],
+  ///     vec![1],
+  ///     vec![1, 0],
+  ///     vec![1, 1],
+  ///     vec![1, 1, 0],
+  ///     vec![1, 1, 1],
+  ///     vec![2]
+  ///   ]
+  /// );
+  /// // Each simple dipole is visited once, and its ID leads back to it
+  /// let dipoles = root
+  ///   .iter()
+  ///   .filter(|(_, component)| matches!(component.content, Simple(_)))
+  ///   .collect::<Vec<_>>();
+  /// assert_eq!(dipoles.len(), root.count_dipoles());
+  /// for (id, component) in dipoles {
+  ///   assert!(std::ptr::eq(root.get_comp_by_id(&id).unwrap(), component));
+  /// }
+  /// ```
+  #[inline]
+  pub fn iter(&self) -> ComponentIter<'_>
+  {
+    ComponentIter {
+      stack: vec![(Id::new(), self)],
+    }
+  }
+
+  /// Depth-first iterator over the simple dipoles (and the poisoned
+  /// components) of the component only, with mutable access. Unlike
+  /// [`Component::iter`], the series and parallel components are not yielded,
+  /// as they contain the dipoles: use [`Component::get_comp_by_id_mut_uninit`]
+  /// with the IDs given by [`Component::iter`] to modify them. They are
+  /// uninitialized instead, as well as the yielded dipoles, since their
+  /// impedances may change. As with [`Component::iter`], the loads of the
+  /// `Transformer` and `Vcvs` dipoles are not visited.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   Component,
+  ///   ComponentContent::Simple,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  /// };
+  ///
+  /// let mut root = Component::from(Capacitor(1e-6));
+  /// root.push_parallel(Component::from(Resistor(1e3)));
+  /// root.push_serie(Component::from(Capacitor(2e-6)));
+  ///
+  /// // Double every capacitance
+  /// for (_, component) in root.dipoles_mut() {
+  ///   if let Simple(Capacitor(c)) = &mut component.content {
+  ///     *c *= 2.;
+  ///   }
+  /// }
+  /// assert!(matches!(root.get_comp_by_id(&[0, 0]).unwrap().content, Simple(Capacitor(c)) if c == 2e-6));
+  /// assert!(matches!(root.get_comp_by_id(&[1]).unwrap().content, Simple(Capacitor(c)) if c == 4e-6));
+  /// ```
+  #[inline]
+  pub fn dipoles_mut(&mut self) -> DipolesMut<'_>
+  {
+    DipolesMut {
+      stack: vec![(Id::new(), self)],
+    }
+  }
+
   /// Sets up the nodes of the `Component` and its children. In particular, the
   /// `nodes` HashMap is filled with the nodes of the circuit. It is assumed
-  /// that the IDs of the `Component` and its children are already set.
+  /// that the IDs of the `Component` and its children are already set. The
+  /// aft nodes of the components are set along the way.
   ///
   /// # Arguments
   ///
   /// * `nodes` - A mutable reference to a `HashMap` containing the nodes of the
   ///   circuit.
-  pub(super) fn init_nodes(&self, nodes: &mut HashMap<Id, Node>) -> &Self
+  /// * `aft_node_id` - The ID of the aft node of the `Component`, `None`
+  ///   standing for the ground.
+  pub(super) fn init_nodes(
+    &mut self,
+    nodes: &mut HashMap<Id, Node>,
+    aft_node_id: Option<Id>,
+  ) -> &mut Self
   {
     use ComponentContent::*;
+    self.aft_node_id = aft_node_id;
     let id = &self.fore_node_id;
     let mut node = Node::new();
     node.id = id.clone();
     nodes.insert(id.clone(), node);
-    match &self.content {
-      Series(components) | Parallel(components) =>
-        for component in components.iter() {
-          component.init_nodes(nodes);
+    match &mut self.content {
+      Series(components) =>
+        for k in 0..components.len() {
+          let aft_node_id = match components.get(k + 1) {
+            Some(next) => Some(next.fore_node_id.clone()),
+            None => self.aft_node_id.clone(),
+          };
+          components[k].init_nodes(nodes, aft_node_id);
+        },
+      Parallel(components) =>
+        for component in components.iter_mut() {
+          component.init_nodes(nodes, self.aft_node_id.clone());
         },
+      Simple(Dipole::Vcvs { load, .. }) => {
+        // The IDs of the load may not be set yet, and it drives an isolated
+        // network against the ground
+        let mut load = load.as_ref().clone();
+        let mut load_id = id.clone();
+        load_id.push(0u8);
+        load.set_id(load_id);
+        load.init_nodes(nodes, None);
+      },
       _ => (),
     }
     self
   }
 }
+
+/// Depth-first iterator over a component and its subcomponents, with their
+/// IDs. See [`Component::iter`].
+#[derive(Clone, Debug)]
+pub struct ComponentIter<'a>
+{
+  stack: Vec<(Id, &'a Component)>,
+}
+
+impl<'a> Iterator for ComponentIter<'a>
+{
+  type Item = (Id, &'a Component);
+
+  fn next(&mut self) -> Option<Self::Item>
+  {
+    let (id, component) = self.stack.pop()?;
+    if let ComponentContent::Series(components) | ComponentContent::Parallel(components) =
+      &component.content
+    {
+      for (index, child) in components.iter().enumerate().rev() {
+        let mut child_id = id.clone();
+        child_id.push(index as u8);
+        self.stack.push((child_id, child));
+      }
+    }
+    Some((id, component))
+  }
+}
+
+/// Depth-first iterator over the simple dipoles of a component, with their IDs
+/// and mutable access. See [`Component::dipoles_mut`].
+#[derive(Debug)]
+pub struct DipolesMut<'a>
+{
+  stack: Vec<(Id, &'a mut Component)>,
+}
+
+impl<'a> Iterator for DipolesMut<'a>
+{
+  type Item = (Id, &'a mut Component);
+
+  fn next(&mut self) -> Option<Self::Item>
+  {
+    loop {
+      let (id, component) = self.stack.pop()?;
+      component.uninit_all();
+      if !matches!(
+        component.content,
+        ComponentContent::Series(_) | ComponentContent::Parallel(_)
+      ) {
+        return Some((id, component));
+      }
+      if let ComponentContent::Series(components) | ComponentContent::Parallel(components) =
+        &mut component.content
+      {
+        for (index, child) in components.iter_mut().enumerate().rev() {
+          let mut child_id = id.clone();
+          child_id.push(index as u8);
+          self.stack.push((child_id, child));
+        }
+      }
+    }
+  }
+}
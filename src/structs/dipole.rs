@@ -11,9 +11,14 @@ use serde::{
   Serializer,
 };
 
-use crate::error::{
-  self,
-  Error::CircuitBuild,
+use crate::{
+  error::{
+    self,
+    Error::CircuitBuild,
+  },
+  Component,
+  ComponentInitState,
+  Id,
 };
 
 #[derive(Clone, Debug, Default)]
@@ -27,9 +32,169 @@ pub enum Dipole
   Capacitor(f64),
   /// An inductor with a given inductance in henries.
   Inductor(f64),
+  /// An ideal wire, of zero impedance at all pulses. It takes the whole
+  /// current of a parallel combination it belongs to, which must then be
+  /// under a zero tension.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///     Short,
+  ///   },
+  /// };
+  /// use num::Complex;
+  ///
+  /// // A capacitor bypassed by a wire, in series with a resistor
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(0., Complex::from(1.))
+  ///   .add_pulse(1e3, Complex::from(1.))
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(100.)))
+  ///   .push_serie(Component::from(Capacitor(1e-6)));
+  /// c.get_comp_by_id_mut(&[1])
+  ///   .unwrap()
+  ///   .push_parallel(Component::from(Short));
+  /// c.init()?;
+  ///
+  /// for k in 0..2 {
+  ///   // The resistor takes the whole tension at all pulses...
+  ///   assert!((c.get_node(&id![0]).unwrap().currents[k] - 1e-2).norm() < 1e-12);
+  ///   assert!(c.get_node(&id![1]).unwrap().potentials[k].norm() < 1e-12);
+  ///   // ...and the current flows through the wire only
+  ///   assert!(c.get_node(&id![1, 0]).unwrap().currents[k].norm() < 1e-12);
+  ///   assert!((c.get_node(&id![1, 1]).unwrap().currents[k] - 1e-2).norm() < 1e-12);
+  /// }
+  ///
+  /// // A wire directly across the source is a genuine short circuit
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(0., Complex::from(1.))
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Short));
+  /// assert!(c.init().is_err());
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  Short,
+  /// An open circuit, of infinite impedance at all pulses. No current flows
+  /// through a series combination it belongs to, and it takes the whole
+  /// tension of the combination.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Open,
+  ///     Resistor,
+  ///   },
+  /// };
+  /// use num::Complex;
+  ///
+  /// // A resistor in parallel with a branch interrupted by an open circuit
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(0., Complex::from(2.))
+  ///   .add_pulse(1e3, Complex::from(1.))
+  ///   .content_mut()
+  ///   .push_parallel(Component::from(Resistor(50.)))
+  ///   .push_parallel(Component::from(Resistor(100.)));
+  /// c.get_comp_by_id_mut(&[1])
+  ///   .unwrap()
+  ///   .push_serie(Component::from(Open))
+  ///   .push_serie(Component::from(Capacitor(1e-6)));
+  /// c.init()?;
+  ///
+  /// for (k, voltage) in [2., 1.].into_iter().enumerate() {
+  ///   // The current only flows through the resistor...
+  ///   assert!((c.get_node(&id![]).unwrap().currents[k] - voltage / 50.).norm() < 1e-12);
+  ///   assert!(c.get_node(&id![1]).unwrap().currents[k].norm() < 1e-12);
+  ///   // ...and the open circuit takes the whole tension of its branch
+  ///   let open = c.get_node(&id![1, 1]).unwrap();
+  ///   assert!((open.next_component_tensions[k] - voltage).norm() < 1e-12);
+  ///   assert!(c.get_node(&id![1, 2]).unwrap().potentials[k].norm() < 1e-12);
+  /// }
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  Open,
   /// An equivalent component represented by a rational fraction the pulse with
   /// complex coefficients.
   Equivalent(RatioFrac<Complex<f64>>),
+  /// An ideal transformer whose secondary winding is closed on a load. Its
+  /// `ratio` is the number of turns of the primary winding over that of the
+  /// secondary one, so that the load seen from the primary winding is scaled
+  /// by `ratio²`. Only the impedance of the load is used, its own currents and
+  /// tensions are not computed.
+  Transformer
+  {
+    ratio: f64, load: Box<Component>
+  },
+  /// An ideal voltage-controlled voltage source, whose output tension is
+  /// `gain` times the potential of the `control` node and drives `load`, an
+  /// isolated network (e.g. the output stage of an operational amplifier
+  /// macromodel). The IDs of the load are those of a subcomponent of the
+  /// source, starting with the ID of the source followed by 0.
+  ///
+  /// Since the circuit is solved as a tree from its root, two restrictions
+  /// apply:
+  /// - the source draws no current from the circuit, where it stands as an open
+  ///   circuit: it must be placed in parallel with other components, and not in
+  ///   series;
+  /// - the `control` node must be solved before the source, that is appear
+  ///   earlier in the traversal order of the tree (e.g. the fore node of a
+  ///   combination containing the source, or of a component preceding it).
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Resistor,
+  ///     Vcvs,
+  ///   },
+  /// };
+  /// use num::Complex;
+  ///
+  /// // A gain stage of 10 sensing the middle of a divider and driving 100 Ω
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(0., Complex::from(1.))
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(1e3)))
+  ///   .push_serie(Component::from(Resistor(1e3)));
+  /// c.get_comp_by_id_mut(&[1])
+  ///   .unwrap()
+  ///   .push_parallel(Component::from(Vcvs {
+  ///     control: id![1],
+  ///     gain:    10.,
+  ///     load:    Box::new(Component::from(Resistor(100.))),
+  ///   }));
+  /// c.init()?;
+  ///
+  /// // The divider is not loaded by the source
+  /// assert!((c.get_node(&id![1]).unwrap().potentials[0] - 0.5).norm() < 1e-12);
+  /// // The load is the subcomponent [1, 1, 0]
+  /// let output = c.get_node(&id![1, 1, 0]).unwrap();
+  /// assert!((output.potentials[0] - 5.).norm() < 1e-12);
+  /// assert!((output.currents[0] - 0.05).norm() < 1e-12);
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  Vcvs
+  {
+    control: Id,
+    gain:    f64,
+    load:    Box<Component>,
+  },
   /// A poisoned state, used as a default state.
   #[default]
   Poisoned,
@@ -38,6 +203,33 @@ pub enum Dipole
 impl Dipole
 {
   /// Calculates the impedance of a dipole.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the dipole is poisoned, or if it is a transformer
+  /// whose load is.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   Component,
+  ///   Dipole::{
+  ///     Resistor,
+  ///     Transformer,
+  ///   },
+  /// };
+  /// use num::Complex;
+  ///
+  /// // A 1:2 transformer loaded with 100 Ω presents 25 Ω on its primary
+  /// let mut transformer = Component::from(Transformer {
+  ///   ratio: 0.5,
+  ///   load:  Box::new(Component::from(Resistor(100.))),
+  /// });
+  /// transformer.init_impedance()?;
+  /// assert_eq!(transformer.impedance(1e3), Complex::from(25.));
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
   #[inline]
   pub fn impedance(&self) -> error::Result<RatioFrac<Complex<f64>>>
   {
@@ -54,7 +246,31 @@ impl Dipole
         Complex::zero(),
         Complex { re: 0f64, im: *l }
       ])),
+      Dipole::Short => Ok(RatioFrac::from(Complex::zero())),
+      Dipole::Open => Ok(RatioFrac::from((
+        polynomial![Complex::from(1f64)],
+        polynomial![Complex::zero()],
+      ))),
       Dipole::Equivalent(e) => Ok(e.clone()),
+      Dipole::Transformer { ratio, load } => {
+        // The impedance of the load is computed in place by
+        // `Component::init_impedance`, it is only computed on a copy here
+        // otherwise
+        let mut impedance = if load.init_state >= ComponentInitState::Impedance {
+          load.impedance.clone()
+        } else {
+          let mut load = load.as_ref().clone();
+          load.init_impedance()?;
+          load.impedance
+        };
+        impedance *= &RatioFrac::from(Complex::from(ratio * ratio));
+        Ok(impedance)
+      },
+      // No current flows into the source
+      Dipole::Vcvs { .. } => Ok(RatioFrac::from((
+        polynomial![Complex::from(1f64)],
+        polynomial![Complex::zero()],
+      ))),
       Dipole::Poisoned => Err(CircuitBuild(
         "Called impedance on poisoned dipole".to_string(),
       )),
@@ -62,13 +278,82 @@ impl Dipole
   }
 }
 
+#[derive(Serialize)]
+struct ComplexFormat
+{
+  re: f64,
+  im: f64,
+}
+
+/// The coefficients of the numerator and of the denominator of a rational
+/// fraction, lowest order first.
+#[derive(Serialize)]
+struct RatioFracFormat
+{
+  numerator:   Vec<ComplexFormat>,
+  denominator: Vec<ComplexFormat>,
+}
+
+impl From<&RatioFrac<Complex<f64>>> for RatioFracFormat
+{
+  fn from(r: &RatioFrac<Complex<f64>>) -> Self
+  {
+    let format = |p: &Polynomial<Complex<f64>>| {
+      p.iter()
+        .map(|c| ComplexFormat { re: c.re, im: c.im })
+        .collect()
+    };
+    RatioFracFormat {
+      numerator:   format(&r.numerator),
+      denominator: format(&r.denominator),
+    }
+  }
+}
+
+/// Serializes a dipole as its `"type"` and its `"value"`. The value of an
+/// `Equivalent` dipole holds the coefficients of the numerator and of the
+/// denominator of its impedance, lowest order first, as `{re, im}` objects.
+///
+/// # Example
+///
+/// ```
+/// use circuits_simulator::Dipole::Equivalent;
+/// use fractios::RatioFrac;
+/// use num::Complex;
+/// use polyx::{
+///   polynomial,
+///   Polynomial,
+/// };
+///
+/// let impedance = RatioFrac::from((
+///   polynomial![Complex::new(1e300, 0.), Complex::new(0., -2.5)],
+///   polynomial![Complex::new(1., 0.)],
+/// ));
+/// let json = serde_json::to_value(Equivalent(impedance)).unwrap();
+/// assert_eq!(
+///   json,
+///   serde_json::json!({
+///     "type": "equivalent",
+///     "value": {
+///       "numerator": [{ "re": 1e300, "im": 0. }, { "re": 0., "im": -2.5 }],
+///       "denominator": [{ "re": 1., "im": 0. }],
+///     },
+///   })
+/// );
+/// ```
 impl Serialize for Dipole
 {
   fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
   where
     S: Serializer,
   {
-    let mut state = serializer.serialize_struct("Dipole", 2)?;
+    let len = match self {
+      Dipole::Short | Dipole::Open | Dipole::Poisoned => 1,
+      Dipole::Transformer { .. } => 3,
+      Dipole::Vcvs { .. } => 4,
+      _ => 2,
+    };
+    let mut state = serializer.serialize_struct("Dipole", len)?;
     match self {
       Dipole::Resistor(r) => {
         state.serialize_field("type", "resistor")?;
@@ -82,10 +367,30 @@ impl Serialize for Dipole
         state.serialize_field("type", "inductor")?;
         state.serialize_field("value", l)?;
       },
-      Dipole::Equivalent(_e) => {
+      Dipole::Short => {
+        state.serialize_field("type", "short")?;
+      },
+      Dipole::Open => {
+        state.serialize_field("type", "open")?;
+      },
+      Dipole::Equivalent(e) => {
         state.serialize_field("type", "equivalent")?;
-        todo!("Serialize equivalent dipole");
-        // state.serialize_field("value", e)?;
+        state.serialize_field("value", &RatioFracFormat::from(e))?;
+      },
+      Dipole::Transformer { ratio, load } => {
+        state.serialize_field("type", "transformer")?;
+        state.serialize_field("ratio", ratio)?;
+        state.serialize_field("load", load)?;
+      },
+      Dipole::Vcvs {
+        control,
+        gain,
+        load,
+      } => {
+        state.serialize_field("type", "vcvs")?;
+        state.serialize_field("control", control)?;
+        state.serialize_field("gain", gain)?;
+        state.serialize_field("load", load)?;
       },
       Dipole::Poisoned => {
         state.serialize_field("type", "poisoned")?;
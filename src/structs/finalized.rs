@@ -0,0 +1,111 @@
+use super::{
+  Circuit,
+  Id,
+};
+use crate::{
+  emulation::EmulationData,
+  error::Result,
+};
+
+/// An immutable and fully initialized circuit, obtained with
+/// [`Circuit::finalize`]. Its emulation methods only borrow it immutably, so
+/// that it can be emulated from several threads at once.
+///
+/// # Example
+///
+/// ```
+/// use circuits_simulator::{
+///   id,
+///   Circuit,
+///   Component,
+///   Dipole::{
+///     Capacitor,
+///     Resistor,
+///   },
+/// };
+/// use num::Complex;
+///
+/// let mut c = Circuit::new();
+/// c.add_pulse(1., Complex::new(0., -0.5))
+///   .content_mut()
+///   .push_serie(Component::from(Resistor(200.)))
+///   .push_serie(Component::from(Capacitor(10e-9)));
+/// let c = c.finalize()?;
+///
+/// std::thread::scope(|s| {
+///   let resistor = s.spawn(|| c.emulate_one(1e-3, 1e-5, &id![0u8]));
+///   let capacitor = s.spawn(|| c.emulate_one(1e-3, 1e-5, &id![1u8]));
+///   resistor.join().unwrap()?;
+///   capacitor.join().unwrap()?;
+///   Ok::<(), circuits_simulator::Error>(())
+/// })?;
+/// # Ok::<(), circuits_simulator::Error>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct FinalizedCircuit
+{
+  circuit: Circuit,
+}
+
+impl Circuit
+{
+  /// Initializes the circuit and freezes it into a [`FinalizedCircuit`],
+  /// separating the build phase from the emulation phase.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the initialization of the circuit fails. See
+  /// [`Circuit::init`].
+  #[inline]
+  pub fn finalize(mut self) -> Result<FinalizedCircuit>
+  {
+    self.init()?;
+    Ok(FinalizedCircuit { circuit: self })
+  }
+}
+
+impl FinalizedCircuit
+{
+  /// Gives a reference to the underlying initialized circuit.
+  #[inline]
+  pub fn circuit(&self) -> &Circuit
+  {
+    &self.circuit
+  }
+
+  /// Gives back the underlying circuit, to modify it.
+  #[inline]
+  pub fn into_inner(self) -> Circuit
+  {
+    self.circuit
+  }
+
+  /// Emulates a node of the circuit. See [`Circuit::emulate_one`].
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the emulation fails.
+  #[inline]
+  pub fn emulate_one(&self, duration: f64, step: f64, node_id: &Id) -> Result<EmulationData>
+  {
+    self.circuit.emulate_initialized(duration, step, node_id)
+  }
+
+  /// Emulates several nodes of the circuit. See [`Circuit::emulate_many`].
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the emulation of a node fails.
+  pub fn emulate_many(
+    &self,
+    duration: f64,
+    step: f64,
+    node_ids: &[Id],
+  ) -> Result<Vec<EmulationData>>
+  {
+    node_ids
+      .iter()
+      .map(|node_id| self.emulate_one(duration, step, node_id))
+      .collect()
+  }
+}
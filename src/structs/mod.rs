@@ -1,11 +1,13 @@
 mod circuit;
 mod component;
 mod dipole;
+mod finalized;
 mod node;
 mod source;
 
 pub use circuit::*;
 pub use component::*;
 pub use dipole::*;
+pub use finalized::*;
 pub use node::*;
 pub use source::*;
@@ -1,20 +1,105 @@
-use std::fmt::Debug;
+use std::{
+  fmt::Debug,
+  ops::{
+    Mul,
+    MulAssign,
+  },
+};
 
+use fractios::RatioFrac;
 use num::Complex;
+#[cfg(feature = "fft")]
 use num_traits::PrimInt;
+use num_traits::Zero;
 use serde::{
   Serialize,
   Serializer,
 };
 
-use crate::fourier::fouriers;
+#[cfg(feature = "fft")]
+use crate::fourier::{
+  fouriers,
+  fouriers_complex,
+  fouriers_samples,
+};
+use crate::{
+  emulation::check_time_grid,
+  error,
+  util::SplitMix64,
+};
 
 #[derive(Clone, Debug, Default)]
 /// A source of voltage.
 pub struct Source
 {
   /// Map between pulses (sorted) and voltages.
-  pub voltages: Vec<(f64, Complex<f64>)>,
+  pub voltages:           Vec<(f64, Complex<f64>)>,
+  /// The output impedance of the source, in series with the circuit. `None`
+  /// stands for an ideal source. It is taken into account by
+  /// [`Circuit::init`](crate::Circuit::init), hence by the emulations, and by
+  /// the Thévenin and Norton equivalents, while the other analyses (poles,
+  /// zeros, transfer functions and transient modes) consider the circuit
+  /// alone.
+  pub internal_impedance: Option<RatioFrac<Complex<f64>>>,
+  /// `false` (the default) if the tension of the source is a real signal,
+  /// whose spectrum is conjugate symmetric: only its non-negative pulses are
+  /// stored, each positive one standing for itself and for its opposite.
+  /// `true` if it is a complex signal (see `Source::set_complex_fn`), whose
+  /// pulses, negative ones included, all stand for themselves only. The
+  /// emulations then give the real part of the complex signals.
+  pub complex:            bool,
+}
+
+/// Standard periodic waveforms, see [`Source::waveform`]. All of them have the
+/// given peak amplitude and start a period at `t = 0`.
+#[cfg(feature = "fft")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Waveform
+{
+  /// `amplitude * sin(2π f t)`.
+  Sine,
+  /// `amplitude` over the first half of each period and `-amplitude` over the
+  /// second half.
+  Square,
+  /// Zero at `t = 0`, rising linearly to `amplitude` at a quarter of the
+  /// period, falling to `-amplitude` at three quarters, in phase with `Sine`.
+  Triangle,
+  /// Zero at `t = 0`, rising linearly to `amplitude` at half the period, where
+  /// it jumps to `-amplitude` before rising back to zero.
+  Sawtooth,
+  /// A unipolar pulse train: `amplitude` over the first `duty` fraction of
+  /// each period (with `0 ≤ duty ≤ 1`) and zero otherwise.
+  Pulse
+  {
+    duty: f64
+  },
+}
+
+#[cfg(feature = "fft")]
+impl Waveform
+{
+  /// Evaluates the waveform of unit amplitude at the phase `x`, in periods.
+  fn eval(self, x: f64) -> f64
+  {
+    let x = x.rem_euclid(1f64);
+    match self {
+      Waveform::Sine => (2f64 * std::f64::consts::PI * x).sin(),
+      Waveform::Square =>
+        if x < 0.5 {
+          1f64
+        } else {
+          -1f64
+        },
+      Waveform::Triangle => 4f64 * ((x - 0.25).rem_euclid(1f64) - 0.5).abs() - 1f64,
+      Waveform::Sawtooth => 2f64 * (x + 0.5).rem_euclid(1f64) - 1f64,
+      Waveform::Pulse { duty } =>
+        if x < duty {
+          1f64
+        } else {
+          0f64
+        },
+    }
+  }
 }
 
 // Utility struct to enable binary search on f64
@@ -38,7 +123,11 @@ impl Source
   #[inline]
   pub fn new() -> Self
   {
-    Source { voltages: vec![] }
+    Source {
+      voltages:           vec![],
+      internal_impedance: None,
+      complex:            false,
+    }
   }
 
   /// Sets the voltage at a specific index in the `voltages` vector.
@@ -65,6 +154,42 @@ impl Source
     self
   }
 
+  /// Adds the tone `amplitude * cos(2π frequency t + phase)`, as a single
+  /// pulse at `2π frequency` whose voltage is scaled so that the synthesized
+  /// signal has the given amplitude: it is halved for a positive pulse of a
+  /// real source, which also stands for the opposite pulse. A negative
+  /// frequency of a real source is folded onto the positive one. If the pulse
+  /// is already present, its voltage is replaced.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::Source;
+  ///
+  /// let mut source = Source::new();
+  /// source.add_tone(50., 2., 0.5).add_tone(0., 1., 0.);
+  /// let series = source.to_time_series(0.1, 1e-4)?;
+  /// for (k, value) in series.iter().enumerate() {
+  ///   let t = k as f64 * 1e-4;
+  ///   let expected = 1. + 2. * (2. * std::f64::consts::PI * 50. * t + 0.5).cos();
+  ///   assert!((value - expected).abs() < 1e-12);
+  /// }
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn add_tone(&mut self, frequency: f64, amplitude: f64, phase: f64) -> &mut Self
+  {
+    let (mut pulse, mut phase) = (2f64 * std::f64::consts::PI * frequency, phase);
+    if !self.complex && pulse < 0f64 {
+      (pulse, phase) = (-pulse, -phase);
+    }
+    let mut voltage = Complex::from_polar(amplitude / self.weight(pulse), phase);
+    // The constant term of a real signal is real
+    if !self.complex && pulse.is_zero() {
+      voltage.im = 0f64;
+    }
+    self.add_pulse(pulse, voltage)
+  }
+
   /// Removes the pulse at the specified index from the `voltages` vector.
   #[inline]
   pub fn remove_pulse(&mut self, index: usize) -> &mut Self
@@ -73,20 +198,119 @@ impl Source
     self
   }
 
-  /// Clears the `voltages` vector.
+  /// Clears the `voltages` vector, the source being real again.
   #[inline]
   pub fn clear(&mut self) -> &mut Self
   {
     self.voltages.clear();
+    self.complex = false;
     self
   }
 
+  /// Creates a new `Source` from its spectrum, given as pairs of pulses and
+  /// complex voltages. As with [`Source::add_pulse`], a repeated pulse keeps
+  /// its last voltage.
+  pub fn from_spectrum<T>(spectrum: T) -> Self
+  where
+    T: IntoIterator<Item = (f64, Complex<f64>)>,
+  {
+    let mut source = Self::new();
+    for (pulse, voltage) in spectrum {
+      source.add_pulse(pulse, voltage);
+    }
+    source
+  }
+
+  /// Creates a multisine source: a sum of tones of the same amplitude at the
+  /// given pulses, with random phases drawn uniformly from a seed so that the
+  /// signal is reproducible. Each tone is `amplitude * cos(pulse * t + phase)`,
+  /// except a zero pulse which gives a constant `amplitude`.
+  ///
+  /// The crest factor (peak over RMS value) of a random-phase multisine
+  /// behaves like the one of gaussian noise, typically 3 to 4, while a single
+  /// sine has a crest factor of √2. Driving the circuit at a given peak
+  /// tension therefore gives less power per tone than
+  /// [`Source::schroeder_multisine`], whose deterministic phases keep the crest
+  /// factor close to the one of a single sine. Random phases are still useful
+  /// to average out the phase-dependent effects of several realizations.
+  ///
+  /// # Arguments
+  ///
+  /// * `pulses` - The pulses of the tones.
+  /// * `amplitude` - The amplitude of each tone.
+  /// * `seed` - The seed of the random phases.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::Source;
+  ///
+  /// let source = Source::multisine(&[1e3, 2e3, 3e3], 1., 42);
+  /// assert!(source
+  ///   .voltages()
+  ///   .all(|(_, v)| (v.norm() - 0.5).abs() < 1e-12));
+  /// ```
+  pub fn multisine(pulses: &[f64], amplitude: f64, seed: u64) -> Self
+  {
+    let mut rng = SplitMix64::new(seed);
+    Self::from_spectrum(pulses.iter().map(|&pulse| {
+      let phase = 2. * std::f64::consts::PI * rng.next_f64();
+      (pulse, tone(pulse, amplitude, phase))
+    }))
+  }
+
+  /// Creates a multisine source with Schroeder phases: the k-th of the N tones
+  /// (starting from k = 1) has the phase `φ_k = -k(k - 1)π / N`. Each tone is
+  /// `amplitude * cos(pulse * t + φ_k)`, except a zero pulse which gives a
+  /// constant `amplitude`.
+  ///
+  /// For harmonically related pulses, these phases spread the energy of the
+  /// tones over the period like a chirp and give a crest factor close to the
+  /// √2 of a single sine, much lower than the one of random phases (see
+  /// [`Source::multisine`]). The circuit can then be driven harder without
+  /// exceeding a given peak tension, which improves the signal-to-noise ratio
+  /// of a frequency response measurement.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::Source;
+  /// use num::Complex;
+  ///
+  /// let pulses = (1..=32).map(|k| k as f64).collect::<Vec<_>>();
+  /// let source = Source::schroeder_multisine(&pulses, 1.);
+  ///
+  /// // Sample one period of the signal to measure its crest factor
+  /// let samples = (0..4096)
+  ///   .map(|n| {
+  ///     let t = 2. * std::f64::consts::PI * n as f64 / 4096.;
+  ///     source
+  ///       .voltages()
+  ///       .map(|(pulse, v)| 2. * (v * Complex::new(0., pulse * t).exp()).re)
+  ///       .sum::<f64>()
+  ///   })
+  ///   .collect::<Vec<_>>();
+  /// let peak = samples.iter().fold(0f64, |acc, x| acc.max(x.abs()));
+  /// let rms = (samples.iter().map(|x| x * x).sum::<f64>() / 4096.).sqrt();
+  /// assert!(peak / rms < 2.);
+  /// ```
+  pub fn schroeder_multisine(pulses: &[f64], amplitude: f64) -> Self
+  {
+    let n = pulses.len() as f64;
+    Self::from_spectrum(pulses.iter().enumerate().map(|(k, &pulse)| {
+      let k = (k + 1) as f64;
+      let phase = -k * (k - 1.) * std::f64::consts::PI / n;
+      (pulse, tone(pulse, amplitude, phase))
+    }))
+  }
+
   /// Creates a new `Source` from a real valued function that generates voltage
   /// values using its Fourier transform. The function takes a time value as
   /// input and returns a voltage value. The `duration` parameter specifies the
   /// total duration of the voltage source (henceforth the duration of the
   /// simulation). The `n_freqs_` parameter specifies the number of frequencies
   /// to use in the Fourier series.
+  #[cfg(feature = "fft")]
   #[inline]
   pub fn from_fn<I, F>(f: F, duration: f64, n_freqs_: I) -> Self
   where
@@ -98,13 +322,229 @@ impl Source
     source
   }
 
+  /// Creates a new `Source` from a standard periodic waveform (see
+  /// [`Waveform`] for the conventions of each of them), as with
+  /// [`Source::from_fn`].
+  ///
+  /// # Arguments
+  ///
+  /// * `kind` - The waveform.
+  /// * `frequency` - The frequency of the waveform, in hertz.
+  /// * `amplitude` - The peak amplitude of the waveform, in volts.
+  /// * `duration` - The duration of the simulation, in seconds.
+  /// * `n_freqs` - The number of frequencies of the Fourier series.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `frequency` is not positive and finite, if the duty cycle of a
+  /// pulse is not between 0 and 1, or if `n_freqs` cannot be converted to
+  /// `usize`.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   Source,
+  ///   Waveform,
+  /// };
+  ///
+  /// // 1 kHz over 10 ms, with a 25 % duty cycle: the DC component is the mean
+  /// let source = Source::waveform(Waveform::Pulse { duty: 0.25 }, 1e3, 2., 10e-3, 2000);
+  /// let (pulse, dc) = source.voltages().next().unwrap();
+  /// assert_eq!(*pulse, 0.);
+  /// assert!((dc.re - 0.5).abs() < 1e-2);
+  /// ```
+  #[cfg(feature = "fft")]
+  pub fn waveform<I>(
+    kind: Waveform,
+    frequency: f64,
+    amplitude: f64,
+    duration: f64,
+    n_freqs: I,
+  ) -> Self
+  where
+    I: PrimInt + Debug,
+  {
+    assert!(
+      frequency > 0f64 && frequency.is_finite(),
+      "The frequency of a waveform must be positive and finite, got {frequency:?} Hz"
+    );
+    if let Waveform::Pulse { duty } = kind {
+      assert!(
+        (0f64..=1f64).contains(&duty),
+        "The duty cycle of a pulse must be between 0 and 1, got {duty:?}"
+      );
+    }
+    Self::from_fn(|t| amplitude * kind.eval(frequency * t), duration, n_freqs)
+  }
+
+  /// Creates a new `Source` from a waveform made of consecutive time segments,
+  /// each given by its length (in seconds) and its own function, e.g. a
+  /// silence, then a tone burst, then a ramp. Each function receives the time
+  /// elapsed since the start of its segment. The segments are concatenated
+  /// from `t = 0` and the waveform is transformed as with [`Source::from_fn`],
+  /// with a total duration equal to the sum of the lengths of the segments.
+  /// The waveform is zero outside of the segments, in particular before `t =
+  /// 0`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if a length is negative or not finite, or if `n_freqs` cannot be
+  /// converted to `usize`.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   Segment,
+  ///   Source,
+  /// };
+  ///
+  /// let segments: [Segment; 3] = [
+  ///   (5e-3, Box::new(|_| 0.)),
+  ///   (2e-3, Box::new(|t| (2e4 * t).sin())),
+  ///   (3e-3, Box::new(|t| t / 3e-3)),
+  /// ];
+  /// // Lasts 10 ms
+  /// let source = Source::from_segments(&segments, 500);
+  /// let pulses = source
+  ///   .voltages()
+  ///   .map(|(pulse, _)| *pulse)
+  ///   .collect::<Vec<_>>();
+  /// assert!((pulses[1] - std::f64::consts::PI / 10e-3).abs() < 1e-9);
+  /// ```
+  #[cfg(feature = "fft")]
+  pub fn from_segments<I>(segments: &[Segment], n_freqs: I) -> Self
+  where
+    I: PrimInt + Debug,
+  {
+    let mut starts = Vec::with_capacity(segments.len());
+    let mut duration = 0f64;
+    for (length, _) in segments.iter() {
+      assert!(
+        *length >= 0f64 && length.is_finite(),
+        "The length of a segment must be non-negative and finite, got {length:?} s"
+      );
+      starts.push(duration);
+      duration += length;
+    }
+    let waveform = |t: f64| {
+      starts
+        .iter()
+        .zip(segments.iter())
+        .find(|(start, (length, _))| **start <= t && t < **start + length)
+        .map_or(0f64, |(start, (_, f))| f(t - start))
+    };
+    Self::from_fn(waveform, duration, n_freqs)
+  }
+
+  /// Creates a new `Source` from uniform samples of a waveform over `duration`,
+  /// e.g. measured ones, the first sample being taken at `t = 0` and the last
+  /// one a step before `duration`. The samples are transformed directly with
+  /// an FFT, of any length, though lengths whose prime factors are small are
+  /// the fastest.
+  ///
+  /// The waveform is taken as periodic of period `duration`. With the
+  /// convention of [`Source::set_fn`], the pulses are the multiples of
+  /// `π / duration`, the odd ones having zero voltages, up to the Nyquist
+  /// pulse `π n / duration` of the `n` samples. The tension of the source at
+  /// the time of each sample is then that sample.
+  ///
+  /// # Panics
+  ///
+  /// Panics if there are less than 2 samples or if `duration` is not positive
+  /// and finite.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use std::f64::consts::PI;
+  ///
+  /// use circuits_simulator::Source;
+  /// use num::Complex;
+  ///
+  /// // 100 samples of a sine of amplitude 2 turning 3 times over 10 ms
+  /// let duration = 1e-2;
+  /// let samples = (0..100)
+  ///   .map(|k| 2. * (6. * PI * k as f64 / 100.).sin())
+  ///   .collect::<Vec<_>>();
+  /// let source = Source::from_samples(&samples, duration);
+  ///
+  /// assert_eq!(source.coefficients().len(), 101);
+  /// assert!((source.fundamental().unwrap() - PI / duration).abs() < 1e-9);
+  /// for (k, (pulse, voltage)) in source.voltages().enumerate() {
+  ///   assert!((pulse - k as f64 * PI / duration).abs() < 1e-9);
+  ///   let expected = if k == 6 {
+  ///     Complex::new(0., -1.)
+  ///   } else {
+  ///     Complex::from(0.)
+  ///   };
+  ///   assert!((voltage - expected).norm() < 1e-12);
+  /// }
+  ///
+  /// // Any waveform is recovered at the sampling times, whatever the number of
+  /// // samples
+  /// let samples = [0.3, -1.2, 2.5, 0.7, 0.1, -0.4, 1.9];
+  /// for n in 2..=samples.len() {
+  ///   let source = Source::from_samples(&samples[..n], duration);
+  ///   let series = source.to_time_series(duration, duration / n as f64)?;
+  ///   for (tension, sample) in series.iter().zip(&samples[..n]) {
+  ///     assert!((tension - sample).abs() < 1e-12);
+  ///   }
+  /// }
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  #[cfg(feature = "fft")]
+  pub fn from_samples(samples: &[f64], duration: f64) -> Self
+  {
+    assert!(
+      samples.len() >= 2,
+      "At least 2 samples are required, got {}",
+      samples.len()
+    );
+    assert!(
+      duration > 0f64 && duration.is_finite(),
+      "The duration of the samples must be positive and finite, got {duration:?} s"
+    );
+    let mut source = Self::new();
+    let spacing = std::f64::consts::PI / duration;
+    for (k, coef) in fouriers_samples(samples).into_iter().enumerate() {
+      if k > 0 {
+        source.add_pulse((2 * k - 1) as f64 * spacing, Complex::zero());
+      }
+      source.add_pulse((2 * k) as f64 * spacing, coef);
+    }
+    source
+  }
+
   /// Clears and updates `self` using a real valued function that generates
   /// voltage values using its Fourier transform. The function takes a time
   /// value as input and returns a voltage value. The `duration` parameter
   /// specifies the total duration of the voltage source (henceforth the
   /// duration of the simulation). The `n_freqs_` parameter specifies the number
   /// of frequencies to use in the Fourier series.
+  #[cfg(feature = "fft")]
+  #[inline]
   pub fn set_fn<I, F>(&mut self, f: F, duration: f64, n_freqs_: I) -> &mut Self
+  where
+    F: Fn(f64) -> f64,
+    I: PrimInt + Debug,
+  {
+    self.set_fn_oversampled(f, duration, n_freqs_, 1)
+  }
+
+  /// Same as [`Source::set_fn`], but the Fourier coefficients are integrated
+  /// with `oversample` times more samples of `f`. This improves the accuracy of
+  /// the coefficients of functions with sharp features at a cost linear in
+  /// `oversample`.
+  #[cfg(feature = "fft")]
+  pub fn set_fn_oversampled<I, F>(
+    &mut self,
+    f: F,
+    duration: f64,
+    n_freqs_: I,
+    oversample: usize,
+  ) -> &mut Self
   where
     F: Fn(f64) -> f64,
     I: PrimInt + Debug,
@@ -114,7 +554,7 @@ impl Source
     let n_freqs = n_freqs_
       .to_usize()
       .unwrap_or_else(|| panic!("Failed to convert {n_freqs_:?} to usize"));
-    let fourier_coefs = fouriers(f, fundamental, n_freqs - 1);
+    let fourier_coefs = fouriers(f, fundamental, n_freqs - 1, oversample);
     let twopif = fundamental * 2. * std::f64::consts::PI;
 
     let mut pulse = 0f64;
@@ -125,12 +565,634 @@ impl Source
     self
   }
 
+  /// Same as [`Source::set_fn`] for a complex valued function, whose spectrum
+  /// has no conjugate symmetry: the `n_freqs_ - 1` negative pulses are kept
+  /// along with the zero and the positive ones, and the source is marked as
+  /// complex (see [`Source::complex`]).
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::Source;
+  /// use num::Complex;
+  ///
+  /// // A phasor turning backwards at 3 rad/s, over the window [-π, π]
+  /// let mut source = Source::new();
+  /// source.set_complex_fn(|t| Complex::new(0., -3. * t).exp(), std::f64::consts::PI, 5);
+  /// assert!(source.complex);
+  /// assert_eq!(source.voltages().count(), 9);
+  /// for (pulse, voltage) in source.voltages() {
+  ///   let expected = if (pulse + 3.).abs() < 1e-9 { 1. } else { 0. };
+  ///   assert!((voltage - expected).norm() < 1e-12);
+  /// }
+  /// ```
+  #[cfg(feature = "fft")]
+  pub fn set_complex_fn<I, F>(&mut self, f: F, duration: f64, n_freqs_: I) -> &mut Self
+  where
+    F: Fn(f64) -> Complex<f64>,
+    I: PrimInt + Debug,
+  {
+    self.clear();
+    self.complex = true;
+    let fundamental = (duration + duration).recip();
+    let n_freqs = n_freqs_
+      .to_usize()
+      .unwrap_or_else(|| panic!("Failed to convert {n_freqs_:?} to usize"));
+    let fourier_coefs = fouriers_complex(f, fundamental, n_freqs - 1);
+    let twopif = fundamental * 2. * std::f64::consts::PI;
+
+    for (k, coef) in fourier_coefs.into_iter().enumerate() {
+      let pulse = (k as f64 - (n_freqs - 1) as f64) * twopif;
+      self.add_pulse(pulse, coef);
+    }
+    self
+  }
+
+  /// Same as [`Source::set_fn`], but also checks whether `f` is periodic over
+  /// the analysis window, so that the spectrum is free of leakage. The
+  /// behaviour of [`Source::set_fn`] is unchanged.
+  ///
+  /// # Returns
+  ///
+  /// A [`PeriodicityDiagnostic`] of `f` over the analysis window.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::Source;
+  ///
+  /// let mut source = Source::new();
+  /// // The window is [-1, 1], which holds exactly one period of this sine...
+  /// let diagnostic = source.set_fn_checked(|t| (std::f64::consts::PI * t).sin(), 1., 100);
+  /// assert!(!diagnostic.likely_leakage());
+  /// // ...but not of this one
+  /// let diagnostic = source.set_fn_checked(|t| t.sin(), 1., 100);
+  /// assert!(diagnostic.likely_leakage());
+  /// ```
+  #[cfg(feature = "fft")]
+  pub fn set_fn_checked<I, F>(&mut self, f: F, duration: f64, n_freqs_: I) -> PeriodicityDiagnostic
+  where
+    F: Fn(f64) -> f64,
+    I: PrimInt + Debug,
+  {
+    let n_freqs = n_freqs_
+      .to_usize()
+      .unwrap_or_else(|| panic!("Failed to convert {n_freqs_:?} to usize"));
+    let diagnostic = PeriodicityDiagnostic::new(&f, duration, 2 * n_freqs - 1);
+    self.set_fn(f, duration, n_freqs_);
+    diagnostic
+  }
+
+  /// The number of frequencies above which [`Source::required_frequencies`]
+  /// gives up.
+  #[cfg(feature = "fft")]
+  pub const MAX_REQUIRED_FREQUENCIES: usize = 1 << 16;
+
+  /// Estimates the smallest number of frequencies to pass to
+  /// [`Source::set_fn`] so that the Fourier series of `f` reaches a given
+  /// accuracy.
+  ///
+  /// The accuracy is measured in energy: by Parseval's theorem, the mean
+  /// square of `f` over the analysis window is the sum of the energies of its
+  /// harmonics, `|c_0|²` for the constant and `2|c_k|²` for the others. The
+  /// returned count `n` is the smallest one such that the harmonics beyond the
+  /// `n` first ones hold at most `rel_tol` of the total energy, i.e. the
+  /// relative mean square error of the truncated series is at most `rel_tol`.
+  ///
+  /// The coefficients are computed with growing counts, doubled at each
+  /// step, and the energies are compared on a grid twice as fine as the
+  /// returned count to leave room for the tail.
+  ///
+  /// # Arguments
+  ///
+  /// * `f` - The function generating the voltage, as in [`Source::set_fn`].
+  /// * `duration` - The duration of the simulation, in seconds.
+  /// * `rel_tol` - The fraction of the energy that may be left out.
+  ///
+  /// # Returns
+  ///
+  /// The number of frequencies, at least 2. The search stops at
+  /// [`Source::MAX_REQUIRED_FREQUENCIES`], which is returned if the spectrum
+  /// did not converge: this is the sign of a discontinuous signal or of a
+  /// window that is not a whole number of periods (see
+  /// [`Source::set_fn_checked`]), whose harmonics decay too slowly.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `duration` or `rel_tol` is not positive.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::Source;
+  ///
+  /// // The window is [-1, 1], which holds 5 periods of this sine: it is the
+  /// // fifth harmonic, hence the constant and the 5 first harmonics are needed
+  /// let sine = |t: f64| (5. * std::f64::consts::PI * t).sin();
+  /// assert_eq!(Source::required_frequencies(sine, 1., 1e-9), 6);
+  ///
+  /// // A square wave needs more harmonics as the tolerance gets tighter
+  /// let square = |t: f64| {
+  ///   if (2. * t).rem_euclid(2.) < 1. {
+  ///     1.
+  ///   } else {
+  ///     -1.
+  ///   }
+  /// };
+  /// let coarse = Source::required_frequencies(square, 1., 1e-2);
+  /// let fine = Source::required_frequencies(square, 1., 1e-3);
+  /// assert!(coarse < fine && fine < Source::MAX_REQUIRED_FREQUENCIES);
+  /// ```
+  #[cfg(feature = "fft")]
+  pub fn required_frequencies<F>(f: F, duration: f64, rel_tol: f64) -> usize
+  where
+    F: Fn(f64) -> f64,
+  {
+    assert!(duration > 0., "The duration must be positive");
+    assert!(rel_tol > 0., "The relative tolerance must be positive");
+    let fundamental = (duration + duration).recip();
+
+    let mut n_freqs = 2;
+    while n_freqs < Self::MAX_REQUIRED_FREQUENCIES {
+      let energies = fouriers(&f, fundamental, 2 * n_freqs - 1, 1)
+        .iter()
+        .enumerate()
+        .map(|(k, coef)| {
+          if k == 0 {
+            coef.norm_sqr()
+          } else {
+            2. * coef.norm_sqr()
+          }
+        })
+        .collect::<Vec<_>>();
+      let total = energies.iter().sum::<f64>();
+      // Smallest count whose tail is negligible, found from the highest
+      // harmonics down
+      let mut tail = 0f64;
+      let mut required = energies.len();
+      for (k, energy) in energies.iter().enumerate().skip(2).rev() {
+        tail += energy;
+        if tail > rel_tol * total {
+          break;
+        }
+        required = k;
+      }
+      if required <= n_freqs {
+        return required;
+      }
+      n_freqs *= 2;
+    }
+    Self::MAX_REQUIRED_FREQUENCIES
+  }
+
   /// Non-consuming iterator over the pulses and their voltages
   #[inline]
   pub fn voltages(&self) -> impl Iterator<Item = &(f64, Complex<f64>)>
   {
     self.voltages.iter()
   }
+
+  /// Gives the pulses of the source and their voltages, that is its Fourier
+  /// coefficients after `Source::set_fn`, sorted by pulse.
+  #[inline]
+  pub fn coefficients(&self) -> &[(f64, Complex<f64>)]
+  {
+    &self.voltages
+  }
+
+  /// Gives the spacing of the pulses of the source, that is the fundamental
+  /// pulse of its Fourier series after `Source::set_fn`, as the difference
+  /// between its first two pulses.
+  ///
+  /// # Returns
+  ///
+  /// The fundamental pulse in rad/s, or `None` if the source has less than two
+  /// pulses.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # #[cfg(feature = "fft")]
+  /// # {
+  /// use circuits_simulator::Source;
+  ///
+  /// // A square wave of period 2 s, the duration of the analysis window
+  /// let square = |t: f64| if t.rem_euclid(2.) < 1. { 1. } else { -1. };
+  /// let source = Source::from_fn(square, 1., 1000);
+  /// let fundamental = source.fundamental().unwrap();
+  /// assert!((fundamental - std::f64::consts::PI).abs() < 1e-12);
+  ///
+  /// // The odd harmonics, in 4 / kπ, dominate the even ones
+  /// let amplitude = |k: usize| 2. * source.coefficients()[k].1.norm();
+  /// for k in [1, 3, 5] {
+  ///   assert!((amplitude(k) - 4. / (k as f64 * std::f64::consts::PI)).abs() < 1e-2);
+  ///   assert!(amplitude(k + 1) < 1e-2 * amplitude(k));
+  /// }
+  /// # }
+  /// ```
+  #[inline]
+  pub fn fundamental(&self) -> Option<f64>
+  {
+    match self.voltages.as_slice() {
+      [(first, _), (second, _), ..] => Some(second - first),
+      _ => None,
+    }
+  }
+
+  /// Drops the negligible terms of the source, e.g. for fast previews, as
+  /// each term of the source costs one term in the inner loop of the
+  /// emulations. The energy of a term is `|voltage|²`, times its weight in the
+  /// synthesized signals (2 for a positive pulse of a real source), and the
+  /// smallest set of terms whose cumulative energy reaches `fraction` of the
+  /// total one is kept, the others being removed. The constant term, of zero
+  /// pulse, is always kept, and a `fraction` of at least 1 keeps every term.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `fraction` is NaN.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use std::f64::consts::PI;
+  ///
+  /// use circuits_simulator::Source;
+  ///
+  /// // The Fourier series of a square wave of period 2 s, whose harmonics are
+  /// // odd and in 4 / kπ
+  /// let mut source = Source::new();
+  /// source.add_tone(0., 0., 0.);
+  /// for k in 1..1000 {
+  ///   let amplitude = if k % 2 == 1 { 4. / (PI * k as f64) } else { 0. };
+  ///   source.add_tone(k as f64 / 2., amplitude, -PI / 2.);
+  /// }
+  /// let energy = |source: &Source| {
+  ///   source
+  ///     .voltages()
+  ///     .map(|(pulse, voltage)| if *pulse == 0. { 1. } else { 2. } * voltage.norm_sqr())
+  ///     .sum::<f64>()
+  /// };
+  /// let total = energy(&source);
+  ///
+  /// source.truncate_energy(0.99);
+  /// assert!(energy(&source) >= 0.99 * total);
+  /// // The constant term is kept, as well as the dominant odd harmonics only
+  /// assert_eq!(source.coefficients()[0].0, 0.);
+  /// let harmonics = source.coefficients()[1..]
+  ///   .iter()
+  ///   .map(|(pulse, _)| (pulse / PI).round() as usize)
+  ///   .collect::<Vec<_>>();
+  /// assert!(harmonics.iter().all(|k| k % 2 == 1));
+  /// assert_eq!(harmonics[..3], [1, 3, 5]);
+  /// assert!(source.coefficients().len() < 100);
+  ///
+  /// // Only the constant term remains without any energy to reach
+  /// source.truncate_energy(0.);
+  /// assert_eq!(source.coefficients().len(), 1);
+  /// ```
+  pub fn truncate_energy(&mut self, fraction: f64) -> &mut Self
+  {
+    assert!(!fraction.is_nan(), "The fraction of energy to keep is NaN");
+    if fraction >= 1f64 {
+      return self;
+    }
+    let energies = self
+      .voltages()
+      .map(|(pulse, voltage)| self.weight(*pulse) * voltage.norm_sqr())
+      .collect::<Vec<_>>();
+    let target = fraction * energies.iter().sum::<f64>();
+
+    let mut keep = vec![false; energies.len()];
+    let mut kept = 0f64;
+    for (index, (pulse, _)) in self.voltages().enumerate() {
+      if pulse.is_zero() {
+        keep[index] = true;
+        kept += energies[index];
+      }
+    }
+    let mut order = (0..energies.len()).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| energies[b].total_cmp(&energies[a]));
+    for index in order {
+      if kept >= target {
+        break;
+      }
+      if !keep[index] {
+        keep[index] = true;
+        kept += energies[index];
+      }
+    }
+
+    let mut keep = keep.into_iter();
+    self.voltages.retain(|_| keep.next().unwrap());
+    self
+  }
+
+  /// Gives the weight of the term of a pulse in the synthesized signals: a
+  /// positive pulse of a real source accounts for twice the real part of its
+  /// term, while the zero pulse and the pulses of a complex source account for
+  /// their term only.
+  #[inline]
+  pub(crate) fn weight(&self, pulse: f64) -> f64
+  {
+    if self.complex || pulse.is_zero() {
+      1f64
+    } else {
+      2f64
+    }
+  }
+
+  /// Evaluates the (open-circuit) tension of the source at a given time, that
+  /// is its real part for a complex source.
+  pub(crate) fn tension(&self, time: f64) -> f64
+  {
+    self
+      .voltages()
+      .map(|(pulse, voltage)| {
+        self.weight(*pulse) * (voltage * Complex::new(0f64, time * pulse).exp()).re
+      })
+      .sum()
+  }
+
+  /// Samples the (open-circuit) tension of the source on the time grid of the
+  /// emulations, from 0 to `duration` with a step of `step`.
+  ///
+  /// # Errors
+  ///
+  /// Returns a `CircuitSolve` error if `step` is not positive and finite, if
+  /// `duration` is negative or infinite, or if `step` exceeds `duration`.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::Source;
+  /// use num::Complex;
+  ///
+  /// let mut source = Source::new();
+  /// source.add_pulse(0., Complex::from(1.));
+  /// assert_eq!(source.to_time_series(1., 0.25)?, [1.; 4]);
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn to_time_series(&self, duration: f64, step: f64) -> error::Result<Vec<f64>>
+  {
+    check_time_grid(duration, step)?;
+    Ok(
+      (0..(duration / step).ceil() as usize)
+        .map(|k| self.tension(k as f64 * step))
+        .collect(),
+    )
+  }
+
+  /// Reconstructs the (open-circuit) tension of the source at `n` evenly
+  /// spaced times from 0 (included) to `duration` (excluded), superposing its
+  /// terms as the emulations do, e.g. to plot the stimulus of a circuit against
+  /// its response. See [`Source::to_time_series`] to give the step instead.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if `duration` is not positive and finite while `n` is
+  /// not zero.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use std::f64::consts::PI;
+  ///
+  /// use circuits_simulator::Source;
+  ///
+  /// // A square wave of period 4 ms, of amplitude 1, up to its 125th harmonic
+  /// let square_wave = |t: f64| if t.rem_euclid(4e-3) < 2e-3 { 1. } else { -1. };
+  /// let mut source = Source::new();
+  /// for k in (1..=125).step_by(2) {
+  ///   source.add_tone(250. * k as f64, 4. / (PI * k as f64), -PI / 2.);
+  /// }
+  ///
+  /// let n = 800;
+  /// let samples = source.waveform_samples(8e-3, n)?;
+  /// assert_eq!(samples.len(), n);
+  /// for (k, sample) in samples.into_iter().enumerate() {
+  ///   let t = k as f64 * 8e-3 / n as f64;
+  ///   // The Gibbs phenomenon is confined near the edges
+  ///   let phase = t.rem_euclid(2e-3);
+  ///   if phase.min(2e-3 - phase) > 4e-4 {
+  ///     assert!((sample - square_wave(t)).abs() < 2e-2);
+  ///   }
+  /// }
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn waveform_samples(&self, duration: f64, n: usize) -> error::Result<Vec<f64>>
+  {
+    if n == 0 {
+      return Ok(Vec::new());
+    }
+    let step = duration / n as f64;
+    check_time_grid(duration, step)?;
+    Ok((0..n).map(|k| self.tension(k as f64 * step)).collect())
+  }
+
+  /// Compares the source with the function it approximates, on the time grid
+  /// of [`Source::to_time_series`]. This measures the error due to the
+  /// truncation of the Fourier series, including the Gibbs ringing near the
+  /// discontinuities, for the chosen number of frequencies (see
+  /// `Source::required_frequencies` to choose it).
+  ///
+  /// # Arguments
+  ///
+  /// * `f` - The function approximated by the source, e.g. the one given to
+  ///   `Source::set_fn`.
+  /// * `duration` - The duration of the comparison, in seconds.
+  /// * `step` - The time step of the comparison, in seconds.
+  ///
+  /// # Returns
+  ///
+  /// The largest absolute error and the root mean square error, in volts.
+  ///
+  /// # Errors
+  ///
+  /// Returns a `CircuitSolve` error if the time grid is invalid (see
+  /// [`Source::to_time_series`]).
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # #[cfg(feature = "fft")]
+  /// # {
+  /// use circuits_simulator::Source;
+  ///
+  /// // A smooth periodic function is reproduced accurately...
+  /// let sine = |t: f64| (5. * std::f64::consts::PI * t).sin();
+  /// let source = Source::from_fn(sine, 1., 10);
+  /// let (max_error, _) = source.approximation_error(sine, 1., 1e-3)?;
+  /// assert!(max_error < 1e-9);
+  ///
+  /// // ...unlike a square wave, whose ringing overshoots near the steps
+  /// let square = |t: f64| {
+  ///   if (2. * t).rem_euclid(2.) < 1. {
+  ///     1.
+  ///   } else {
+  ///     -1.
+  ///   }
+  /// };
+  /// let source = Source::from_fn(square, 1., 100);
+  /// let (max_error, rms_error) = source.approximation_error(square, 1., 1e-3)?;
+  /// assert!(max_error > 0.5 && rms_error < 0.2);
+  /// # }
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn approximation_error<F>(&self, f: F, duration: f64, step: f64) -> error::Result<(f64, f64)>
+  where
+    F: Fn(f64) -> f64,
+  {
+    let series = self.to_time_series(duration, step)?;
+    let errors = series
+      .iter()
+      .enumerate()
+      .map(|(k, tension)| (tension - f(k as f64 * step)).abs());
+    let (max_error, square_sum) = errors.fold((0f64, 0f64), |(max_error, square_sum), error| {
+      (max_error.max(error), square_sum + error * error)
+    });
+    Ok((max_error, (square_sum / series.len() as f64).sqrt()))
+  }
+}
+
+/// A time segment of a waveform: its length in seconds and the function giving
+/// the waveform over it. See [`Source::from_segments`].
+#[cfg(feature = "fft")]
+pub type Segment = (f64, Box<dyn Fn(f64) -> f64>);
+
+/// Scales every voltage of the source by a complex gain, e.g. to model a gain
+/// stage or an attenuator ahead of the circuit. The pulses are left unchanged.
+impl MulAssign<Complex<f64>> for Source
+{
+  #[inline]
+  fn mul_assign(&mut self, gain: Complex<f64>)
+  {
+    for (_, voltage) in self.voltages.iter_mut() {
+      *voltage *= gain;
+    }
+  }
+}
+
+/// Scales every voltage of the source by a real gain.
+impl MulAssign<f64> for Source
+{
+  #[inline]
+  fn mul_assign(&mut self, gain: f64)
+  {
+    *self *= Complex::from(gain);
+  }
+}
+
+impl Mul<Complex<f64>> for Source
+{
+  type Output = Source;
+
+  #[inline]
+  fn mul(mut self, gain: Complex<f64>) -> Source
+  {
+    self *= gain;
+    self
+  }
+}
+
+impl Mul<f64> for Source
+{
+  type Output = Source;
+
+  #[inline]
+  fn mul(mut self, gain: f64) -> Source
+  {
+    self *= gain;
+    self
+  }
+}
+
+/// Complex voltage of the tone `amplitude * cos(pulse * t + phase)`, given that
+/// only positive pulses are stored and a zero pulse is a constant.
+#[inline]
+fn tone(pulse: f64, amplitude: f64, phase: f64) -> Complex<f64>
+{
+  if pulse == 0f64 {
+    Complex::from(amplitude)
+  } else {
+    Complex::from_polar(amplitude / 2., phase)
+  }
+}
+
+/// Diagnostic of the periodicity of a function over the analysis window
+/// `[-duration, duration]` of [`Source::set_fn`], returned by
+/// [`Source::set_fn_checked`].
+///
+/// The Fourier series computed by [`Source::set_fn`] describes the periodic
+/// extension of the function beyond this window. If the function and its first
+/// derivatives do not match at both edges of the window, this extension is
+/// discontinuous and its spectrum decays slowly: the energy leaks to all the
+/// harmonics and the emulation shows Gibbs ringing near the edges.
+///
+/// The jumps of the derivatives are estimated with one-sided finite
+/// differences and multiplied by powers of the sampling step of
+/// [`Source::set_fn`], so that they are expressed in units of the function and
+/// comparable to its amplitude.
+#[cfg(feature = "fft")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PeriodicityDiagnostic
+{
+  /// The largest absolute value of the function over the samples of the
+  /// window.
+  pub amplitude:      f64,
+  /// The jump of the value of the periodic extension at the edge of the
+  /// window.
+  pub value_jump:     f64,
+  /// The jump of the first derivative, times the sampling step.
+  pub slope_jump:     f64,
+  /// The jump of the second derivative, times the squared sampling step.
+  pub curvature_jump: f64,
+}
+
+#[cfg(feature = "fft")]
+impl PeriodicityDiagnostic
+{
+  /// The relative jump, compared to the amplitude, above which the window is
+  /// considered to cause leakage.
+  pub const TOLERANCE: f64 = 1e-3;
+
+  /// Computes the diagnostic of `f` over `[-duration, duration]`, sampled at
+  /// `nb_samples` points.
+  fn new<F: Fn(f64) -> f64>(f: &F, duration: f64, nb_samples: usize) -> Self
+  {
+    let h = 2f64 * duration / nb_samples as f64;
+    let amplitude = (0..nb_samples)
+      .map(|k| f(h * (k as f64 + 0.5) - duration).abs())
+      .fold(0f64, f64::max);
+    // Values at the end of the window going backwards and at its start going
+    // forwards, which should match for a periodic function. The differences are
+    // taken on a finer stencil than the sampling step so that the truncation
+    // errors of the one-sided schemes remain negligible even for harmonics close
+    // to the Nyquist frequency
+    let delta = h / 64.;
+    let end = [0., 1., 2., 3.].map(|k| f(duration - k * delta));
+    let start = [0., 1., 2., 3.].map(|k| f(k * delta - duration));
+    let slope = |v: [f64; 4]| (3. * v[0] - 4. * v[1] + v[2]) / (2. * delta) * h;
+    let curvature =
+      |v: [f64; 4]| (2. * v[0] - 5. * v[1] + 4. * v[2] - v[3]) / (delta * delta) * h * h;
+    Self {
+      amplitude,
+      value_jump: (end[0] - start[0]).abs(),
+      // The backward slope at the end is the opposite of a forward slope
+      slope_jump: (slope(end) + slope(start)).abs(),
+      curvature_jump: (curvature(end) - curvature(start)).abs(),
+    }
+  }
+
+  /// Returns `true` if one of the jumps is larger than
+  /// [`PeriodicityDiagnostic::TOLERANCE`] times the amplitude, i.e. if the
+  /// spectrum likely suffers from leakage.
+  #[inline]
+  pub fn likely_leakage(&self) -> bool
+  {
+    [self.value_jump, self.slope_jump, self.curvature_jump]
+      .iter()
+      .any(|jump| *jump > Self::TOLERANCE * self.amplitude)
+  }
 }
 
 #[derive(Serialize)]
@@ -0,0 +1,205 @@
+use crate::{
+  error::{
+    Error::CircuitBuild,
+    Result,
+  },
+  Circuit,
+  ComponentContent,
+  Id,
+  Source,
+};
+
+impl Circuit
+{
+  /// Adds an ideal voltage source in series with the main one, inserted before
+  /// the fore node of a component: either the root (`id![]`) or one of the
+  /// components of the series combination at the root, so that the source
+  /// belongs to the loop of the main source. Its positive terminal faces the
+  /// component, i.e. it raises the potentials of the nodes from the component
+  /// onwards.
+  ///
+  /// The circuit is solved by superposition: the circuit driven by each added
+  /// source alone, the main one being replaced by its internal impedance, is
+  /// solved on the pulses of this source, and the signals given by
+  /// [`Circuit::emulate_one`] and the functions built on it (such as
+  /// [`Circuit::emulate_many`] or [`Circuit::power`]) are the sums of the
+  /// signals of these circuits and of the circuit driven by the main source
+  /// alone, as are those of [`Circuit::emulate_at`],
+  /// [`Circuit::average_power`], [`Circuit::thd`] and
+  /// [`Circuit::transient_modes`]. The spectra of the nodes of the circuit,
+  /// and the other analyses based on them, only account for the main source,
+  /// and [`Circuit::transient`] rejects the added sources.
+  ///
+  /// # Errors
+  ///
+  /// Returns a `CircuitBuild` error if the component is not in the loop of the
+  /// main source, if the source has an internal impedance, or if another
+  /// source is already added before the same node (the root and its first
+  /// series component sharing their fore node).
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  ///   Source,
+  /// };
+  ///
+  /// // A second source between the resistor and the capacitor...
+  /// let mut extra = Source::new();
+  /// extra.add_tone(150., 0.5, 0.3);
+  /// let mut c = Circuit::new();
+  /// c.add_tone(50., 1., 0.)
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(100.)))
+  ///   .push_serie(Component::from(Capacitor(10e-6)));
+  /// c.add_source(&id![1], extra)?;
+  ///
+  /// // ...is equivalent to a single source made of both spectra
+  /// let mut equivalent = Circuit::new();
+  /// equivalent
+  ///   .add_tone(50., 1., 0.)
+  ///   .add_tone(150., 0.5, 0.3)
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(100.)))
+  ///   .push_serie(Component::from(Capacitor(10e-6)));
+  ///
+  /// let (duration, step) = (2e-2, 1e-5);
+  /// let close = |a: &[f64], b: &[f64]| a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-9);
+  /// for k in 0..2 {
+  ///   let (currents, tensions, _) = c.emulate_one(duration, step, &id![k])?;
+  ///   let (expected_currents, expected_tensions, _) =
+  ///     equivalent.emulate_one(duration, step, &id![k])?;
+  ///   assert!(close(&currents, &expected_currents));
+  ///   assert!(close(&tensions, &expected_tensions));
+  /// }
+  /// // Only the potentials of the nodes before the added source differ
+  /// let (_, _, potentials) = c.emulate_one(duration, step, &id![1])?;
+  /// assert!(close(
+  ///   &potentials,
+  ///   &equivalent.emulate_one(duration, step, &id![1])?.2
+  /// ));
+  /// let (_, _, potentials) = c.emulate_one(duration, step, &id![0])?;
+  /// assert!(close(
+  ///   &potentials,
+  ///   &c.generator().to_time_series(duration, step)?
+  /// ));
+  ///
+  /// // Sampling at arbitrary times, the average power and the transients
+  /// // superpose both sources as well, unlike the time-domain integration
+  /// let times = [0., 1.234e-3, 7.5e-3];
+  /// let (_, tensions, _) = c.emulate_at(&times, &id![1])?;
+  /// assert!(close(&tensions, &equivalent.emulate_at(&times, &id![1])?.1));
+  /// let power = c.average_power(&id![0])?;
+  /// assert!((power - equivalent.average_power(&id![0])?).abs() < 1e-12);
+  /// let initial_conditions = std::collections::HashMap::from([(id![1], 0.2)]);
+  /// let (_, tensions, _) =
+  ///   c.emulate_one_with_initial_conditions(duration, step, &id![1], &initial_conditions)?;
+  /// assert!((tensions[0] - 0.2).abs() < 1e-9);
+  /// assert!(c
+  ///   .transient(duration, step, &[id![1]], &initial_conditions)
+  ///   .is_err());
+  ///
+  /// // Both sources cannot be inserted at the same place
+  /// assert!(c.add_source(&id![1], Source::new()).is_err());
+  /// assert!(c.add_source(&id![0, 1], Source::new()).is_err());
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn add_source(&mut self, id: &Id, source: Source) -> Result<&mut Self>
+  {
+    let position = self.source_position(id)?;
+    if source.internal_impedance.is_some() {
+      return Err(CircuitBuild(format!(
+        "The source added before the component of id {id:?} must be ideal, without internal \
+         impedance"
+      )));
+    }
+    for (other, _) in self.sources.iter() {
+      if self.source_position(other)? == position {
+        return Err(CircuitBuild(format!(
+          "A source is already added before the component of id {id:?}, at {other:?}: add \
+           their spectra instead"
+        )));
+      }
+    }
+    self.sources.push((id.clone(), source));
+    Ok(self.uninit_source())
+  }
+
+  /// Gives the sources added with [`Circuit::add_source`], by the ID of the
+  /// component before which they are inserted.
+  #[inline]
+  pub fn sources(&self) -> &[(Id, Source)]
+  {
+    &self.sources
+  }
+
+  /// Removes the sources added with [`Circuit::add_source`], the main source
+  /// being kept.
+  #[inline]
+  pub fn clear_sources(&mut self) -> &mut Self
+  {
+    self.sources.clear();
+    self.uninit_source()
+  }
+
+  /// Gives the index, in the series combination at the root, of the
+  /// component before which a source is inserted, 0 standing for the root.
+  fn source_position(&self, id: &Id) -> Result<usize>
+  {
+    match (id.as_slice(), &self.content().content) {
+      ([], _) => Ok(0),
+      ([index], ComponentContent::Series(components)) if (*index as usize) < components.len() =>
+        Ok(*index as usize),
+      _ => Err(CircuitBuild(format!(
+        "A source can only be added before the root or one of the components of the series \
+         combination at the root, got the id {id:?}"
+      ))),
+    }
+  }
+
+  /// Solves the circuits driven by each added source alone, see
+  /// [`Circuit::add_source`].
+  pub(crate) fn init_superposed(&mut self) -> Result<()>
+  {
+    let mut superposed = Vec::with_capacity(self.sources.len());
+    for (id, source) in self.sources.iter() {
+      let position = self.source_position(id)?;
+      let mut circuit = self.clone();
+      circuit.sources.clear();
+      let mut source = source.clone();
+      source.internal_impedance = self.generator().internal_impedance.clone();
+      circuit.replace_source(source).init()?;
+
+      // The added source is solved as if it were the main one, so that the
+      // nodes before it, the root included, are actually below by its tension
+      let voltages = circuit
+        .voltages()
+        .map(|(_, voltage)| *voltage)
+        .collect::<Vec<_>>();
+      let before = circuit
+        .nodes()
+        .map(|node| node.id.clone())
+        .filter(|id| match id.first() {
+          None => position > 0,
+          Some(index) => (*index as usize) < position,
+        })
+        .collect::<Vec<_>>();
+      for id in before {
+        let node = circuit.get_node_mut(&id).unwrap();
+        for (potential, voltage) in node.potentials.iter_mut().zip(voltages.iter()) {
+          *potential -= voltage;
+        }
+      }
+      superposed.push(circuit);
+    }
+    self.superposed = superposed;
+    Ok(())
+  }
+}
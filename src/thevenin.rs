@@ -0,0 +1,258 @@
+use fractios::RatioFrac;
+use num::Complex;
+use num_traits::Zero;
+
+use crate::{
+  error::{
+    Error::CircuitSolve,
+    Result,
+  },
+  util::solve_linear,
+  Circuit,
+  Component,
+  ComponentContent,
+  Id,
+};
+
+/// The electrical network of a circuit at a given pulse, with its source
+/// turned off. Node 0 is the ground, that is the negative terminal of the
+/// source.
+#[derive(Debug, Default)]
+struct Network
+{
+  /// Union-find forest of the nodes, merging the nodes linked by a wire.
+  parents:     Vec<usize>,
+  /// Admittances between pairs of nodes.
+  admittances: Vec<(usize, usize, Complex<f64>)>,
+}
+
+impl Network
+{
+  fn add_node(&mut self) -> usize
+  {
+    self.parents.push(self.parents.len());
+    self.parents.len() - 1
+  }
+
+  fn find(&mut self, node: usize) -> usize
+  {
+    let mut root = node;
+    while self.parents[root] != root {
+      root = self.parents[root];
+    }
+    self.parents[node] = root;
+    root
+  }
+
+  /// Merges two nodes, keeping the ground as a representative.
+  fn merge(&mut self, node1: usize, node2: usize)
+  {
+    let (root1, root2) = (self.find(node1), self.find(node2));
+    self.parents[root1.max(root2)] = root1.min(root2);
+  }
+
+  /// Adds the dipoles of a component between two nodes and returns the node of
+  /// `port`, if it is the fore node of one of its subcomponents.
+  fn add_component(
+    &mut self,
+    component: &Component,
+    fore: usize,
+    aft: usize,
+    pulse: f64,
+    port: &Id,
+  ) -> Option<usize>
+  {
+    let mut port_node = (&component.fore_node_id == port).then_some(fore);
+    use ComponentContent::*;
+    match &component.content {
+      Series(components) => {
+        let mut node = fore;
+        for (k, component) in components.iter().enumerate() {
+          let next = if k + 1 == components.len() {
+            aft
+          } else {
+            self.add_node()
+          };
+          port_node = port_node.or(self.add_component(component, node, next, pulse, port));
+          node = next;
+        }
+        if components.is_empty() {
+          self.merge(fore, aft);
+        }
+      },
+      Parallel(components) =>
+        for component in components.iter() {
+          port_node = port_node.or(self.add_component(component, fore, aft, pulse, port));
+        },
+      _ => self.add_dipole(&component.impedance, fore, aft, pulse),
+    }
+    port_node
+  }
+
+  /// Adds a dipole of a given impedance between two nodes.
+  fn add_dipole(&mut self, impedance: &RatioFrac<Complex<f64>>, fore: usize, aft: usize, pulse: f64)
+  {
+    let admittance = impedance.eval_inv(Complex::from(pulse));
+    if !admittance.is_finite() {
+      self.merge(fore, aft);
+    } else if !admittance.is_zero() {
+      self.admittances.push((fore, aft, admittance));
+    }
+  }
+
+  /// Computes the impedance between a node and the ground with nodal analysis.
+  fn impedance(&mut self, port: usize) -> Option<Complex<f64>>
+  {
+    let port = self.find(port);
+    if port == 0 {
+      return Some(Complex::zero());
+    }
+    // Index of each remaining node in the linear system, the ground excluded
+    let mut indices = vec![None; self.parents.len()];
+    let mut size = 0;
+    for (node, index) in indices.iter_mut().enumerate().skip(1) {
+      if self.parents[node] == node {
+        *index = Some(size);
+        size += 1;
+      }
+    }
+    let mut matrix = vec![vec![Complex::zero(); size]; size];
+    for k in 0..self.admittances.len() {
+      let (node1, node2, admittance) = self.admittances[k];
+      let (index1, index2) = (indices[self.find(node1)], indices[self.find(node2)]);
+      if let Some(i) = index1 {
+        matrix[i][i] += admittance;
+      }
+      if let Some(j) = index2 {
+        matrix[j][j] += admittance;
+      }
+      if let (Some(i), Some(j)) = (index1, index2) {
+        matrix[i][j] -= admittance;
+        matrix[j][i] -= admittance;
+      }
+    }
+    // Potentials of the nodes when a unit current is injected into the port
+    let mut rhs = vec![Complex::zero(); size];
+    rhs[indices[port]?] = Complex::from(1f64);
+    solve_linear(matrix, rhs).map(|potentials| potentials[indices[port].unwrap()])
+  }
+}
+
+impl Circuit
+{
+  /// Computes the impedance seen between a node and the ground at a given
+  /// pulse, the source being turned off (i.e. replaced by a wire, or by its
+  /// internal impedance).
+  fn port_impedance(&mut self, id: &Id, pulse: f64) -> Result<Complex<f64>>
+  {
+    self.init_impedance()?;
+    let mut network = Network::default();
+    let ground = network.add_node();
+    // The source being turned off, it is a wire between both terminals of the
+    // circuit or its internal impedance, if any
+    let fore = match &self.generator().internal_impedance {
+      Some(internal_impedance) => {
+        let fore = network.add_node();
+        network.add_dipole(internal_impedance, fore, ground, pulse);
+        fore
+      },
+      None => ground,
+    };
+    let port = network
+      .add_component(self.content(), fore, ground, pulse, id)
+      .ok_or_else(|| CircuitSolve(format!("Node of id {id:?} not found")))?;
+    network.impedance(port).ok_or_else(|| {
+      CircuitSolve(format!(
+        "The impedance at the node of id {id:?} is infinite or undetermined at the pulse \
+         {pulse:?} rad/s"
+      ))
+    })
+  }
+
+  /// Computes the Thévenin equivalent of the circuit at a port made of a node
+  /// and the ground, at a given pulse. The ground, which is the reference
+  /// terminal of the port, is the negative terminal of the source, that is the
+  /// aft node of the main component.
+  ///
+  /// The open-circuit voltage is the potential of the node in the solution of
+  /// the circuit, and is zero if the source has no component at `pulse`. The
+  /// Thévenin impedance is the impedance between the node and the ground when
+  /// the source is turned off, i.e. replaced by its internal impedance.
+  ///
+  /// # Arguments
+  ///
+  /// * `id` - The ID of the node of the port.
+  /// * `pulse` - The pulse at which the equivalent is computed.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the initialization of the circuit fails, if the node
+  /// does not exist or if the impedance at the port is infinite or
+  /// undetermined.
+  ///
+  /// # Returns
+  ///
+  /// Returns `(open_circuit_voltage, thevenin_impedance)`.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::Resistor,
+  /// };
+  /// use num::Complex;
+  ///
+  /// // A voltage divider seen from its middle node
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(0., Complex::from(10.))
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(100.)))
+  ///   .push_serie(Component::from(Resistor(300.)));
+  ///
+  /// let (voltage, impedance) = c.thevenin_equivalent(&id![1u8], 0.)?;
+  /// assert!((voltage - Complex::from(7.5)).norm() < 1e-9);
+  /// assert!((impedance - Complex::from(75.)).norm() < 1e-9);
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn thevenin_equivalent(&mut self, id: &Id, pulse: f64)
+    -> Result<(Complex<f64>, Complex<f64>)>
+  {
+    self.init()?;
+    let index = self.voltages().position(|(p, _)| *p == pulse);
+    let voltage = match (index, self.get_node(id)) {
+      (Some(index), Some(node)) => node.potentials[index],
+      (None, Some(_)) => Complex::zero(),
+      (_, None) => return Err(CircuitSolve(format!("Node of id {id:?} not found"))),
+    };
+    Ok((voltage, self.port_impedance(id, pulse)?))
+  }
+
+  /// Computes the Norton equivalent of the circuit at a port made of a node and
+  /// the ground, at a given pulse. See [`Circuit::thevenin_equivalent`] for the
+  /// definition of the port.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error in the same cases as [`Circuit::thevenin_equivalent`],
+  /// or if the impedance at the port is zero, case in which the short-circuit
+  /// current is infinite.
+  ///
+  /// # Returns
+  ///
+  /// Returns `(short_circuit_current, norton_impedance)`, the latter being
+  /// the Thévenin impedance.
+  pub fn norton_equivalent(&mut self, id: &Id, pulse: f64) -> Result<(Complex<f64>, Complex<f64>)>
+  {
+    let (voltage, impedance) = self.thevenin_equivalent(id, pulse)?;
+    if impedance.is_zero() {
+      return Err(CircuitSolve(format!(
+        "The impedance at the node of id {id:?} is zero at the pulse {pulse:?} rad/s: its \
+         short-circuit current is infinite"
+      )));
+    }
+    Ok((voltage / impedance, impedance))
+  }
+}
@@ -0,0 +1,948 @@
+use std::collections::HashMap;
+
+use fractios::RatioFrac;
+use num::Complex;
+use num_traits::Zero;
+
+use crate::{
+  error::{
+    Error::CircuitSolve,
+    Result,
+  },
+  precision::{
+    DefaultPrecision,
+    Precision,
+  },
+  util::coefficients,
+  Circuit,
+  ComponentContent,
+  Id,
+  Source,
+};
+
+/// Relative magnitude under which the real or imaginary part of a coefficient
+/// is not displayed.
+const DISPLAY_TOLERANCE: f64 = 1e-9;
+
+/// Relative tolerance under which a pulse is considered a multiple of the
+/// fundamental pulse.
+const HARMONIC_TOLERANCE: f64 = 1e-6;
+
+/// Returns `true` if a pulse is an integer multiple `k * fundamental_pulse`
+/// with `k ≥ 2`, up to a relative tolerance of `HARMONIC_TOLERANCE`.
+fn is_harmonic(pulse: f64, fundamental_pulse: f64) -> bool
+{
+  let rank = (pulse / fundamental_pulse).round();
+  rank >= 2f64 && (pulse - rank * fundamental_pulse).abs() <= HARMONIC_TOLERANCE * pulse
+}
+
+/// Formats a real number for LaTeX, using a power of ten for very small or very
+/// large magnitudes.
+fn latex_number(x: f64) -> String
+{
+  let magnitude = x.abs();
+  if magnitude == 0f64 || (1e-3..1e4).contains(&magnitude) {
+    let s = format!("{x:.6}");
+    let s = s.trim_end_matches('0').trim_end_matches('.');
+    return s.to_string();
+  }
+  let s = format!("{x:.6e}");
+  let (mantissa, exponent) = s.split_once('e').unwrap();
+  let mantissa = mantissa.trim_end_matches('0').trim_end_matches('.');
+  match mantissa {
+    "1" => format!("10^{{{exponent}}}"),
+    "-1" => format!("-10^{{{exponent}}}"),
+    _ => format!("{mantissa} \\cdot 10^{{{exponent}}}"),
+  }
+}
+
+/// Formats a polynomial of the Laplace variable s given by its coefficients
+/// (lowest order first) for LaTeX.
+fn latex_polynomial(coefs: &[Complex<f64>]) -> String
+{
+  let mut result = String::new();
+  for (power, coef) in coefs.iter().enumerate().rev() {
+    if coef.is_zero() {
+      continue;
+    }
+    let re = if coef.re.abs() > DISPLAY_TOLERANCE * coef.norm() {
+      coef.re
+    } else {
+      0f64
+    };
+    let im = if coef.im.abs() > DISPLAY_TOLERANCE * coef.norm() {
+      coef.im
+    } else {
+      0f64
+    };
+    let variable = match power {
+      0 => String::new(),
+      1 => "s".to_string(),
+      _ => format!("s^{{{power}}}"),
+    };
+    // The sign is put apart so that terms can be joined with " + " or " - "
+    let (negative, value) = if im.is_zero() {
+      let value = if re.abs() == 1f64 && power > 0 {
+        String::new()
+      } else {
+        latex_number(re.abs())
+      };
+      (re < 0f64, value)
+    } else if re.is_zero() {
+      let value = if im.abs() == 1f64 {
+        "i".to_string()
+      } else {
+        format!("{}i", latex_number(im.abs()))
+      };
+      (im < 0f64, value)
+    } else {
+      let sign = if im < 0f64 { '-' } else { '+' };
+      (
+        false,
+        format!("({} {sign} {}i)", latex_number(re), latex_number(im.abs())),
+      )
+    };
+    let term = match (value.is_empty(), variable.is_empty()) {
+      (true, _) => variable,
+      (false, true) => value,
+      (false, false) => format!("{value} {variable}"),
+    };
+    if result.is_empty() {
+      if negative {
+        result.push('-');
+      }
+    } else {
+      result.push_str(if negative { " - " } else { " + " });
+    }
+    result.push_str(&term);
+  }
+  if result.is_empty() {
+    result.push('0');
+  }
+  result
+}
+
+/// Changes the variable of a polynomial from the pulse ω to the Laplace
+/// variable s = iω, i.e. multiplies the coefficient of ω^k by (-i)^k.
+pub(crate) fn pulse_to_laplace(coefs: &[Complex<f64>]) -> Vec<Complex<f64>>
+{
+  let mut factor = Complex::from(1f64);
+  coefs
+    .iter()
+    .map(|coef| {
+      let result = coef * factor;
+      factor *= -Complex::i();
+      result
+    })
+    .collect()
+}
+
+/// Magnitudes and phases of a frequency response, as returned by
+/// [`Circuit::frequency_response`], for Bode plots.
+pub trait Bode
+{
+  /// Returns the magnitudes of the gains, in decibels.
+  fn magnitudes_db(&self) -> Vec<f64>;
+
+  /// Returns the phases of the gains, in degrees between -180 and 180.
+  fn phases_deg(&self) -> Vec<f64>;
+}
+
+impl Bode for [(f64, Complex<f64>)]
+{
+  fn magnitudes_db(&self) -> Vec<f64>
+  {
+    self
+      .iter()
+      .map(|(_, gain)| 20f64 * gain.norm().log10())
+      .collect()
+  }
+
+  fn phases_deg(&self) -> Vec<f64>
+  {
+    self
+      .iter()
+      .map(|(_, gain)| gain.arg().to_degrees())
+      .collect()
+  }
+}
+
+/// A signal of a node whose spectrum is analysed, see [`Circuit::thd`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Quantity
+{
+  /// The current flowing through the component following the node.
+  Current,
+  /// The tension across the component following the node.
+  Tension,
+}
+
+impl Circuit
+{
+  /// Computes the ratio between the potential of a node and the tension of
+  /// the source, as a rational fraction of the pulse.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the impedance cannot be computed or if the node does
+  /// not exist.
+  pub(crate) fn potential_transfer(&mut self, id: &Id) -> Result<RatioFrac<Complex<f64>>>
+  {
+    self.init_impedance()?;
+    let not_found = || CircuitSolve(format!("Node of id {id:?} not found"));
+    let mut component = self.content();
+    // Ratios of the potential of the fore node of `component` and of its tension
+    // to the tension of the source
+    let mut potential = RatioFrac::from(Complex::from(1f64));
+    let mut tension = RatioFrac::from(Complex::from(1f64));
+    for &index in id.iter() {
+      let index = index as usize;
+      match &component.content {
+        ComponentContent::Series(components) if index < components.len() => {
+          let mut admittance = component.impedance.clone();
+          admittance.inv_inplace();
+          let mut previous_impedances = RatioFrac::default();
+          for previous in components[..index].iter() {
+            previous_impedances += &previous.impedance;
+          }
+          previous_impedances *= &admittance;
+          previous_impedances *= &tension;
+          previous_impedances *= &RatioFrac::from(Complex::from(-1f64));
+          potential += &previous_impedances;
+          potential.reduce();
+          tension *= &components[index].impedance;
+          tension *= &admittance;
+          tension.reduce();
+          component = &components[index];
+        },
+        ComponentContent::Parallel(components) if index < components.len() =>
+          component = &components[index],
+        _ => return Err(not_found()),
+      }
+    }
+    Ok(potential)
+  }
+
+  /// Computes the ratio between the potential of a node and the open-circuit
+  /// tension of the source, accounting for its internal impedance, as a
+  /// rational fraction of the pulse.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the impedance cannot be computed or if the node does
+  /// not exist.
+  pub(crate) fn source_transfer(&mut self, id: &Id) -> Result<RatioFrac<Complex<f64>>>
+  {
+    let mut transfer = self.potential_transfer(id)?;
+    if let Some(internal_impedance) = self.generator().internal_impedance.clone() {
+      // Part of the tension of the source that reaches the circuit
+      let mut total_impedance = self.impedance().clone();
+      total_impedance += &internal_impedance;
+      total_impedance.inv_inplace();
+      transfer *= self.impedance();
+      transfer *= &total_impedance;
+      transfer.reduce();
+    }
+    Ok(transfer)
+  }
+
+  /// Computes the transfer function between the potentials of two nodes, that
+  /// is the ratio of the potential of `output` to that of `input`, as a
+  /// reduced rational fraction of the pulse. It is derived from the impedances
+  /// of the components, without any emulation.
+  ///
+  /// # Arguments
+  ///
+  /// * `input` - The ID of the input node. `&vec![]` stands for the source.
+  /// * `output` - The ID of the output node.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the impedance cannot be computed, or a `CircuitSolve`
+  /// error if a node does not exist.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  /// };
+  /// use num::Complex;
+  ///
+  /// // RC divider: the tension across the capacitor is 1 / (1 + iRCω) times
+  /// // that of the source
+  /// let (r, c) = (1e3, 1e-6);
+  /// let mut circuit = Circuit::new();
+  /// circuit
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(r)))
+  ///   .push_serie(Component::from(Capacitor(c)));
+  ///
+  /// let transfer = circuit.transfer_function(&id![], &id![1])?;
+  /// for pulse in [0., 1e2, 1e3, 1e4] {
+  ///   let expected = 1. / Complex::new(1., r * c * pulse);
+  ///   let actual = transfer.eval(Complex::from(pulse));
+  ///   assert!((actual - expected).norm() < 1e-12);
+  /// }
+  ///
+  /// assert!(circuit.transfer_function(&id![], &id![2]).is_err());
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn transfer_function(&mut self, input: &Id, output: &Id) -> Result<RatioFrac<Complex<f64>>>
+  {
+    let mut transfer = self.potential_transfer(output)?;
+    let mut input = self.potential_transfer(input)?;
+    input.inv_inplace();
+    transfer *= &input;
+    transfer.reduce();
+    Ok(transfer)
+  }
+
+  /// Renders the transfer function between the potentials of two nodes as a
+  /// LaTeX `\frac{...}{...}` string, in the Laplace variable s = iω. The
+  /// denominator is made monic, zero coefficients are left out and negligible
+  /// real or imaginary parts of the coefficients are not displayed.
+  ///
+  /// # Arguments
+  ///
+  /// * `from` - The ID of the input node. `&vec![]` stands for the source.
+  /// * `to` - The ID of the output node.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the impedance cannot be computed or if a node does not
+  /// exist.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  /// };
+  ///
+  /// let mut c = Circuit::new();
+  /// c.content_mut()
+  ///   .push_serie(Component::from(Resistor(1e3)))
+  ///   .push_serie(Component::from(Capacitor(1e-6)));
+  ///
+  /// let latex = c.transfer_function_latex(&id![], &id![1u8])?;
+  /// assert_eq!(latex, "\\frac{1000}{s + 1000}");
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn transfer_function_latex(&mut self, from: &Id, to: &Id) -> Result<String>
+  {
+    let transfer = self.transfer_function(from, to)?;
+    let numerator = pulse_to_laplace(&coefficients(&transfer.numerator));
+    let denominator = pulse_to_laplace(&coefficients(&transfer.denominator));
+    let lead = denominator.last().copied().unwrap_or(Complex::from(1f64));
+    let normalize = |coefs: Vec<Complex<f64>>| coefs.iter().map(|c| c / lead).collect::<Vec<_>>();
+    Ok(format!(
+      "\\frac{{{}}}{{{}}}",
+      latex_polynomial(&normalize(numerator)),
+      latex_polynomial(&normalize(denominator))
+    ))
+  }
+
+  /// Computes the DC gain from the source to the potential of a node, that is
+  /// the ratio between the constant potential of the node and a constant
+  /// tension of the source, accounting for the internal impedance of the
+  /// source.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the impedance cannot be computed, if the node does
+  /// not exist or if the gain is not finite (e.g. a DC source shorted by an
+  /// inductor).
+  pub fn dc_gain(&mut self, node_id: &Id) -> Result<f64>
+  {
+    let transfer = self.source_transfer(node_id)?;
+    let gain = DefaultPrecision::eval(&transfer, 0f64);
+    if !gain.is_finite() {
+      return Err(CircuitSolve(format!(
+        "The DC gain of the node of id {node_id:?} is not finite"
+      )));
+    }
+    Ok(gain.re)
+  }
+
+  /// Computes the final value of the potential of a node in response to a step
+  /// of the source from 0 to `amplitude`, once the transient has died out. It
+  /// is the DC gain of the node (see [`Circuit::dc_gain`]) times the amplitude,
+  /// computed exactly instead of read at the end of an emulation that may not
+  /// have settled yet, and is the reference of settling and overshoot
+  /// measurements.
+  ///
+  /// # Errors
+  ///
+  /// Same as [`Circuit::dc_gain`].
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Inductor,
+  ///     Resistor,
+  ///   },
+  /// };
+  ///
+  /// // A damped LC low-pass filter loaded by a resistor settles at the divider
+  /// // ratio of the resistors
+  /// let mut c = Circuit::new();
+  /// c.content_mut()
+  ///   .push_serie(Component::from(Resistor(100.)))
+  ///   .push_serie(Component::from(Inductor(1e-3)))
+  ///   .push_serie(Component::from(Capacitor(1e-6)));
+  /// c.get_comp_by_id_mut(&[2])
+  ///   .unwrap()
+  ///   .push_parallel(Component::from(Resistor(300.)));
+  ///
+  /// assert!((c.step_final_value(&id![2u8], 2.)? - 1.5).abs() < 1e-12);
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  #[inline]
+  pub fn step_final_value(&mut self, node_id: &Id, amplitude: f64) -> Result<f64>
+  {
+    Ok(self.dc_gain(node_id)? * amplitude)
+  }
+
+  /// Computes the DC operating point of the circuit, that is the steady state
+  /// under the zero pulse of the source alone (a zero tension if it has none),
+  /// its other pulses being ignored: they cannot make the computation fail,
+  /// e.g. by a resonance. The sources added with [`Circuit::add_source`] are
+  /// reduced to their zero pulses likewise, and superposed. The capacitors
+  /// behave as open circuits and the inductors as wires. The internal
+  /// impedance of the source is accounted for.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the initialization of the circuit under the zero
+  /// pulse fails, e.g. if a DC source is shorted by an inductor.
+  ///
+  /// # Returns
+  ///
+  /// The DC potential of each node and the DC current flowing through the
+  /// component following it, as `(potential, current)` by node ID.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Inductor,
+  ///     Resistor,
+  ///   },
+  ///   Source,
+  /// };
+  /// use num::Complex;
+  ///
+  /// // A divider whose lower resistor is bypassed by a capacitor, grounded
+  /// // through an inductor, under 10 V and a tone
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(0., Complex::from(10.))
+  ///   .add_tone(50., 1., 0.)
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(1e3)))
+  ///   .push_serie(Component::from(Resistor(3e3)))
+  ///   .push_serie(Component::from(Inductor(1e-3)));
+  /// c.get_comp_by_id_mut(&[1])
+  ///   .unwrap()
+  ///   .push_parallel(Component::from(Capacitor(1e-6)));
+  ///
+  /// let operating_point = c.dc_operating_point()?;
+  /// let close =
+  ///   |(a, b): (f64, f64), (c, d): (f64, f64)| (a - c).abs() < 1e-12 && (b - d).abs() < 1e-12;
+  /// assert!(close(operating_point[&id![]], (10., 2.5e-3)));
+  /// assert!(close(operating_point[&id![1]], (7.5, 2.5e-3)));
+  /// // No current flows through the capacitor...
+  /// assert!(close(operating_point[&id![1, 1]], (7.5, 0.)));
+  /// // ...and there is no tension across the inductor
+  /// assert!(close(operating_point[&id![2]], (0., 2.5e-3)));
+  ///
+  /// // A source added between two resistors, whose tone makes the parallel
+  /// // inductor and capacitor resonate, only adds its 5 V of offset
+  /// let mut extra = Source::new();
+  /// extra
+  ///   .add_pulse(0., Complex::from(5.))
+  ///   .add_pulse(1e5, Complex::from(1.));
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(0., Complex::from(10.))
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(1e3)))
+  ///   .push_serie(Component::from(Resistor(1e3)))
+  ///   .push_serie(Component::from(Inductor(1e-4)));
+  /// c.get_comp_by_id_mut(&[2])
+  ///   .unwrap()
+  ///   .push_parallel(Component::from(Capacitor(1e-6)));
+  /// c.add_source(&id![1], extra)?;
+  ///
+  /// let operating_point = c.dc_operating_point()?;
+  /// assert!(close(operating_point[&id![]], (10., 7.5e-3)));
+  /// assert!(close(operating_point[&id![1]], (7.5, 7.5e-3)));
+  /// assert!(close(operating_point[&id![2]], (0., 7.5e-3)));
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn dc_operating_point(&self) -> Result<HashMap<Id, (f64, f64)>>
+  {
+    let constant = |source: &Source| {
+      let voltage = source
+        .voltages()
+        .find(|(pulse, _)| pulse.is_zero())
+        .map_or(Complex::zero(), |(_, voltage)| *voltage);
+      let mut constant = Source::new();
+      constant.add_pulse(0f64, voltage);
+      constant
+    };
+    let mut source = constant(self.generator());
+    source.internal_impedance = self.generator().internal_impedance.clone();
+    let mut dc = self.clone();
+    dc.sources = self
+      .sources
+      .iter()
+      .map(|(id, source)| (id.clone(), constant(source)))
+      .collect();
+    dc.replace_source(source).init()?;
+    let mut operating_point = HashMap::<Id, (f64, f64)>::new();
+    for node in std::iter::once(&dc)
+      .chain(dc.superposed.iter())
+      .flat_map(|circuit| circuit.nodes())
+    {
+      let (potential, current) = operating_point.entry(node.id.clone()).or_default();
+      *potential += node.potentials[0].re;
+      *current += node.currents[0].re;
+    }
+    Ok(operating_point)
+  }
+
+  /// Computes the frequency response of the circuit at a node, that is the
+  /// complex gain from the source to the potential of the node at each given
+  /// pulse, accounting for the internal impedance of the source. Only the
+  /// impedances are needed, so that the voltages of the source are ignored and
+  /// may not be set. See [`Bode`] for the magnitudes and phases of a Bode
+  /// plot.
+  ///
+  /// # Arguments
+  ///
+  /// * `output` - The ID of the node.
+  /// * `pulses` - The pulses at which the gain is evaluated, in rad/s.
+  ///
+  /// # Returns
+  ///
+  /// The pairs `(pulse, gain)` for each pulse of `pulses`.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the impedance cannot be computed, or a `CircuitSolve`
+  /// error if the node does not exist.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Bode,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  /// };
+  ///
+  /// // RC low-pass filter, whose cutoff pulse is 1 / RC = 1000 rad/s
+  /// let mut c = Circuit::new();
+  /// c.content_mut()
+  ///   .push_serie(Component::from(Resistor(1e3)))
+  ///   .push_serie(Component::from(Capacitor(1e-6)));
+  ///
+  /// let pulses = (0..=40)
+  ///   .map(|k| 10f64.powf(1. + k as f64 / 10.))
+  ///   .collect::<Vec<_>>();
+  /// let response = c.frequency_response(&id![1], &pulses)?;
+  /// let (magnitudes, phases) = (response.magnitudes_db(), response.phases_deg());
+  /// // -3 dB and -45° at the cutoff pulse, the 21st of the sweep
+  /// assert!((magnitudes[20] + 10. * 2f64.log10()).abs() < 1e-9);
+  /// assert!((phases[20] + 45.).abs() < 1e-9);
+  /// // -20 dB per decade above it
+  /// assert!((magnitudes[40] - magnitudes[30] + 20.).abs() < 1e-1);
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn frequency_response(
+    &mut self,
+    output: &Id,
+    pulses: &[f64],
+  ) -> Result<Vec<(f64, Complex<f64>)>>
+  {
+    let transfer = self.source_transfer(output)?;
+    Ok(
+      pulses
+        .iter()
+        .map(|&pulse| (pulse, DefaultPrecision::eval(&transfer, pulse)))
+        .collect(),
+    )
+  }
+
+  /// Measures the gain and the phase shift applied by the circuit to each
+  /// harmonic of the source at a node, read from the spectra computed by
+  /// [`Circuit::init`]. This is the transfer function from the source to the
+  /// potential of the node, sampled at the pulses of the source. Pulses at
+  /// which the source is silent are left out.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the initialization of the circuit fails or if the
+  /// node does not exist.
+  ///
+  /// # Returns
+  ///
+  /// The triples `(pulse, gain, phase)` where the gain is `|V_node| /
+  /// |V_source|` and the phase is in degrees.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  /// };
+  /// use num::Complex;
+  ///
+  /// // At its cutoff pulse, an RC low-pass filter has a gain of 1/√2 and a
+  /// // phase shift of -45°
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(1e3, Complex::from(1.))
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(1e3)))
+  ///   .push_serie(Component::from(Capacitor(1e-6)));
+  ///
+  /// let (pulse, gain, phase) = c.harmonic_response(&id![1u8])?[0];
+  /// assert_eq!(pulse, 1e3);
+  /// assert!((gain - 0.5f64.sqrt()).abs() < 1e-12);
+  /// assert!((phase + 45.).abs() < 1e-9);
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn harmonic_response(&mut self, node_id: &Id) -> Result<Vec<(f64, f64, f64)>>
+  {
+    self.init()?;
+    let node = self
+      .get_node(node_id)
+      .ok_or_else(|| CircuitSolve(format!("Node of id {node_id:?} not found")))?;
+    Ok(
+      self
+        .voltages()
+        .zip(node.potentials.iter())
+        .filter(|((_, voltage), _)| !voltage.is_zero())
+        .map(|((pulse, voltage), potential)| {
+          let ratio = potential / voltage;
+          (*pulse, ratio.norm(), ratio.arg().to_degrees())
+        })
+        .collect(),
+    )
+  }
+
+  /// Gives the phasor of the potential of a node at one of the pulses of the
+  /// source, that is the complex amplitude `V` such that the steady-state
+  /// contribution of this pulse to the potential is `Re(V e^{iωt})`: its
+  /// magnitude is the peak amplitude and its argument the phase at `t = 0`.
+  /// For the zero pulse, it is the (real) DC component. For a complex source,
+  /// whose emulated signals are the real parts of the complex ones, the terms
+  /// of the pulses `ω` and `-ω` add up, and both pulses give the phasor of
+  /// `|ω|`.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the initialization of the circuit fails, if the node
+  /// does not exist or if `pulse` is not a pulse of the source.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  ///   Source,
+  /// };
+  /// use num::Complex;
+  ///
+  /// // A 2 V cosine at the cutoff pulse of an RC low-pass filter
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(1e3, Complex::from(1.))
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(1e3)))
+  ///   .push_serie(Component::from(Capacitor(1e-6)));
+  ///
+  /// let phasor = c.steady_state_phasor(&id![1u8], 1e3)?;
+  /// assert!((phasor.norm() - 2f64.sqrt()).abs() < 1e-12);
+  /// assert!((phasor.arg().to_degrees() + 45.).abs() < 1e-9);
+  /// assert!(c.steady_state_phasor(&id![1u8], 2e3).is_err());
+  ///
+  /// // The same cosine as the real part of a complex source of pulses ±1000
+  /// // rad/s
+  /// let mut source = Source::new();
+  /// source.complex = true;
+  /// source
+  ///   .add_pulse(-1e3, Complex::new(1., 0.5))
+  ///   .add_pulse(1e3, Complex::new(1., 0.5));
+  /// c.replace_source(source);
+  /// for pulse in [-1e3, 1e3] {
+  ///   let phasor = c.steady_state_phasor(&id![1u8], pulse)?;
+  ///   assert!((phasor.norm() - 2f64.sqrt()).abs() < 1e-12);
+  ///   assert!((phasor.arg().to_degrees() + 45.).abs() < 1e-9);
+  /// }
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn steady_state_phasor(&mut self, node_id: &Id, pulse: f64) -> Result<Complex<f64>>
+  {
+    self.init()?;
+    if !self.voltages().any(|(p, _)| *p == pulse) {
+      return Err(CircuitSolve(format!(
+        "The source has no pulse {pulse:?} rad/s"
+      )));
+    }
+    let node = self
+      .get_node(node_id)
+      .ok_or_else(|| CircuitSolve(format!("Node of id {node_id:?} not found")))?;
+    Ok(
+      self
+        .real_phasors(&node.potentials)
+        .into_iter()
+        .find(|(p, _)| *p == pulse.abs())
+        .map_or(Complex::zero(), |(_, phasor)| phasor),
+    )
+  }
+
+  /// Measures how much the circuit attenuates the harmonics of a periodic
+  /// source at a node: the ratio of the power of the harmonics in the potential
+  /// of the node to their power in the tension of the source. A good low-pass
+  /// filter fed a square wave gives a ratio much smaller than 1.
+  ///
+  /// The harmonics are the pulses of the source that are integer multiples `k
+  /// * fundamental_pulse` with `k ≥ 2`, up to a relative tolerance of 1e-6.
+  /// The fundamental itself, the DC component and the pulses that are not
+  /// multiples of the fundamental are left out. The power of a harmonic is
+  /// proportional to the squared magnitude of its coefficient, so the ratio is
+  /// `Σ |V_node(kω)|² / Σ |V_source(kω)|²`.
+  ///
+  /// # Arguments
+  ///
+  /// * `node_id` - The ID of the node.
+  /// * `fundamental_pulse` - The pulse of the fundamental, in rad/s.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the fundamental pulse is not positive and finite, if
+  /// the initialization of the circuit fails, if the node does not exist or if
+  /// the source has no harmonic.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  /// };
+  /// use num::Complex;
+  ///
+  /// // The first odd harmonics of a square wave through an RC low-pass filter
+  /// // whose cutoff is the fundamental
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(1e3, Complex::from(1.))
+  ///   .add_pulse(3e3, Complex::from(1. / 3.))
+  ///   .add_pulse(5e3, Complex::from(1. / 5.))
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(1e3)))
+  ///   .push_serie(Component::from(Capacitor(1e-6)));
+  ///
+  /// assert!(c.harmonic_attenuation(&id![1u8], 1e3)? < 0.1);
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn harmonic_attenuation(&mut self, node_id: &Id, fundamental_pulse: f64) -> Result<f64>
+  {
+    if !(fundamental_pulse > 0f64 && fundamental_pulse.is_finite()) {
+      return Err(CircuitSolve(format!(
+        "The fundamental pulse must be positive and finite, got {fundamental_pulse:?} rad/s"
+      )));
+    }
+    self.init()?;
+    let node = self
+      .get_node(node_id)
+      .ok_or_else(|| CircuitSolve(format!("Node of id {node_id:?} not found")))?;
+    let voltages = self
+      .voltages()
+      .map(|(_, voltage)| *voltage)
+      .collect::<Vec<_>>();
+    let harmonic_power = |spectrum: &[Complex<f64>]| {
+      self
+        .real_phasors(spectrum)
+        .into_iter()
+        .filter(|(pulse, _)| is_harmonic(*pulse, fundamental_pulse))
+        .map(|(_, phasor)| phasor.norm_sqr())
+        .sum::<f64>()
+    };
+    let (node_power, source_power) = (harmonic_power(&node.potentials), harmonic_power(&voltages));
+    if source_power.is_zero() {
+      return Err(CircuitSolve(format!(
+        "The source has no harmonic of the fundamental pulse {fundamental_pulse:?} rad/s"
+      )));
+    }
+    Ok(node_power / source_power)
+  }
+
+  /// Computes the total harmonic distortion of a signal of a node, that is
+  /// the ratio of the RMS value of its harmonics to the RMS value of its
+  /// fundamental, read from the spectra computed by [`Circuit::init`]. The
+  /// fundamental is the positive pulse of the strongest voltage of the source
+  /// (the lowest one in case of a tie), so that the leakage of a source built
+  /// from a function over a window of several of its periods is not taken
+  /// for it, and the harmonics are its integer multiples `k * fundamental` with
+  /// `k ≥ 2`, up to a relative tolerance of 1e-6. The DC component and the
+  /// pulses that are not multiples of the fundamental are left out. The
+  /// sources added with [`Circuit::add_source`] are superposed, as in
+  /// [`Circuit::average_power`].
+  ///
+  /// # Arguments
+  ///
+  /// * `node_id` - The ID of the node.
+  /// * `quantity` - The signal of the node to analyse.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the initialization of the circuit fails, if the node
+  /// does not exist, if the source drives no positive pulse or if the
+  /// fundamental vanishes at the node.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  ///   Quantity,
+  /// };
+  /// use num::Complex;
+  ///
+  /// // A square wave, whose harmonics have amplitudes in 1 / k for odd k, has
+  /// // a THD of √(π² / 8 - 1) across a resistor
+  /// let mut c = Circuit::new();
+  /// for k in (1..2000).step_by(2) {
+  ///   c.add_tone(500. * k as f64, 1. / k as f64, 0.);
+  /// }
+  /// c.content_mut().push_serie(Component::from(Resistor(1e3)));
+  /// let expected = (std::f64::consts::PI.powi(2) / 8. - 1.).sqrt();
+  /// assert!((c.thd(&id![], Quantity::Tension)? - expected).abs() < 1e-2);
+  ///
+  /// // Likewise from its Fourier series over a window of two periods, whose
+  /// // pulses are spaced by half the fundamental
+  /// # #[cfg(feature = "fft")]
+  /// # {
+  /// let square = |t: f64| if t.rem_euclid(1e-3) < 5e-4 { 1. } else { -1. };
+  /// let mut c = Circuit::new();
+  /// c.set_generator_fn(square, 1e-3, 200)
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(1e3)));
+  /// assert!((c.thd(&id![], Quantity::Tension)? - expected).abs() < 1e-2);
+  /// # }
+  ///
+  /// // Its first harmonics across the capacitor of a RC low-pass filter, whose
+  /// // cutoff is the fundamental, are attenuated by |1 + ik|
+  /// let mut c = Circuit::new();
+  /// let mut expected = 0.;
+  /// for k in (1..20).step_by(2) {
+  ///   c.add_tone(
+  ///     1e3 * k as f64 / (2. * std::f64::consts::PI),
+  ///     1. / k as f64,
+  ///     0.,
+  ///   );
+  ///   if k > 1 {
+  ///     expected += 1. / (k * k * (1 + k * k)) as f64;
+  ///   }
+  /// }
+  /// // The fundamental has an amplitude of 1 / √2
+  /// let expected = (2. * expected).sqrt();
+  /// c.content_mut()
+  ///   .push_serie(Component::from(Resistor(1e3)))
+  ///   .push_serie(Component::from(Capacitor(1e-6)));
+  /// assert!((c.thd(&id![1], Quantity::Tension)? - expected).abs() < 1e-9);
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn thd(&mut self, node_id: &Id, quantity: Quantity) -> Result<f64>
+  {
+    self.init()?;
+    let fundamental_pulse = self
+      .superposed_phasors(|circuit| Ok(circuit.voltages().map(|(_, voltage)| *voltage).collect()))?
+      .into_iter()
+      .filter(|(pulse, voltage)| *pulse > 0f64 && !voltage.is_zero())
+      .max_by(|(a, voltage_a), (b, voltage_b)| {
+        voltage_a
+          .norm()
+          .total_cmp(&voltage_b.norm())
+          .then(b.total_cmp(a))
+      })
+      .map(|(pulse, _)| pulse)
+      .ok_or_else(|| CircuitSolve("The source drives no positive pulse".to_string()))?;
+    let phasors = self.superposed_phasors(|circuit| {
+      let node = circuit
+        .get_node(node_id)
+        .ok_or_else(|| CircuitSolve(format!("Node of id {node_id:?} not found")))?;
+      Ok(match quantity {
+        Quantity::Current => node.currents.clone(),
+        Quantity::Tension => node.next_component_tensions.clone(),
+      })
+    })?;
+    let (mut fundamental, mut harmonics) = (0f64, 0f64);
+    for (pulse, phasor) in phasors {
+      if pulse == fundamental_pulse {
+        fundamental = phasor.norm_sqr();
+      } else if is_harmonic(pulse, fundamental_pulse) {
+        harmonics += phasor.norm_sqr();
+      }
+    }
+    if fundamental.is_zero() {
+      return Err(CircuitSolve(format!(
+        "The fundamental pulse {fundamental_pulse:?} rad/s vanishes at the node of id \
+         {node_id:?}"
+      )));
+    }
+    Ok((harmonics / fundamental).sqrt())
+  }
+}
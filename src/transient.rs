@@ -0,0 +1,473 @@
+//! Transient response of a circuit to initial conditions.
+//!
+//! The spectral solution of a circuit is its forced steady state x_f(t). The
+//! total response to given initial conditions is x(t) = x_f(t) + Σ c_k φ_k
+//! exp(i p_k t), where the p_k are the natural (complex) pulses of the circuit
+//! whose source is turned off, i.e. the zeros of its impedance, and φ_k are the
+//! amplitudes of the nodes in the corresponding modes.
+//!
+//! The state of the circuit is made of the tensions of its capacitors and the
+//! currents of its inductors. The weights c_k are found by solving, for each
+//! state variable x_j, Σ c_k φ_k,j = x_j(0) - x_f,j(0). Subtracting the value
+//! of the forced response at t = 0 ensures continuity: the total response at t
+//! = 0 is exactly the specified initial state, instead of counting the initial
+//! value of the forced response twice.
+//!
+//! This exact solution keeps the accuracy of the spectral solution at any time
+//! step, and handles every dipole whose impedance is known, but it requires the
+//! state variables to be independent. The `integrator` module steps the
+//! circuit in the time domain instead (see [`Circuit::transient`]), for the
+//! cases this one cannot handle.
+
+use std::collections::HashMap;
+
+use num::Complex;
+use num_traits::Zero;
+
+use crate::{
+  emulation::EmulationData,
+  error::{
+    Error::{
+      CircuitBuild,
+      CircuitSolve,
+    },
+    Result,
+  },
+  util::{
+    evaluate_zero_without_x,
+    solve_linear,
+  },
+  Circuit,
+  Component,
+  ComponentContent,
+  Dipole,
+  Id,
+};
+
+/// Amplitudes of the current, tension and potential of a node in a mode.
+type ModeAmplitude = (Complex<f64>, Complex<f64>, Complex<f64>);
+
+/// A natural mode of a circuit whose source is turned off. Its contribution to
+/// a quantity of a node at time t is `amplitude * exp(i * pulse * t)`.
+#[derive(Clone, Debug)]
+pub struct TransientMode
+{
+  /// The complex pulse of the mode. Its imaginary part is the damping rate of
+  /// the mode.
+  pub pulse:      Complex<f64>,
+  /// The amplitudes of the current, tension and potential of each node in the
+  /// mode.
+  pub amplitudes: HashMap<Id, ModeAmplitude>,
+}
+
+/// Collects the IDs of the capacitors and inductors of a component, with a flag
+/// telling whether they are capacitors.
+fn collect_states(component: &Component) -> Vec<(Id, bool)>
+{
+  component
+    .iter()
+    .filter_map(|(_, component)| match component.content {
+      ComponentContent::Simple(Dipole::Capacitor(_)) =>
+        Some((component.fore_node_id.clone(), true)),
+      ComponentContent::Simple(Dipole::Inductor(_)) =>
+        Some((component.fore_node_id.clone(), false)),
+      _ => None,
+    })
+    .collect()
+}
+
+/// Checks that the initial conditions of a component do not fix the same
+/// tension or current to different values. See [`Circuit::transient_modes`] for
+/// the flagged topologies.
+fn check_conflicts(
+  component: &Component,
+  initial_conditions: &HashMap<Id, f64>,
+  is_root: bool,
+) -> Result<()>
+{
+  use ComponentContent::*;
+  let initial_value = |component: &Component| {
+    initial_conditions
+      .get(&component.fore_node_id)
+      .copied()
+      .unwrap_or_default()
+  };
+  match &component.content {
+    Series(components) | Parallel(components) => {
+      // Capacitors in parallel share their tension, inductors in series share
+      // their current
+      let is_parallel = matches!(component.content, Parallel(_));
+      let sharing = components
+        .iter()
+        .filter(|component| match component.content {
+          Simple(Dipole::Capacitor(_)) => is_parallel,
+          Simple(Dipole::Inductor(_)) => !is_parallel,
+          _ => false,
+        })
+        .collect::<Vec<_>>();
+      if sharing
+        .windows(2)
+        .any(|pair| initial_value(pair[0]) != initial_value(pair[1]))
+      {
+        return Err(CircuitBuild(format!(
+          "The components of ids {:?} are {} and share their {} but have different initial \
+           conditions",
+          sharing
+            .iter()
+            .map(|component| &component.fore_node_id)
+            .collect::<Vec<_>>(),
+          if is_parallel {
+            "in parallel"
+          } else {
+            "in series"
+          },
+          if is_parallel { "tension" } else { "current" },
+        )));
+      }
+      for child in components.iter() {
+        let is_source_tension = is_root && is_parallel;
+        if is_source_tension
+          && matches!(child.content, Simple(Dipole::Capacitor(_)))
+          && initial_conditions.contains_key(&child.fore_node_id)
+        {
+          return capacitor_on_source(&child.fore_node_id);
+        }
+        check_conflicts(child, initial_conditions, false)?;
+      }
+    },
+    Simple(Dipole::Capacitor(_))
+      if is_root && initial_conditions.contains_key(&component.fore_node_id) =>
+      return capacitor_on_source(&component.fore_node_id),
+    _ => (),
+  }
+  Ok(())
+}
+
+/// Error for a capacitor whose tension is fixed by the source.
+fn capacitor_on_source<T>(id: &Id) -> Result<T>
+{
+  Err(CircuitBuild(format!(
+    "The capacitor of id {id:?} is in parallel with the ideal source, which fixes its tension: \
+     it cannot have an initial condition"
+  )))
+}
+
+/// Computes the amplitudes of the nodes of a component in the mode of a given
+/// complex pulse, the same way `Component::init_current_tension_potential` does
+/// for a real pulse.
+fn propagate_mode(
+  component: &Component,
+  current: Complex<f64>,
+  tension: Complex<f64>,
+  potential: Complex<f64>,
+  pulse: Complex<f64>,
+  amplitudes: &mut HashMap<Id, ModeAmplitude>,
+)
+{
+  amplitudes.insert(
+    component.fore_node_id.clone(),
+    (current, tension, potential),
+  );
+  use ComponentContent::*;
+  match &component.content {
+    Series(components) => {
+      let mut remaining_potential = potential;
+      for component in components.iter() {
+        let next_tension = if current.is_zero() {
+          Complex::zero()
+        } else {
+          current * component.impedance.eval(pulse)
+        };
+        propagate_mode(
+          component,
+          current,
+          next_tension,
+          remaining_potential,
+          pulse,
+          amplitudes,
+        );
+        remaining_potential -= next_tension;
+      }
+    },
+    Parallel(components) => {
+      let mut admittances = components
+        .iter()
+        .map(|component| component.impedance.eval_inv(pulse))
+        .collect::<Vec<_>>();
+      if admittances.iter().any(|admittance| !admittance.is_finite()) {
+        // At a zero pulse, the components behaving as wires take all the current,
+        // in proportion of their "admittance ratio"
+        for (admittance, component) in admittances.iter_mut().zip(components.iter()) {
+          *admittance = if admittance.is_finite() {
+            Complex::zero()
+          } else {
+            evaluate_zero_without_x(&component.impedance).inv()
+          };
+        }
+      }
+      let total_admittance = admittances.iter().sum::<Complex<f64>>();
+      for (component, admittance) in components.iter().zip(admittances) {
+        propagate_mode(
+          component,
+          current * admittance / total_admittance,
+          tension,
+          potential,
+          pulse,
+          amplitudes,
+        );
+      }
+    },
+    _ => (),
+  }
+}
+
+impl Circuit
+{
+  /// Completes the initial conditions stored in the circuit with `overrides`,
+  /// which take precedence.
+  pub(crate) fn merged_initial_conditions(&self, overrides: &HashMap<Id, f64>) -> HashMap<Id, f64>
+  {
+    let mut initial_conditions = self.initial_conditions().clone();
+    initial_conditions.extend(overrides.iter().map(|(id, value)| (id.clone(), *value)));
+    initial_conditions
+  }
+
+  /// Computes the natural modes of the circuit, weighted so that their sum
+  /// added to the forced (spectral) solution reproduces the given initial
+  /// conditions at t = 0. See the module documentation for the details.
+  ///
+  /// # Arguments
+  ///
+  /// * `initial_conditions` - A map from the IDs of capacitors to their
+  ///   tensions and from the IDs of inductors to their currents at t = 0. It
+  ///   completes and overrides the initial conditions stored in the circuit
+  ///   (see [`Circuit::set_initial_voltage`]). Missing capacitors and inductors
+  ///   are considered uncharged.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the initialization of the circuit fails, if an ID of
+  /// `initial_conditions` is neither a capacitor nor an inductor, or if the
+  /// state variables of the circuit are not independent (e.g. a capacitor
+  /// directly fed by the source, or an `Equivalent` dipole with a reactive
+  /// behaviour), case in which the number of natural modes does not match the
+  /// number of state variables.
+  ///
+  /// A `CircuitBuild` error is returned beforehand if the initial conditions
+  /// fix a same quantity to different values, which would otherwise be
+  /// silently resolved by the least inconsistent modes. The flagged topologies
+  /// are:
+  /// - capacitors that are direct children of the same parallel component with
+  ///   different initial tensions,
+  /// - inductors that are direct children of the same series component with
+  ///   different initial currents,
+  /// - a capacitor with an initial condition that is the main component or a
+  ///   direct child of a parallel main component, whose tension is fixed by the
+  ///   ideal source.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use std::collections::HashMap;
+  ///
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  ///   Error,
+  /// };
+  /// use num::Complex;
+  ///
+  /// // Two capacitors in parallel behind a resistor have a single mode
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(0., Complex::from(1.))
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(1e3)))
+  ///   .push_serie(Component::from(Capacitor(1e-6)));
+  /// c.get_comp_by_id_mut(&[1])
+  ///   .unwrap()
+  ///   .push_parallel(Component::from(Capacitor(2e-6)));
+  ///
+  /// // Different tensions are reported as such...
+  /// let conflicting = HashMap::from([(id![1, 0], 1.), (id![1, 1], 2.)]);
+  /// assert!(matches!(
+  ///   c.transient_modes(&conflicting),
+  ///   Err(Error::CircuitBuild(_))
+  /// ));
+  /// // ...while the dependent state variables are rejected anyway
+  /// let consistent = HashMap::from([(id![1, 0], 1.), (id![1, 1], 1.)]);
+  /// assert!(matches!(
+  ///   c.transient_modes(&consistent),
+  ///   Err(Error::CircuitSolve(_))
+  /// ));
+  /// ```
+  pub fn transient_modes(
+    &mut self,
+    initial_conditions: &HashMap<Id, f64>,
+  ) -> Result<Vec<TransientMode>>
+  {
+    self.init()?;
+    let initial_conditions = &self.merged_initial_conditions(initial_conditions);
+    let states = collect_states(self.content());
+    for id in initial_conditions.keys() {
+      if !states.iter().any(|(state_id, _)| state_id == id) {
+        return Err(CircuitBuild(format!(
+          "Component of id {id:?} is neither a capacitor nor an inductor and cannot have an \
+           initial condition"
+        )));
+      }
+    }
+
+    // The conflicts are more specific than the mismatch of the number of
+    // modes, which they always entail, hence checked first
+    check_conflicts(self.content(), initial_conditions, true)?;
+
+    let pulses = self.impedance_zeros()?;
+    if pulses.len() != states.len() {
+      return Err(CircuitSolve(format!(
+        "The circuit has {} capacitors and inductors but {} natural modes: their tensions and \
+         currents are not independent",
+        states.len(),
+        pulses.len()
+      )));
+    }
+    let mut modes = pulses
+      .into_iter()
+      .map(|pulse| {
+        let mut amplitudes = HashMap::new();
+        propagate_mode(
+          self.content(),
+          Complex::from(1f64),
+          Complex::zero(),
+          Complex::zero(),
+          pulse,
+          &mut amplitudes,
+        );
+        TransientMode { pulse, amplitudes }
+      })
+      .collect::<Vec<_>>();
+
+    let matrix = states
+      .iter()
+      .map(|(id, is_capacitor)| {
+        modes
+          .iter()
+          .map(|mode| {
+            let (current, tension, _) = mode.amplitudes[id];
+            if *is_capacitor {
+              tension
+            } else {
+              current
+            }
+          })
+          .collect()
+      })
+      .collect();
+    let rhs = states
+      .iter()
+      .map(|(id, is_capacitor)| {
+        // The forced value at t = 0, the added sources being superposed
+        let forced = std::iter::once(&*self)
+          .chain(self.superposed.iter())
+          .map(|circuit| {
+            let node = circuit.get_node(id).expect("Node not found :/");
+            let spectrum = if *is_capacitor {
+              &node.next_component_tensions
+            } else {
+              &node.currents
+            };
+            circuit.reconstruct(spectrum, 0f64)
+          })
+          .sum::<f64>();
+        let initial_value = initial_conditions.get(id).copied().unwrap_or_default();
+        Complex::from(initial_value - forced)
+      })
+      .collect();
+    let weights = solve_linear(matrix, rhs).ok_or_else(|| {
+      CircuitSolve(
+        "The natural modes of the circuit cannot reproduce its initial state".to_string(),
+      )
+    })?;
+
+    for (mode, weight) in modes.iter_mut().zip(weights) {
+      for (current, tension, potential) in mode.amplitudes.values_mut() {
+        *current *= weight;
+        *tension *= weight;
+        *potential *= weight;
+      }
+    }
+    Ok(modes)
+  }
+
+  /// Emulates a node of the circuit like [`Circuit::emulate_one`], adding the
+  /// transient response to the given initial conditions to the forced
+  /// response. See [`Circuit::transient_modes`] for the meaning of
+  /// `initial_conditions`.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the transient modes cannot be computed or if the
+  /// emulation fails.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use std::collections::HashMap;
+  ///
+  /// use circuits_simulator::{
+  ///   id,
+  ///   Circuit,
+  ///   Component,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Resistor,
+  ///   },
+  /// };
+  /// use num::Complex;
+  ///
+  /// // A capacitor charged at -1 V is fed with a constant 1 V tension
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(0., Complex::from(1.))
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(1e3)))
+  ///   .push_serie(Component::from(Capacitor(1e-7)));
+  ///
+  /// let initial_conditions = HashMap::from([(id![1u8], -1.)]);
+  /// let (_, tensions, _) =
+  ///   c.emulate_one_with_initial_conditions(1e-3, 1e-5, &id![1u8], &initial_conditions)?;
+  /// assert!((tensions[0] + 1.).abs() < 1e-9);
+  /// # Ok::<(), circuits_simulator::Error>(())
+  /// ```
+  pub fn emulate_one_with_initial_conditions(
+    &mut self,
+    duration: f64,
+    step: f64,
+    node_id: &Id,
+    initial_conditions: &HashMap<Id, f64>,
+  ) -> Result<EmulationData>
+  {
+    let modes = self.transient_modes(initial_conditions)?;
+    let (mut currents, mut tensions, mut potentials) = self.emulate_one(duration, step, node_id)?;
+    for mode in modes.iter() {
+      let (current, tension, mut potential) = mode.amplitudes[node_id];
+      if let Some(ground) = self.ground() {
+        potential -= mode.amplitudes[ground].2;
+      }
+      let samples = currents
+        .iter_mut()
+        .zip(tensions.iter_mut())
+        .zip(potentials.iter_mut());
+      for (k, ((i, v), p)) in samples.enumerate() {
+        let factor = (Complex::<f64>::i() * mode.pulse * (k as f64 * step)).exp();
+        *i += (current * factor).re;
+        *v += (tension * factor).re;
+        *p += (potential * factor).re;
+      }
+    }
+    Ok((currents, tensions, potentials))
+  }
+}
@@ -0,0 +1,74 @@
+use num_traits::Zero;
+
+/// SI prefixes with their powers of ten, from the smallest to the largest.
+const PREFIXES: [(i32, &str); 9] = [
+  (-12, "p"),
+  (-9, "n"),
+  (-6, "µ"),
+  (-3, "m"),
+  (0, ""),
+  (3, "k"),
+  (6, "M"),
+  (9, "G"),
+  (12, "T"),
+];
+
+/// Scales samples in base SI units (amperes, volts...) for display, picking
+/// the SI prefix that brings their peak magnitude between 1 and 1000. The
+/// emulation results themselves are always in base units.
+///
+/// # Returns
+///
+/// Returns the scaled samples and the prefix of their unit (e.g. `"µ"`, to be
+/// followed by `"A"` or `"V"` in an axis label). Samples that are all zero,
+/// or among which one is not finite (e.g. NaN), are left unscaled.
+///
+/// # Example
+///
+/// ```
+/// use circuits_simulator::scale_for_display;
+///
+/// let (scaled, prefix) = scale_for_display(&[1.2e-6, -3.4e-6]);
+/// assert_eq!(prefix, "µ");
+/// assert!((scaled[1] + 3.4).abs() < 1e-12);
+///
+/// let (_, prefix) = scale_for_display(&[1.2e-6, f64::NAN]);
+/// assert_eq!(prefix, "");
+/// ```
+pub fn scale_for_display(samples: &[f64]) -> (Vec<f64>, &'static str)
+{
+  let peak = samples.iter().fold(0f64, |acc, x| acc.max(x.abs()));
+  // The peak ignores NaN samples, hence the check of each sample
+  if peak.is_zero() || !samples.iter().all(|x| x.is_finite()) {
+    return (samples.to_vec(), "");
+  }
+  let exponent = peak.log10().floor() as i32;
+  let (power, prefix) = PREFIXES
+    .iter()
+    .rev()
+    .find(|(power, _)| *power <= exponent)
+    .unwrap_or(&PREFIXES[0]);
+  let factor = 10f64.powi(-power);
+  (samples.iter().map(|x| x * factor).collect(), prefix)
+}
+
+/// Formats a value in base SI units with the SI prefix that brings it between 1
+/// and 1000 and at most 4 decimals, e.g. `"10n"` for `1e-8`. Zero and values
+/// that are not finite are left unscaled.
+pub(crate) fn format_si(value: f64) -> String
+{
+  if value.is_zero() || !value.is_finite() {
+    return format!("{value}");
+  }
+  let exponent = (value.abs().log10().floor() as i32).div_euclid(3) * 3;
+  let mut index = PREFIXES
+    .iter()
+    .position(|(power, _)| *power == exponent)
+    .unwrap_or(if exponent < 0 { 0 } else { PREFIXES.len() - 1 });
+  let round = |index: usize| (value * 10f64.powi(-PREFIXES[index].0) * 1e4).round() / 1e4;
+  // The rounding may bring the mantissa up to 1000
+  if round(index).abs() >= 1e3 && index + 1 < PREFIXES.len() {
+    index += 1;
+  }
+  format!("{}{}", round(index), PREFIXES[index].1)
+}
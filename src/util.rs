@@ -1,6 +1,8 @@
 #![allow(dead_code)]
 use fractios::RatioFrac;
+use num::Complex;
 use num_traits::Zero;
+use polyx::Polynomial;
 
 // Note that an impedance is never a multiple of x² or 1/x², where x is the
 // pulse. In particular, a component behaves as a wire under a constant tension
@@ -20,6 +22,27 @@ pub(crate) fn is_multiple_of_invx<T: Zero + Clone>(r: &RatioFrac<T>) -> bool
   r.denominator[0].is_zero()
 }
 
+/// Returns `true` if a complex number is zero up to an absolute tolerance.
+#[inline]
+pub(crate) fn is_negligible(x: Complex<f64>, tolerance: f64) -> bool
+{
+  x.norm() <= tolerance
+}
+/// Same as [`is_multiple_of_x`], up to an absolute tolerance on the constant
+/// coefficient of the numerator.
+#[inline]
+pub(crate) fn is_multiple_of_x_within(r: &RatioFrac<Complex<f64>>, tolerance: f64) -> bool
+{
+  is_negligible(r.numerator[0], tolerance)
+}
+/// Same as [`is_multiple_of_invx`], up to an absolute tolerance on the
+/// constant coefficient of the denominator.
+#[inline]
+pub(crate) fn is_multiple_of_invx_within(r: &RatioFrac<Complex<f64>>, tolerance: f64) -> bool
+{
+  is_negligible(r.denominator[0], tolerance)
+}
+
 #[inline]
 pub(crate) fn evaluate_zero_without_x<T>(r: &RatioFrac<T>) -> T
 where
@@ -34,3 +57,190 @@ where
 {
   r.numerator[0].clone() / r.denominator[1].clone()
 }
+
+/// Returns the coefficients of a polynomial, lowest order first, without the
+/// trailing (highest order) zeros.
+pub(crate) fn coefficients<T: Zero + Clone>(p: &Polynomial<T>) -> Vec<T>
+{
+  let mut coefs = p.iter().cloned().collect::<Vec<_>>();
+  while coefs.last().is_some_and(|c| c.is_zero()) {
+    coefs.pop();
+  }
+  coefs
+}
+
+/// Returns `true` if the fraction does not depend on its variable.
+#[inline]
+pub(crate) fn is_constant<T: Zero + Clone>(r: &RatioFrac<T>) -> bool
+{
+  coefficients(&r.numerator).len() <= 1 && coefficients(&r.denominator).len() <= 1
+}
+
+/// Evaluates a polynomial given by its coefficients (lowest order first) with
+/// Horner's method.
+#[inline]
+pub(crate) fn horner(coefs: &[Complex<f64>], x: Complex<f64>) -> Complex<f64>
+{
+  coefs
+    .iter()
+    .rev()
+    .fold(Complex::zero(), |acc, c| acc * x + c)
+}
+
+const ROOTS_MAX_ITER: usize = 1000;
+const ROOTS_TOLERANCE: f64 = 1e-12;
+
+/// Finds all the complex roots of a polynomial given by its coefficients
+/// (lowest order first) using the Durand-Kerner method. Roots at zero are
+/// factored out beforehand so that they are exact. Returns `None` if the method
+/// does not converge, and an empty vector for constant polynomials.
+pub(crate) fn roots(coefs: &[Complex<f64>]) -> Option<Vec<Complex<f64>>>
+{
+  let mut end = coefs.len();
+  while end > 0 && coefs[end - 1].is_zero() {
+    end -= 1;
+  }
+  let nb_zero_roots = coefs[..end].iter().take_while(|c| c.is_zero()).count();
+  let coefs = &coefs[nb_zero_roots..end];
+  let mut result = vec![Complex::zero(); nb_zero_roots];
+  if coefs.len() < 2 {
+    return Some(result);
+  }
+
+  let degree = coefs.len() - 1;
+  let lead = coefs[degree];
+  let monic = coefs.iter().map(|c| c / lead).collect::<Vec<_>>();
+  // Cauchy's bound on the modulus of the roots
+  let radius = 1f64
+    + monic[..degree]
+      .iter()
+      .map(|c| c.norm())
+      .fold(0f64, f64::max);
+  let mut approx = (0..degree)
+    .map(|k| {
+      Complex::from_polar(
+        radius,
+        0.4 + 2. * std::f64::consts::PI * k as f64 / degree as f64,
+      )
+    })
+    .collect::<Vec<_>>();
+
+  for _ in 0..ROOTS_MAX_ITER {
+    let mut max_delta = 0f64;
+    for i in 0..degree {
+      let zi = approx[i];
+      let mut denominator = Complex::from(1f64);
+      for (j, zj) in approx.iter().enumerate() {
+        if j != i {
+          denominator *= zi - zj;
+        }
+      }
+      let delta = horner(&monic, zi) / denominator;
+      if !delta.is_finite() {
+        return None;
+      }
+      approx[i] -= delta;
+      max_delta = max_delta.max(delta.norm() / (1f64 + approx[i].norm()));
+    }
+    if max_delta < ROOTS_TOLERANCE {
+      result.extend(approx);
+      return Some(result);
+    }
+  }
+  None
+}
+
+/// Solves the linear system `matrix * x = rhs` using Gaussian elimination with
+/// partial pivoting. `matrix` is given as a vector of rows. Returns `None` if
+/// the matrix is singular.
+pub(crate) fn solve_linear(
+  mut matrix: Vec<Vec<Complex<f64>>>,
+  mut rhs: Vec<Complex<f64>>,
+) -> Option<Vec<Complex<f64>>>
+{
+  let n = rhs.len();
+  for col in 0..n {
+    let pivot =
+      (col..n).max_by(|&a, &b| matrix[a][col].norm().total_cmp(&matrix[b][col].norm()))?;
+    if matrix[pivot][col].is_zero() {
+      return None;
+    }
+    matrix.swap(col, pivot);
+    rhs.swap(col, pivot);
+    let (upper, lower) = matrix.split_at_mut(col + 1);
+    let pivot_row = &upper[col];
+    let pivot_rhs = rhs[col];
+    for (line, value) in lower.iter_mut().zip(rhs[col + 1..].iter_mut()) {
+      let factor = line[col] / pivot_row[col];
+      for (x, p) in line[col..].iter_mut().zip(pivot_row[col..].iter()) {
+        *x -= factor * p;
+      }
+      *value -= factor * pivot_rhs;
+    }
+  }
+  let mut solution = vec![Complex::zero(); n];
+  for row in (0..n).rev() {
+    let known = matrix[row][row + 1..]
+      .iter()
+      .zip(solution[row + 1..].iter())
+      .map(|(a, x)| a * x)
+      .sum::<Complex<f64>>();
+    solution[row] = (rhs[row] - known) / matrix[row][row];
+  }
+  Some(solution)
+}
+
+/// Relative tolerance under which a pulse is considered to be a root of the
+/// numerator or denominator of an impedance.
+pub(crate) const ROOT_TOLERANCE: f64 = 1e-9;
+
+/// Tells whether `x` is a root of a polynomial, that is whether its value at
+/// `x` is negligible compared to the magnitudes of its terms.
+pub(crate) fn is_near_root(p: &Polynomial<Complex<f64>>, x: Complex<f64>, tolerance: f64) -> bool
+{
+  let coefs = coefficients(p);
+  let scale = coefs
+    .iter()
+    .rev()
+    .fold(0f64, |acc, c| acc * x.norm() + c.norm());
+  horner(&coefs, x).norm() <= tolerance * scale
+}
+
+/// A small SplitMix64 pseudo-random generator, enough to draw reproducible
+/// phases without depending on an external crate.
+pub(crate) struct SplitMix64(u64);
+
+impl SplitMix64
+{
+  #[inline]
+  pub(crate) fn new(seed: u64) -> Self
+  {
+    Self(seed)
+  }
+
+  pub(crate) fn next_u64(&mut self) -> u64
+  {
+    self.0 = self.0.wrapping_add(0x9E3779B97F4A7C15);
+    let mut z = self.0;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+  }
+
+  /// Draws a number uniformly in [0, 1).
+  #[inline]
+  pub(crate) fn next_f64(&mut self) -> f64
+  {
+    (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+  }
+
+  /// Draws a number from the standard normal distribution, with the
+  /// Box-Muller transform.
+  #[inline]
+  pub(crate) fn next_gaussian(&mut self) -> f64
+  {
+    // 1 - u lies in (0, 1], whose logarithm is finite
+    let radius = (-2f64 * (1f64 - self.next_f64()).ln()).sqrt();
+    radius * (2f64 * std::f64::consts::PI * self.next_f64()).cos()
+  }
+}
@@ -0,0 +1,137 @@
+use crate::{
+  error::{
+    Error::CircuitBuild,
+    Result,
+  },
+  Circuit,
+  Component,
+  ComponentContent,
+  Dipole,
+  Id,
+};
+
+/// Collects the structural problems of a component and of its subcomponents,
+/// `id` being the ID of the component relative to the root of the circuit.
+pub(crate) fn collect_problems(component: &Component, id: &Id, problems: &mut Vec<String>)
+{
+  use ComponentContent::*;
+  let mut check_value = |name: &str, value: f64| {
+    if value < 0f64 || value.is_nan() {
+      problems.push(format!(
+        "The {name} of id {id:?} has a negative or undefined value: {value}"
+      ));
+    }
+  };
+  match &component.content {
+    Series(components) | Parallel(components) => {
+      if components.is_empty() {
+        problems.push(format!("The combination of id {id:?} is empty"));
+      }
+      for (k, component) in components.iter().enumerate() {
+        let mut sub_id = id.clone();
+        sub_id.push(k as u8);
+        collect_problems(component, &sub_id, problems);
+      }
+    },
+    Simple(Dipole::Resistor(r)) => check_value("resistor", *r),
+    Simple(Dipole::Capacitor(c)) => check_value("capacitor", *c),
+    Simple(Dipole::Inductor(l)) => check_value("inductor", *l),
+    Simple(Dipole::Transformer { ratio, load }) => {
+      check_value("transformer", *ratio);
+      // The load of a transformer has no ID of its own
+      collect_problems(load, id, problems);
+    },
+    Simple(Dipole::Vcvs { load, .. }) => {
+      let mut load_id = id.clone();
+      load_id.push(0u8);
+      collect_problems(load, &load_id, problems);
+    },
+    Simple(Dipole::Poisoned) => problems.push(format!("The dipole of id {id:?} is poisoned")),
+    Simple(Dipole::Short | Dipole::Open | Dipole::Equivalent(_)) => (),
+    Poisoned => problems.push(format!("The component of id {id:?} is poisoned")),
+  }
+}
+
+/// Returns an error listing the problems of a circuit, one per line, if any.
+pub(crate) fn report(problems: Vec<String>) -> Result<()>
+{
+  if problems.is_empty() {
+    Ok(())
+  } else {
+    Err(CircuitBuild(format!(
+      "The circuit has {} problem{}:\n{}",
+      problems.len(),
+      if problems.len() > 1 { "s" } else { "" },
+      problems.join("\n")
+    )))
+  }
+}
+
+impl Circuit
+{
+  /// Checks the structure of the circuit without initializing it, which is
+  /// much cheaper than [`Circuit::init`] and reports the problems that would
+  /// otherwise make it fail or panic deep in the computation of the
+  /// impedances: poisoned components or dipoles, empty series or parallel
+  /// combinations, negative (or NaN) values of resistors, capacitors,
+  /// inductors and transformer ratios, and a source without any pulse. The
+  /// IDs in the report are relative to the root of the circuit.
+  ///
+  /// # Errors
+  ///
+  /// Returns a `CircuitBuild` error listing all the problems found, one per
+  /// line.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use circuits_simulator::{
+  ///   Circuit,
+  ///   Component,
+  ///   ComponentContent,
+  ///   Dipole::{
+  ///     Capacitor,
+  ///     Inductor,
+  ///     Poisoned,
+  ///     Resistor,
+  ///   },
+  /// };
+  /// use num::Complex;
+  ///
+  /// let mut c = Circuit::new();
+  /// c.add_pulse(0., Complex::from(1.))
+  ///   .content_mut()
+  ///   .push_serie(Component::from(Resistor(100.)))
+  ///   .push_serie(Component::from(Capacitor(1e-6)));
+  /// assert!(c.validate().is_ok());
+  ///
+  /// // A negative value
+  /// c.content_mut().push_serie(Component::from(Inductor(-1e-3)));
+  /// let message = c.validate().unwrap_err().to_string();
+  /// assert!(message.contains("inductor of id [2]"));
+  ///
+  /// // A poisoned dipole and an empty combination are reported along with it
+  /// c.content_mut()
+  ///   .push_serie(Component::from(Poisoned))
+  ///   .push_serie(Component::from(ComponentContent::Parallel(vec![])));
+  /// let message = c.validate().unwrap_err().to_string();
+  /// assert!(message.contains("3 problems"));
+  /// assert!(message.contains("inductor of id [2]"));
+  /// assert!(message.contains("dipole of id [3] is poisoned"));
+  /// assert!(message.contains("combination of id [4] is empty"));
+  ///
+  /// // An empty circuit, without any pulse
+  /// let message = Circuit::new().validate().unwrap_err().to_string();
+  /// assert!(message.contains("component of id [] is poisoned"));
+  /// assert!(message.contains("source has no pulse"));
+  /// ```
+  pub fn validate(&self) -> Result<()>
+  {
+    let mut problems = Vec::new();
+    collect_problems(self.content(), &vec![], &mut problems);
+    if self.voltages().next().is_none() {
+      problems.push("The source has no pulse".to_string());
+    }
+    report(problems)
+  }
+}